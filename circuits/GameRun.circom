@@ -1,5 +1,8 @@
 // Cosmic Coder - ZK run attestation (BN254 / Groth16).
-// Binds: run_hash (hi/lo), score, wave, nonce, season_id, used_zk_weapon.
+// Binds: run_hash (hi/lo), score, wave, nonce, season_id, used_zk_weapon,
+//        player commitment (hi/lo) and contract id commitment (hi/lo).
+// Commitments are sha256(ScVal XDR of the Address) split into 128-bit halves
+// (zk_types::address_commitment); submit_zk recomputes them from the caller and itself.
 // Enforces: score >= wave * MIN_SCORE_PER_WAVE (5).
 // used_zk_weapon: 0 = no ZK weapon used, 1 = ZK Plasma Rifle used.
 pragma circom 2.1.4;
@@ -14,6 +17,10 @@ template GameRun() {
     signal input nonce;            // u64
     signal input season_id;        // u32
     signal input used_zk_weapon;   // 0 or 1 (boolean flag for ZK Plasma Rifle)
+    signal input player_address_hi; // high 128 bits of player commitment
+    signal input player_address_lo; // low 128 bits of player commitment
+    signal input contract_id_hi;    // high 128 bits of contract id commitment
+    signal input contract_id_lo;    // low 128 bits of contract id commitment
 
     // Enforce game rule: score >= wave * 5 (MIN_SCORE_PER_WAVE)
     signal minScore;
@@ -28,8 +35,9 @@ template GameRun() {
     weaponCheck <== used_zk_weapon * (used_zk_weapon - 1);
     weaponCheck === 0;
 
-    // Expose as public outputs so verifier gets 7 pub signals
-    // Order: [run_hash_hi, run_hash_lo, score, wave, nonce, season_id, used_zk_weapon]
+    // Expose as public outputs so verifier gets 11 pub signals
+    // Order: [run_hash_hi, run_hash_lo, score, wave, nonce, season_id, used_zk_weapon,
+    //         player_address_hi, player_address_lo, contract_id_hi, contract_id_lo]
     signal output run_hash_hi_out <== run_hash_hi;
     signal output run_hash_lo_out <== run_hash_lo;
     signal output score_out <== score;
@@ -37,6 +45,10 @@ template GameRun() {
     signal output nonce_out <== nonce;
     signal output season_id_out <== season_id;
     signal output used_zk_weapon_out <== used_zk_weapon;
+    signal output player_address_hi_out <== player_address_hi;
+    signal output player_address_lo_out <== player_address_lo;
+    signal output contract_id_hi_out <== contract_id_hi;
+    signal output contract_id_lo_out <== contract_id_lo;
 }
 
 component main = GameRun();
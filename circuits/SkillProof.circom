@@ -1,7 +1,8 @@
 // SkillProof - ZK circuit for weapon unlock verification
 // Proves: score >= threshold without revealing exact score
 // Uses Poseidon hash to bind wallet + score + nonce
-// Public inputs: threshold, publicHash (in order)
+// Binds: player address commitment (hi/lo), so a proof only unlocks for that player
+// Public inputs: threshold, publicHash, player_hi, player_lo (in order)
 pragma circom 2.1.4;
 
 include "../node_modules/circomlib/circuits/comparators.circom";
@@ -16,6 +17,8 @@ template SkillProof() {
     // Public inputs (declared as outputs for Groth16 public signals)
     signal input threshold;    // u32 - minimum score required (public)
     signal input publicHash;   // field element - Poseidon(wallet, score, nonce) (public)
+    signal input player_hi;    // high 128 bits of sha256(player address XDR) (public)
+    signal input player_lo;    // low 128 bits of sha256(player address XDR) (public)
     
     // 1. Verify score >= threshold
     component gte = GreaterEqThan(32);
@@ -31,7 +34,11 @@ template SkillProof() {
     
     // 3. Verify computed hash matches publicHash
     poseidon.out === publicHash;
+
+    // 4. Keep the player commitment in a constraint so it is part of the statement
+    signal player_sq;
+    player_sq <== player_hi * player_lo;
 }
 
-// Public inputs: threshold, publicHash
-component main {public [threshold, publicHash]} = SkillProof();
+// Public inputs: threshold, publicHash, player_hi, player_lo
+component main {public [threshold, publicHash, player_hi, player_lo]} = SkillProof();
@@ -7,9 +7,7 @@
 use soroban_sdk::{
     contract, contractimpl, contracterror, contracttype, Address, Env, IntoVal, Symbol, Vec,
 };
-use zk_types::Groth16Error;
-use policy::{DomainBinding, PolicyError};
-use zk_types::ZkPublicInputs;
+use zk_types::{DomainBinding, PolicyError, ZkPublicInputs};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -18,6 +16,8 @@ pub enum ApplicationError {
     PolicyNotSet = 1,
     PolicyRejected = 2,
     InvalidInput = 3,
+    NotInitialized = 4,
+    AlreadyInitialized = 5,
 }
 
 #[contracttype]
@@ -28,35 +28,71 @@ pub struct LeaderboardEntry {
     pub score: u32,
 }
 
+/// Result a player claims for a proven run; `submit_proof` checks it against the proof.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimedRun {
+    pub score: u32,
+    pub wave: u32,
+    pub season_id: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LeaderboardKey {
     pub season_id: u32,
 }
 
+fn require_admin(env: &Env) -> Result<(), ApplicationError> {
+    let admin: Address = env
+        .storage()
+        .persistent()
+        .get(&Symbol::new(env, "Admin"))
+        .ok_or(ApplicationError::NotInitialized)?;
+    admin.require_auth();
+    Ok(())
+}
+
 #[contract]
 pub struct Application;
 
 #[contractimpl]
 impl Application {
-    /// Set policy contract address.
-    pub fn set_policy(env: Env, policy: Address) {
+    /// Set the admin allowed to configure the application. Once only; `admin` must authorize.
+    pub fn init(env: Env, admin: Address) -> Result<(), ApplicationError> {
+        let key = Symbol::new(&env, "Admin");
+        if env.storage().persistent().has(&key) {
+            return Err(ApplicationError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().persistent().set(&key, &admin);
+        Ok(())
+    }
+
+    /// Set policy contract address. Admin only.
+    pub fn set_policy(env: Env, policy: Address) -> Result<(), ApplicationError> {
+        require_admin(&env)?;
         env.storage().persistent().set(&Symbol::new(&env, "Policy"), &policy);
+        Ok(())
     }
 
     /// Submit proof: calls policy.validate, then updates leaderboard if approved.
-    /// Emits ZkRunSubmitted on success.
+    /// `run.season_id` must equal the proven `public_inputs.season_id` (InvalidInput
+    /// otherwise); the policy checks score and wave. Emits ZkRunSubmitted on success.
     pub fn submit_proof(
         env: Env,
         domain: DomainBinding,
         proof: soroban_sdk::BytesN<256>,
         public_inputs: ZkPublicInputs,
         vk_hash: soroban_sdk::BytesN<32>,
-        // Semantic fields
-        score: u32,
-        wave: u32,
-        season_id: u32,
+        run: ClaimedRun,
     ) -> Result<(), ApplicationError> {
+        let ClaimedRun { score, wave, season_id } = run;
+        // The leaderboard is keyed by season_id, so it must be the proven one.
+        if season_id != public_inputs.season_id {
+            return Err(ApplicationError::InvalidInput);
+        }
+
         // 1) Call policy contract
         let policy: Address = match env.storage().persistent().get::<Symbol, Address>(&Symbol::new(&env, "Policy")) {
             Some(a) => a,
@@ -78,14 +114,14 @@ impl Application {
         );
         match raw {
             Ok(Ok(())) => {}
-            Ok(Err(e)) => {
+            Err(Ok(e)) => {
                 env.events().publish(
                     (Symbol::new(&env, "app"), Symbol::new(&env, "policy_reject")),
                     (domain.player_address, domain.challenge_id, e as u32),
                 );
                 return Err(ApplicationError::PolicyRejected);
             }
-            Err(_) => return Err(ApplicationError::PolicyRejected),
+            _ => return Err(ApplicationError::PolicyRejected),
         }
 
         // 2) Update leaderboard (only if higher score for this player in this season)
@@ -0,0 +1,244 @@
+//! Integration test for the v2 stack: Verifier (VK by hash) -> Policy -> Application leaderboard.
+
+#![cfg(test)]
+extern crate std;
+
+use policy::testutils::v2_run;
+use policy::{derive_domain_separator, Policy, PolicyClient};
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    Address, BytesN, Env, IntoVal, Symbol,
+};
+use verifier::testutils::new_verifier;
+use zk_types::testutils::{packed_proof, v2_fixture, zero_vk, V2_CONTRACT};
+use zk_types::{address_digest, DomainBinding, ZkPublicInputs};
+
+use crate::{Application, ApplicationClient, ApplicationError, ClaimedRun, LeaderboardEntry};
+
+/// Deploy and wire all three contracts; register the VK. Returns (application, policy, vk_hash).
+fn setup_stack(env: &Env) -> (ApplicationClient<'_>, PolicyClient<'_>, BytesN<32>) {
+    let verifier = new_verifier(env);
+    let vk = zero_vk(env, 13);
+    let vk_hash = verifier.store_vk(&vk);
+
+    let policy = PolicyClient::new(env, &env.register(Policy, ()));
+    policy.init(&Address::generate(env));
+    policy.set_verifier(&verifier.address);
+
+    let app = ApplicationClient::new(env, &env.register(Application, ()));
+    app.init(&Address::generate(env));
+    app.set_policy(&policy.address);
+    policy.set_application(&app.address);
+    (app, policy, vk_hash)
+}
+
+/// setup_stack without the policy client.
+fn setup(env: &Env) -> (ApplicationClient<'_>, BytesN<32>) {
+    let (app, _, vk_hash) = setup_stack(env);
+    (app, vk_hash)
+}
+
+/// Domain and public inputs for a run submitted to `app`.
+fn run(
+    env: &Env,
+    app: &Address,
+    player: &Address,
+    nonce: u64,
+    score: u32,
+    wave: u32,
+) -> (DomainBinding, ZkPublicInputs) {
+    let mut domain = DomainBinding {
+        challenge_id: 1,
+        player_address: player.clone(),
+        nonce,
+        contract_id: app.clone(),
+        domain_separator: BytesN::from_array(env, &[0u8; 32]),
+    };
+    domain.domain_separator = derive_domain_separator(env, &domain);
+    let inputs = ZkPublicInputs {
+        run_hash_hi: BytesN::from_array(env, &[1u8; 32]),
+        run_hash_lo: BytesN::from_array(env, &[2u8; 32]),
+        score,
+        wave,
+        nonce,
+        season_id: 3,
+        challenge_id: 1,
+        player_address: address_digest(env, player),
+        contract_id: address_digest(env, app),
+        domain_separator: domain.domain_separator.clone(),
+    };
+    (domain, inputs)
+}
+
+fn claim(score: u32, wave: u32, season_id: u32) -> ClaimedRun {
+    ClaimedRun { score, wave, season_id }
+}
+
+fn proof(env: &Env) -> BytesN<256> {
+    BytesN::from_array(env, &[0u8; 256])
+}
+
+#[test]
+fn test_submit_proof_end_to_end_updates_leaderboard() {
+    let env = Env::default();
+    let (app, vk_hash) = setup(&env);
+    let player = Address::generate(&env);
+
+    let (domain, inputs) = run(&env, &app.address, &player, 1, 100, 5);
+    app.submit_proof(&domain, &proof(&env), &inputs, &vk_hash, &claim(100, 5, 3));
+    assert_eq!(
+        app.get_leaderboard(&3),
+        soroban_sdk::vec![
+            &env,
+            LeaderboardEntry {
+                player: player.clone(),
+                wave: 5,
+                score: 100
+            }
+        ]
+    );
+
+    // A better run replaces the entry; the same nonce cannot be reused.
+    let (domain2, inputs2) = run(&env, &app.address, &player, 2, 300, 8);
+    app.submit_proof(&domain2, &proof(&env), &inputs2, &vk_hash, &claim(300, 8, 3));
+    let lb = app.get_leaderboard(&3);
+    assert_eq!(lb.len(), 1);
+    assert_eq!(lb.get(0).unwrap().score, 300);
+
+    assert_eq!(
+        app.try_submit_proof(&domain, &proof(&env), &inputs, &vk_hash, &claim(100, 5, 3)),
+        Err(Ok(ApplicationError::PolicyRejected))
+    );
+}
+
+#[test]
+fn test_policy_rejection_leaves_leaderboard_untouched() {
+    let env = Env::default();
+    let (app, vk_hash) = setup(&env);
+    let player = Address::generate(&env);
+
+    // Claimed score differs from the proven one.
+    let (domain, inputs) = run(&env, &app.address, &player, 1, 100, 5);
+    assert_eq!(
+        app.try_submit_proof(&domain, &proof(&env), &inputs, &vk_hash, &claim(500, 5, 3)),
+        Err(Ok(ApplicationError::PolicyRejected))
+    );
+    // Unregistered VK.
+    let unknown = BytesN::from_array(&env, &[9u8; 32]);
+    assert_eq!(
+        app.try_submit_proof(&domain, &proof(&env), &inputs, &unknown, &claim(100, 5, 3)),
+        Err(Ok(ApplicationError::PolicyRejected))
+    );
+    assert_eq!(app.get_leaderboard(&3).len(), 0);
+}
+
+/// Without mocked auths, validate only runs when the configured application calls it.
+#[test]
+fn test_policy_validate_only_from_application() {
+    let env = Env::default();
+    let (app, policy, vk_hash) = setup_stack(&env);
+    env.set_auths(&[]);
+
+    let player = Address::generate(&env);
+    let (domain, inputs) = run(&env, &app.address, &player, 1, 100, 5);
+    assert!(policy
+        .try_validate(&domain, &proof(&env), &inputs, &vk_hash, &100, &5)
+        .is_err());
+    app.submit_proof(&domain, &proof(&env), &inputs, &vk_hash, &claim(100, 5, 3));
+    assert_eq!(app.get_leaderboard(&3).len(), 1);
+}
+
+#[test]
+fn test_season_must_match_public_inputs() {
+    let env = Env::default();
+    let (app, vk_hash) = setup(&env);
+    let player = Address::generate(&env);
+
+    // The proof is for season 3; it cannot be filed under season 4.
+    let (domain, inputs) = run(&env, &app.address, &player, 1, 100, 5);
+    assert_eq!(
+        app.try_submit_proof(&domain, &proof(&env), &inputs, &vk_hash, &claim(100, 5, 4)),
+        Err(Ok(ApplicationError::InvalidInput))
+    );
+    assert_eq!(app.get_leaderboard(&4).len(), 0);
+
+    // The nonce was not consumed, so the correct season still goes through.
+    app.submit_proof(&domain, &proof(&env), &inputs, &vk_hash, &claim(100, 5, 3));
+    assert_eq!(app.get_leaderboard(&3).len(), 1);
+}
+
+#[test]
+fn test_submit_proof_without_policy() {
+    let env = Env::default();
+    let app = ApplicationClient::new(&env, &env.register(Application, ()));
+    let (domain, inputs) = run(&env, &app.address, &Address::generate(&env), 1, 100, 5);
+    let vk_hash = BytesN::from_array(&env, &[0u8; 32]);
+    assert_eq!(
+        app.try_submit_proof(&domain, &proof(&env), &inputs, &vk_hash, &claim(100, 5, 3)),
+        Err(Ok(ApplicationError::PolicyNotSet))
+    );
+}
+
+/// init is once-only and needs the admin's auth; set_policy needs it too.
+#[test]
+fn test_set_policy_is_admin_only() {
+    let env = Env::default();
+    let app = ApplicationClient::new(&env, &env.register(Application, ()));
+    let policy = Address::generate(&env);
+    assert_eq!(app.try_set_policy(&policy), Err(Ok(ApplicationError::NotInitialized)));
+
+    let admin = Address::generate(&env);
+    assert!(app.try_init(&admin).is_err());
+    app.mock_all_auths().init(&admin);
+    assert_eq!(
+        app.mock_all_auths().try_init(&Address::generate(&env)),
+        Err(Ok(ApplicationError::AlreadyInitialized))
+    );
+
+    assert!(app.try_set_policy(&policy).is_err());
+    app.mock_all_auths().set_policy(&policy);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin,
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    app.address.clone(),
+                    Symbol::new(&env, "set_policy"),
+                    (policy,).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+}
+
+/// Real GameRunV2 proof generated for `V2_PLAYER` submitting to `V2_CONTRACT`.
+#[test]
+fn test_real_gamerun_v2_proof_end_to_end() {
+    let env = Env::default();
+    let (vk, proof, _) = v2_fixture(&env);
+    let (domain, inputs) = v2_run(&env);
+
+    let verifier = new_verifier(&env);
+    let vk_hash = verifier.store_vk(&vk);
+    let policy = PolicyClient::new(&env, &env.register(Policy, ()));
+    policy.init(&Address::generate(&env));
+    policy.set_verifier(&verifier.address);
+
+    // The proof commits to the application's address, so deploy it there.
+    let app_id = Address::from_str(&env, V2_CONTRACT);
+    let app = ApplicationClient::new(&env, &env.register_at(&app_id, Application, ()));
+    app.init(&Address::generate(&env));
+    app.set_policy(&policy.address);
+    policy.set_application(&app.address);
+
+    let proof = packed_proof(&env, &proof);
+    app.submit_proof(&domain, &proof, &inputs, &vk_hash, &claim(1200, 12, 3));
+    let lb = app.get_leaderboard(&3);
+    assert_eq!(lb.len(), 1);
+    assert_eq!(
+        lb.get(0).unwrap(),
+        LeaderboardEntry { player: domain.player_address, wave: 12, score: 1200 }
+    );
+}
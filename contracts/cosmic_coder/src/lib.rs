@@ -1,15 +1,17 @@
 //! Cosmic Coder - ZK-ranked survival game on Stellar.
 //! Ranked leaderboard depends exclusively on Groth16 proof verification (BN254).
 //! Verifier and policy are separate; shared types in zk_types.
-//! ZK Plasma Rifle integration: pub_signals[6] = used_zk_weapon flag.
+//! ZK Plasma Rifle integration: pub_signals[6] = used_zk_weapon flag, accepted only from owners.
+//! Proofs are bound to the submitting player and this contract: pub_signals[7..11].
+//! Verification keys are registered by the admin per circuit; callers reference them by hash.
 
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Bytes, Env, IntoVal,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Bytes,
+    BytesN, ConversionError, Env, IntoVal, InvokeError, Symbol, Val, Vec, U256,
 };
-use zk_types::{Groth16Error, ZkProof, ZkVerificationKey};
+use zk_types::{address_commitment, vk_hash, Groth16Error, VkState, ZkProof, ZkVerificationKey};
 
 // BN254 scalar field modulus (Fr) in uncompressed big-endian bytes:
 // r = 21888242871839275222246405745257275088548364400416034343698204186575808495617
@@ -32,9 +34,93 @@ fn is_ge_be_32(a: &[u8; 32], b: &[u8; 32]) -> bool {
     true // equal
 }
 
+/// Big-endian 32-byte field encoding of an unsigned integer public signal.
+fn u64_signal(v: u64) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[24..].copy_from_slice(&v.to_be_bytes());
+    out
+}
+
+/// 128-bit half of run_hash as a field element (top 16 bytes zero).
+fn run_hash_half_signal(half: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[16..].copy_from_slice(half);
+    out
+}
+
+/// Noir GameRun public inputs (circuits/noir/src/main.nr): [run_hash_hi, run_hash_lo, score,
+/// wave, nonce, season_id, used_zk_weapon]. The circuit sums every input as u128, so when
+/// hi + lo would overflow the hash is folded exactly like the client does (gameClient.js
+/// computeSafeNoirHashInputs): hi = run_hash mod (2^128 - reserve), lo = 0, where reserve is
+/// the sum of the other inputs. There are no player or contract inputs (see submit_zk_noir).
+fn noir_run_inputs(
+    env: &Env,
+    run_hash: &[u8; 32],
+    score: u32,
+    wave: u32,
+    nonce: u64,
+    season_id: u32,
+    used_zk_weapon: bool,
+) -> Vec<BytesN<32>> {
+    let mut hi_bytes = [0u8; 16];
+    let mut lo_bytes = [0u8; 16];
+    hi_bytes.copy_from_slice(&run_hash[..16]);
+    lo_bytes.copy_from_slice(&run_hash[16..]);
+    let (hi, lo) = (u128::from_be_bytes(hi_bytes), u128::from_be_bytes(lo_bytes));
+
+    let reserve = nonce as u128 + score as u128 + wave as u128 + season_id as u128 + used_zk_weapon as u128;
+    let (hash_hi, hash_lo) = match hi.checked_add(lo) {
+        Some(sum) if sum <= u128::MAX - reserve => (hi, lo),
+        _ => {
+            let modulus = U256::from_u128(env, u128::MAX)
+                .sub(&U256::from_u128(env, reserve))
+                .add(&U256::from_u32(env, 1));
+            let folded = U256::from_be_bytes(env, &Bytes::from_array(env, run_hash)).rem_euclid(&modulus);
+            let mut word = [0u8; 32];
+            folded.to_be_bytes().copy_into_slice(&mut word);
+            let mut low = [0u8; 16];
+            low.copy_from_slice(&word[16..]);
+            (u128::from_be_bytes(low), 0)
+        }
+    };
+
+    let mut inputs = Vec::new(env);
+    for half in [hash_hi, hash_lo] {
+        inputs.push_back(BytesN::from_array(env, &run_hash_half_signal(&half.to_be_bytes())));
+    }
+    for v in [score as u64, wave as u64, nonce, season_id as u64, used_zk_weapon as u64] {
+        inputs.push_back(BytesN::from_array(env, &u64_signal(v)));
+    }
+    inputs
+}
+
+/// GameRun public signals: [run_hash_hi, run_hash_lo, score, wave, nonce, season_id,
+/// used_zk_weapon, player_hi, player_lo, contract_hi, contract_lo].
+const GAMERUN_PUB_SIGNALS: u32 = 11;
+
+/// SkillProof public signals: [threshold, publicHash, player_hi, player_lo].
+const SKILLPROOF_PUB_SIGNALS: u32 = 4;
+
 /// TTL for nonce anti-replay storage (approx 1 year in ledgers, ~5 sec per ledger)
 const NONCE_TTL_LEDGERS: u32 = 6_307_200;
 
+/// TTL for per-player weapon ownership bitmaps, extended whenever they are read or written
+/// (approx 1 year in ledgers).
+const WEAPON_TTL_LEDGERS: u32 = 6_307_200;
+
+/// Weapon ids are bit positions in the 256-bit ownership bitmap; 0 is reserved.
+const MAX_WEAPON_ID: u32 = 255;
+
+/// Catalog id of the ZK Plasma Rifle, the weapon behind the GameRun used_zk_weapon flag.
+/// Ranked runs claiming it require the player to have unlocked it.
+const ZK_WEAPON_ID: u32 = 4;
+
+/// Players per get_weapon_bitmaps / migrate_weapon_unlocks call.
+const MAX_WEAPON_BATCH: u32 = 32;
+
+/// Nonces below a player's highest used nonce in a season that are still accepted (once each).
+const NONCE_WINDOW: u64 = 128;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -45,6 +131,28 @@ pub enum CosmicCoderError {
     VerifierError = 4,
     InvalidInput = 5,
     MalformedVk = 6,
+    AlreadyInitialized = 7,
+    NotInitialized = 8,
+    NoPendingAdmin = 9,
+    PublicInputMismatch = 10,
+    UnknownVk = 11,
+    VkRevoked = 12,
+    VkAlreadyRegistered = 13,
+    SeasonNotFound = 14,
+    SeasonNotActive = 15,
+    SeasonClosed = 16,
+    InvalidSeason = 17,
+    InvalidSession = 18,
+    /// The key's deprecation window (deprecate_vk) has ended.
+    VkExpired = 19,
+    /// unlock_weapon: this SkillProof publicHash was already consumed by an unlock.
+    CommitmentUsed = 22,
+    /// weapon_id is not in the weapon catalog.
+    UnknownWeapon = 23,
+    /// The catalog entry is disabled.
+    WeaponDisabled = 24,
+    /// A ranked run claims the ZK weapon (ZK_WEAPON_ID) the player has not unlocked.
+    WeaponNotOwned = 25,
     VerifierCrash = 100,
     GameHubCrash = 101,
     InvalidZkProof = 102,
@@ -58,6 +166,18 @@ pub enum UltraHonkError {
     ProofParseError = 2,
     VerificationFailed = 3,
     VkNotSet = 4,
+    NotInitialized = 5,
+    AlreadyInitialized = 6,
+    VkAlreadySet = 7,
+    ProofAlreadyVerified = 8,
+}
+
+/// Id of a key stored in the UltraHonk verifier (mirrors `ultrahonk_verifier::VkId`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UltraHonkVkId {
+    pub circuit: Symbol,
+    pub version: u32,
 }
 
 #[contracttype]
@@ -76,13 +196,16 @@ pub struct ScoreEntry {
     pub score: u32,
 }
 
-/// Key for ranked leaderboard storage: one vec per season.
+/// Key for ranked leaderboard storage: the season's top LEADERBOARD_CAP entries, sorted desc.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LeaderboardKey {
     pub season_id: u32,
 }
 
+/// Maximum entries kept in a season's ranked top list.
+const LEADERBOARD_CAP: u32 = 100;
+
 /// Per-player milestone progress for a season, derived from verified ranked submissions.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -99,37 +222,141 @@ pub struct PlayerMilestone {
     pub best_wave: u32,
 }
 
-/// Anti-replay: (player, nonce, season_id) must be unique.
+/// Anti-replay state of one player in one season: highest nonce used and a bitmap of the
+/// NONCE_WINDOW nonces ending at it (bit i set = nonce `high - i` used). One entry per
+/// (player, season), so a new season starts from a fresh window.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ReplayKey {
+pub struct NonceWindow {
+    pub high: u64,
+    pub seen: u128,
+}
+
+impl NonceWindow {
+    /// Window with `nonce` marked used; Replay if it was used or fell out of the window.
+    fn accept(window: Option<NonceWindow>, nonce: u64) -> Result<NonceWindow, CosmicCoderError> {
+        let w = match window {
+            Some(w) => w,
+            None => return Ok(NonceWindow { high: nonce, seen: 1 }),
+        };
+        if nonce > w.high {
+            let shift = nonce - w.high;
+            let seen = if shift >= NONCE_WINDOW { 1 } else { (w.seen << shift) | 1 };
+            return Ok(NonceWindow { high: nonce, seen });
+        }
+        let age = w.high - nonce;
+        if age >= NONCE_WINDOW || w.seen & (1u128 << age) != 0 {
+            return Err(CosmicCoderError::Replay);
+        }
+        Ok(NonceWindow {
+            high: w.high,
+            seen: w.seen | (1u128 << age),
+        })
+    }
+}
+
+/// Event payload: emitted only when verifier returns Ok(true).
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ZkRunSubmitted {
     pub player: Address,
-    pub nonce: u64,
     pub season_id: u32,
+    pub score: u32,
+    pub wave: u32,
+    pub run_hash: Bytes,
 }
 
-/// Strict nonce key for panic-based anti-replay (nonce only, extracted from pub_signals).
+/// Claimed result of a ranked run (submit_zk / submit_zk_noir); the backend's proof must
+/// attest to it. `session_id` is the player's open session from start_match.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct NonceKey {
+pub struct RankedRun {
+    pub player: Address,
+    pub session_id: u32,
     pub nonce: u64,
+    pub run_hash: Bytes,
+    pub season_id: u32,
+    pub score: u32,
+    pub wave: u32,
 }
 
-/// Event payload: emitted only when verifier returns Ok(true).
+/// One run of a submit_zk_batch call; fields as in submit_zk.
 #[contracttype]
-#[derive(Clone, Debug)]
-pub struct ZkRunSubmitted {
+#[derive(Clone)]
+pub struct ZkRunSubmission {
     pub player: Address,
+    pub session_id: u32,
+    pub proof: ZkProof,
+    pub pub_signals: Vec<Bytes>,
+    pub nonce: u64,
+    pub run_hash: Bytes,
     pub season_id: u32,
     pub score: u32,
     pub wave: u32,
-    pub run_hash: Bytes,
+}
+
+impl ZkRunSubmission {
+    fn ranked_run(&self) -> RankedRun {
+        RankedRun {
+            player: self.player.clone(),
+            session_id: self.session_id,
+            nonce: self.nonce,
+            run_hash: self.run_hash.clone(),
+            season_id: self.season_id,
+            score: self.score,
+            wave: self.wave,
+        }
+    }
+}
+
+/// Runs per submit_zk_batch. Each run writes about five ledger entries (nonces, session,
+/// open session, best score, milestone), which keeps a full batch under the write footprint.
+const MAX_ZK_BATCH: u32 = 8;
+
+/// Proof system of a ranked submission. Everything except verification is shared
+/// (CosmicCoder::submit_ranked); a new proving system is a variant plus its `verify` arm.
+#[allow(clippy::large_enum_variant)]
+enum ProofBackend {
+    Groth16 {
+        proof: ZkProof,
+        vk_hash: BytesN<32>,
+        pub_signals: Vec<Bytes>,
+    },
+    UltraHonk {
+        proof: Bytes,
+        used_zk_weapon: bool,
+    },
+}
+
+impl ProofBackend {
+    fn verifier_kind(&self) -> VerifierKind {
+        match self {
+            ProofBackend::Groth16 { .. } => VerifierKind::Groth16,
+            ProofBackend::UltraHonk { .. } => VerifierKind::UltraHonk,
+        }
+    }
+
+    /// Verify the proof for `run`; returns the used_zk_weapon flag.
+    fn verify(self, env: &Env, verifier: &Address, run: &RankedRun) -> Result<bool, CosmicCoderError> {
+        match self {
+            ProofBackend::Groth16 {
+                proof,
+                vk_hash,
+                pub_signals,
+            } => CosmicCoder::verify_groth16(env, verifier, run, proof, &vk_hash, &pub_signals),
+            ProofBackend::UltraHonk {
+                proof,
+                used_zk_weapon,
+            } => CosmicCoder::verify_ultrahonk(env, verifier, run, &proof, used_zk_weapon),
+        }
+    }
 }
 
 /// Minimum score per wave for legacy submit_result and ZK.
 const MIN_SCORE_PER_WAVE: u32 = 5;
 
-/// Weapon unlock data structure
+/// Legacy per-weapon unlock entry (one ledger entry per unlock), replaced by the
+/// DataKey::WeaponBits bitmap; see migrate_weapon_unlocks.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WeaponUnlockKey {
@@ -137,13 +364,142 @@ pub struct WeaponUnlockKey {
     pub weapon_id: u32,
 }
 
+/// Weapon catalog entry (set_weapon / list_weapons). Unlocking takes a SkillProof that the
+/// player's score reached `threshold`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Weapon {
+    pub id: u32,
+    pub name: Symbol,
+    pub threshold: u32,
+    /// SkillProof key unlock proofs must use; None accepts any registered SkillProof key.
+    pub vk_hash: Option<BytesN<32>>,
+    pub enabled: bool,
+    /// Season during which the weapon can be unlocked; None for any time.
+    pub season_id: Option<u32>,
+}
+
+/// Circuits with admin-registered Groth16 verification keys.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Circuit {
+    GameRun,
+    SkillProof,
+}
+
+impl Circuit {
+    fn pub_signals(self) -> u32 {
+        match self {
+            Circuit::GameRun => GAMERUN_PUB_SIGNALS,
+            Circuit::SkillProof => SKILLPROOF_PUB_SIGNALS,
+        }
+    }
+}
+
+/// Verifier contract slots. Each proof system has its own verifier interface, so each is
+/// configured independently (see set_verifier / get_verifier).
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifierKind {
+    /// Groth16 `verify_proof(vk, proof, pub_signals) -> bool`, used by submit_zk.
+    Groth16,
+    /// UltraHonk `verify_proof_with_stored_vk(caller, vk_id, public_inputs, proof, reject_seen)
+    /// -> BytesN<32>`, used by submit_zk_noir with the key chosen by set_ultrahonk_vk.
+    UltraHonk,
+    /// Groth16 verifier for SkillProof weapon unlocks (unlock_weapon).
+    WeaponUnlock,
+}
+
+/// Admin-created ranked season. Submissions are accepted while
+/// start_ledger <= current ledger <= end_ledger and the season is not closed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Season {
+    pub season_id: u32,
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+    pub closed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SessionStatus {
+    Open,
+    Ended,
+}
+
+/// Game Hub session opened by start_match. A player has at most one open session; ranked and
+/// casual submits must name it and end exactly that session on the hub.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameSession {
+    pub player: Address,
+    pub start_ledger: u32,
+    pub status: SessionStatus,
+}
+
+/// Registry entry stored under DataKey::Vk(vk_hash).
+#[contracttype]
+#[derive(Clone)]
+pub struct RegisteredVk {
+    pub circuit: Circuit,
+    pub vk: ZkVerificationKey,
+    pub state: VkState,
+}
+
+/// One registered key as listed by `list_vks`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VkInfo {
+    pub vk_hash: BytesN<32>,
+    pub circuit: Circuit,
+    pub state: VkState,
+}
+
 /// Persistent storage keys for core contract config/state.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     GameHub,
+    /// Last allocated Game Hub session id (ids are global, sessions are per player).
     Session,
-    ZkVerifier,
+    Verifier(VerifierKind),
+    Admin,
+    PendingAdmin,
+    /// Registered verification key, keyed by sha256 of its XDR encoding.
+    Vk(BytesN<32>),
+    /// Hashes of all registered keys, in registration order.
+    VkList,
+    /// UltraHonk verifier key id used by submit_zk_noir.
+    UltraHonkVk,
+    /// Player's best verified score in a season (kept even when outside the top list).
+    BestScore(u32, Address),
+    /// Player's best season score among runs without the ZK weapon.
+    UnassistedBest(u32, Address),
+    /// Season top list of runs without the ZK weapon (same rules as LeaderboardKey).
+    UnassistedLeaderboard(u32),
+    /// Ranked runs in a season that used the ZK weapon.
+    WeaponAssistedRuns(u32),
+    Season(u32),
+    /// Highest season_id created so far; seasons are created in id and ledger order.
+    LatestSeason,
+    /// Season created just before this one (ids may skip numbers).
+    PrevSeason(u32),
+    /// Final standings snapshot taken by close_season.
+    FinalStandings(u32),
+    GameSession(u32),
+    /// Open session id of a player.
+    OpenSession(Address),
+    /// Anti-replay window of a player in a season (see NonceWindow).
+    Nonces(Address, u32),
+    /// SkillProof publicHash already consumed in unlock_weapon.
+    UsedUnlockHash(BytesN<32>),
+    /// Weapon catalog entry.
+    Weapon(u32),
+    /// Ids of all catalog entries, in creation order.
+    WeaponList,
+    /// Player's owned weapons: bit `id % 8` of byte `id / 8` is set for weapon `id`.
+    WeaponBits(Address),
 }
 
 #[contract]
@@ -191,25 +547,455 @@ impl CosmicCoder {
         }
     }
 
-    /// Initialize: game hub and ZK verifier addresses.
-    pub fn init(env: Env, game_hub: Address, zk_verifier: Address) {
+    /// Load the admin and require its authorization.
+    fn require_admin(env: &Env) -> Result<Address, CosmicCoderError> {
+        let admin: Address = match env.storage().persistent().get::<DataKey, Address>(&DataKey::Admin) {
+            Some(a) => a,
+            None => return Err(CosmicCoderError::NotInitialized),
+        };
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Registered VK for `circuit` that is usable now (not revoked, not past its deprecation
+    /// window). A key registered for another circuit is treated as unknown so a SkillProof key
+    /// can never verify a GameRun proof.
+    fn load_vk(
+        env: &Env,
+        circuit: Circuit,
+        vk_hash: &BytesN<32>,
+    ) -> Result<ZkVerificationKey, CosmicCoderError> {
+        let entry: RegisteredVk = match env
+            .storage()
+            .persistent()
+            .get::<DataKey, RegisteredVk>(&DataKey::Vk(vk_hash.clone()))
+        {
+            Some(e) => e,
+            None => return Err(CosmicCoderError::UnknownVk),
+        };
+        if entry.circuit != circuit {
+            return Err(CosmicCoderError::UnknownVk);
+        }
+        match entry.state {
+            VkState::Revoked => Err(CosmicCoderError::VkRevoked),
+            state if !state.is_usable_at(env.ledger().sequence()) => Err(CosmicCoderError::VkExpired),
+            _ => Ok(entry.vk),
+        }
+    }
+
+    /// Season that accepts submissions right now.
+    fn require_open_season(env: &Env, season_id: u32) -> Result<(), CosmicCoderError> {
+        let season: Season = match env.storage().persistent().get(&DataKey::Season(season_id)) {
+            Some(s) => s,
+            None => return Err(CosmicCoderError::SeasonNotFound),
+        };
+        if season.closed {
+            return Err(CosmicCoderError::SeasonClosed);
+        }
+        let now = env.ledger().sequence();
+        if now < season.start_ledger || now > season.end_ledger {
+            return Err(CosmicCoderError::SeasonNotActive);
+        }
+        Ok(())
+    }
+
+    /// `session_id` must be the player's open session.
+    fn require_open_session(env: &Env, player: &Address, session_id: u32) -> Result<(), CosmicCoderError> {
+        let open: Option<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OpenSession(player.clone()));
+        if open != Some(session_id) {
+            return Err(CosmicCoderError::InvalidSession);
+        }
+        Ok(())
+    }
+
+    /// Mark the player's session ended and call end_game() on the hub for exactly that session.
+    fn end_session(env: &Env, hub: &Address, player: &Address, session_id: u32, success: bool) {
+        let key = DataKey::GameSession(session_id);
+        if let Some(mut session) = env.storage().persistent().get::<DataKey, GameSession>(&key) {
+            session.status = SessionStatus::Ended;
+            env.storage().persistent().set(&key, &session);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::OpenSession(player.clone()));
+
+        // end_game() on the hub returns () (not Result), so we must use invoke_contract
+        // (try_invoke_contract expects the callee to return Result<_, E> and can trap on decode).
+        env.invoke_contract::<()>(
+            hub,
+            &Symbol::new(env, "end_game"),
+            // Must match mock hub signature exactly: (env, session_id_u32, success)
+            soroban_sdk::vec![env, session_id.into_val(env), success.into_val(env)],
+        );
+    }
+
+    /// Player's nonce window for `season_id` with `nonce` marked used, or Replay. Nothing is
+    /// stored until `commit_nonce`, so a failed verification does not burn the nonce.
+    fn check_nonce(
+        env: &Env,
+        player: &Address,
+        season_id: u32,
+        nonce: u64,
+    ) -> Result<NonceWindow, CosmicCoderError> {
+        let window = env
+            .storage()
+            .persistent()
+            .get::<DataKey, NonceWindow>(&DataKey::Nonces(player.clone(), season_id));
+        NonceWindow::accept(window, nonce)
+    }
+
+    fn commit_nonce(env: &Env, player: &Address, season_id: u32, window: &NonceWindow) {
+        let key = DataKey::Nonces(player.clone(), season_id);
+        env.storage().persistent().set(&key, window);
+        env.storage().persistent().extend_ttl(&key, NONCE_TTL_LEDGERS, NONCE_TTL_LEDGERS);
+    }
+
+    /// Verifier address configured for `kind`.
+    fn load_verifier(env: &Env, kind: VerifierKind) -> Result<Address, CosmicCoderError> {
+        env.storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::Verifier(kind))
+            .ok_or(CosmicCoderError::VerifierNotSet)
+    }
+
+    /// Initialize once: admin, game hub and Groth16 verifier addresses. `zk_verifier` fills the
+    /// Groth16 and WeaponUnlock slots; the UltraHonk slot is set separately via set_verifier.
+    /// `admin` must authorize, so a front-runner cannot claim an uninitialized deployment.
+    pub fn init(
+        env: Env,
+        admin: Address,
+        game_hub: Address,
+        zk_verifier: Address,
+    ) -> Result<(), CosmicCoderError> {
+        if env.storage().persistent().has(&DataKey::Admin) {
+            return Err(CosmicCoderError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().persistent().set(&DataKey::Admin, &admin);
         env.storage().persistent().set(&DataKey::GameHub, &game_hub);
-        env.storage().persistent().set(&DataKey::ZkVerifier, &zk_verifier);
+        env.storage().persistent().set(&DataKey::Verifier(VerifierKind::Groth16), &zk_verifier);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Verifier(VerifierKind::WeaponUnlock), &zk_verifier);
         env.storage().persistent().set(&DataKey::Session, &0u32);
+        Ok(())
+    }
+
+    /// Current admin address.
+    pub fn get_admin(env: Env) -> Result<Address, CosmicCoderError> {
+        env.storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::Admin)
+            .ok_or(CosmicCoderError::NotInitialized)
     }
 
-    /// Set Groth16 verifier contract (required for ranked submit_zk).
-    pub fn set_verifier(env: Env, verifier: Address) {
-        env.storage().persistent().set(&DataKey::ZkVerifier, &verifier);
+    /// Set the verifier contract for one slot. Admin only. Emits ("verifier_set", kind, verifier).
+    pub fn set_verifier(env: Env, kind: VerifierKind, verifier: Address) -> Result<(), CosmicCoderError> {
+        Self::require_admin(&env)?;
+        env.storage().persistent().set(&DataKey::Verifier(kind), &verifier);
+        env.events().publish(
+            (Symbol::new(&env, "verifier_set"), kind, verifier),
+            (),
+        );
+        Ok(())
+    }
+
+    /// Verifier contract configured for a slot (VerifierNotSet if none).
+    pub fn get_verifier(env: Env, kind: VerifierKind) -> Result<Address, CosmicCoderError> {
+        Self::load_verifier(&env, kind)
+    }
+
+    /// Set Game Hub contract (start_game / end_game). Admin only.
+    pub fn set_game_hub(env: Env, game_hub: Address) -> Result<(), CosmicCoderError> {
+        Self::require_admin(&env)?;
+        env.storage().persistent().set(&DataKey::GameHub, &game_hub);
+        Ok(())
+    }
+
+    /// Select the UltraHonk verifier key submit_zk_noir verifies against (GameRun circuit).
+    /// Admin only. Emits ("ultrahonk_vk_set", circuit, version).
+    pub fn set_ultrahonk_vk(env: Env, vk_id: UltraHonkVkId) -> Result<(), CosmicCoderError> {
+        Self::require_admin(&env)?;
+        env.storage().persistent().set(&DataKey::UltraHonkVk, &vk_id);
+        env.events().publish(
+            (Symbol::new(&env, "ultrahonk_vk_set"), vk_id.circuit, vk_id.version),
+            (),
+        );
+        Ok(())
+    }
+
+    /// UltraHonk verifier key id used by submit_zk_noir, if set.
+    pub fn get_ultrahonk_vk(env: Env) -> Option<UltraHonkVkId> {
+        env.storage().persistent().get(&DataKey::UltraHonkVk)
+    }
+
+    /// Register a Groth16 verification key for `circuit`. Admin only.
+    /// Returns vk_hash = sha256(XDR(vk)), the handle passed to submit_zk / unlock_weapon.
+    /// The key's ic length must match the circuit's public signal count.
+    pub fn register_vk(
+        env: Env,
+        circuit: Circuit,
+        vk: ZkVerificationKey,
+    ) -> Result<BytesN<32>, CosmicCoderError> {
+        Self::require_admin(&env)?;
+        if vk.ic.len() != circuit.pub_signals() + 1 {
+            return Err(CosmicCoderError::MalformedVk);
+        }
+        let vk_hash = vk_hash(&env, &vk);
+        let key = DataKey::Vk(vk_hash.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(CosmicCoderError::VkAlreadyRegistered);
+        }
+        env.storage().persistent().set(
+            &key,
+            &RegisteredVk {
+                circuit,
+                vk,
+                state: VkState::Active,
+            },
+        );
+        let mut list: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VkList)
+            .unwrap_or(Vec::new(&env));
+        list.push_back(vk_hash.clone());
+        env.storage().persistent().set(&DataKey::VkList, &list);
+        env.events().publish(
+            (Symbol::new(&env, "vk_registered"), circuit, vk_hash.clone()),
+            (),
+        );
+        Ok(vk_hash)
+    }
+
+    /// Revoke a registered verification key; proofs against it are rejected from now on.
+    /// Admin only. Revocation is permanent (the same key cannot be registered again).
+    pub fn revoke_vk(env: Env, vk_hash: BytesN<32>) -> Result<(), CosmicCoderError> {
+        Self::require_admin(&env)?;
+        let key = DataKey::Vk(vk_hash.clone());
+        let mut entry: RegisteredVk = match env.storage().persistent().get(&key) {
+            Some(e) => e,
+            None => return Err(CosmicCoderError::UnknownVk),
+        };
+        entry.state = VkState::Revoked;
+        env.storage().persistent().set(&key, &entry);
+        env.events().publish(
+            (Symbol::new(&env, "vk_revoked"), entry.circuit, vk_hash),
+            (),
+        );
+        Ok(())
+    }
+
+    /// Keep a key usable up to and including `last_ledger` while clients move to its successor
+    /// (e.g. GameRun -> GameRunV2); afterwards submissions fail with VkExpired. Admin only; may be
+    /// called again to move the window. Emits ("vk_deprecated", circuit, vk_hash) with last_ledger.
+    pub fn deprecate_vk(env: Env, vk_hash: BytesN<32>, last_ledger: u32) -> Result<(), CosmicCoderError> {
+        Self::require_admin(&env)?;
+        let key = DataKey::Vk(vk_hash.clone());
+        let mut entry: RegisteredVk = match env.storage().persistent().get(&key) {
+            Some(e) => e,
+            None => return Err(CosmicCoderError::UnknownVk),
+        };
+        if entry.state == VkState::Revoked {
+            return Err(CosmicCoderError::VkRevoked);
+        }
+        entry.state = VkState::Deprecated(last_ledger);
+        env.storage().persistent().set(&key, &entry);
+        env.events().publish(
+            (Symbol::new(&env, "vk_deprecated"), entry.circuit, vk_hash),
+            last_ledger,
+        );
+        Ok(())
+    }
+
+    /// Every registered key with its circuit and state, in registration order.
+    pub fn list_vks(env: Env) -> Vec<VkInfo> {
+        let list: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VkList)
+            .unwrap_or(Vec::new(&env));
+        let mut out = Vec::new(&env);
+        for vk_hash in list.iter() {
+            if let Some(entry) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, RegisteredVk>(&DataKey::Vk(vk_hash.clone()))
+            {
+                out.push_back(VkInfo {
+                    vk_hash,
+                    circuit: entry.circuit,
+                    state: entry.state,
+                });
+            }
+        }
+        out
+    }
+
+    /// Registry entry for `vk_hash`, if any (including revoked keys).
+    pub fn get_vk(env: Env, vk_hash: BytesN<32>) -> Option<RegisteredVk> {
+        env.storage().persistent().get(&DataKey::Vk(vk_hash))
+    }
+
+    /// Admin transfer step 1: current admin proposes a new admin.
+    /// The transfer only takes effect once the new admin calls accept_admin.
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), CosmicCoderError> {
+        let admin = Self::require_admin(&env)?;
+        env.storage().persistent().set(&DataKey::PendingAdmin, &new_admin);
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposed"), admin, new_admin),
+            (),
+        );
+        Ok(())
+    }
+
+    /// Admin transfer step 2: pending admin accepts and becomes admin.
+    pub fn accept_admin(env: Env) -> Result<(), CosmicCoderError> {
+        let pending: Address = match env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::PendingAdmin)
+        {
+            Some(a) => a,
+            None => return Err(CosmicCoderError::NoPendingAdmin),
+        };
+        pending.require_auth();
+        let previous: Address = match env.storage().persistent().get::<DataKey, Address>(&DataKey::Admin) {
+            Some(a) => a,
+            None => return Err(CosmicCoderError::NotInitialized),
+        };
+        env.storage().persistent().set(&DataKey::Admin, &pending);
+        env.storage().persistent().remove(&DataKey::PendingAdmin);
+        env.events().publish(
+            (Symbol::new(&env, "admin_changed"), previous, pending),
+            (),
+        );
+        Ok(())
+    }
+
+    /// Admin: create a season. Ids and ledger windows must increase: season_id above every
+    /// existing season and start_ledger after the previous season's end_ledger.
+    pub fn create_season(
+        env: Env,
+        season_id: u32,
+        start_ledger: u32,
+        end_ledger: u32,
+    ) -> Result<(), CosmicCoderError> {
+        Self::require_admin(&env)?;
+        if start_ledger > end_ledger || end_ledger < env.ledger().sequence() {
+            return Err(CosmicCoderError::InvalidSeason);
+        }
+        if let Some(latest) = env.storage().persistent().get::<DataKey, u32>(&DataKey::LatestSeason) {
+            let prev: Season = env.storage().persistent().get(&DataKey::Season(latest)).unwrap();
+            if season_id <= latest || start_ledger <= prev.end_ledger {
+                return Err(CosmicCoderError::InvalidSeason);
+            }
+            env.storage().persistent().set(&DataKey::PrevSeason(season_id), &latest);
+        }
+        let season = Season {
+            season_id,
+            start_ledger,
+            end_ledger,
+            closed: false,
+        };
+        env.storage().persistent().set(&DataKey::Season(season_id), &season);
+        env.storage().persistent().set(&DataKey::LatestSeason, &season_id);
+        env.events().publish(
+            (Symbol::new(&env, "season_opened"), season_id),
+            (start_ledger, end_ledger),
+        );
+        Ok(())
+    }
+
+    /// Admin: close a season (at or before its end). Further submissions are rejected and the
+    /// current top list is stored as the season's final standings.
+    pub fn close_season(env: Env, season_id: u32) -> Result<(), CosmicCoderError> {
+        Self::require_admin(&env)?;
+        let key = DataKey::Season(season_id);
+        let mut season: Season = match env.storage().persistent().get(&key) {
+            Some(s) => s,
+            None => return Err(CosmicCoderError::SeasonNotFound),
+        };
+        if season.closed {
+            return Err(CosmicCoderError::SeasonClosed);
+        }
+        season.closed = true;
+        env.storage().persistent().set(&key, &season);
+        let standings = load_ranked(&env, season_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::FinalStandings(season_id), &standings);
+        env.events().publish(
+            (Symbol::new(&env, "season_closed"), season_id),
+            standings.len(),
+        );
+        Ok(())
+    }
+
+    /// Season by id.
+    pub fn get_season(env: Env, season_id: u32) -> Option<Season> {
+        env.storage().persistent().get(&DataKey::Season(season_id))
+    }
+
+    /// Season accepting submissions at the current ledger, if any.
+    pub fn get_active_season(env: Env) -> Option<Season> {
+        let now = env.ledger().sequence();
+        let mut id: u32 = env.storage().persistent().get(&DataKey::LatestSeason)?;
+        // Windows are ordered, so walk back (via PrevSeason links) only past seasons that have
+        // not started yet.
+        loop {
+            let season: Season = env.storage().persistent().get(&DataKey::Season(id))?;
+            if now > season.end_ledger {
+                return None;
+            }
+            if now >= season.start_ledger {
+                return if season.closed { None } else { Some(season) };
+            }
+            id = env.storage().persistent().get(&DataKey::PrevSeason(id))?;
+        }
+    }
+
+    /// Final standings of a closed season (None while the season is open).
+    pub fn get_final_standings(env: Env, season_id: u32) -> Option<Vec<ScoreEntry>> {
+        env.storage().persistent().get(&DataKey::FinalStandings(season_id))
+    }
+
+    /// Smallest nonce above every nonce the player has used in `season_id` (0 before their
+    /// first run of the season). Submitting it always passes the replay check; older unused
+    /// nonces within NONCE_WINDOW also do. Each season has its own window.
+    pub fn get_next_nonce(env: Env, player: Address, season_id: u32) -> u64 {
+        match env
+            .storage()
+            .persistent()
+            .get::<DataKey, NonceWindow>(&DataKey::Nonces(player, season_id))
+        {
+            Some(w) => w.high.saturating_add(1),
+            None => 0,
+        }
+    }
+
+    /// Session by id.
+    pub fn get_session(env: Env, session_id: u32) -> Option<GameSession> {
+        env.storage().persistent().get(&DataKey::GameSession(session_id))
+    }
+
+    /// Player's open session id, if any (pass it to submit_zk / submit_zk_noir / submit_result).
+    pub fn get_open_session(env: Env, player: Address) -> Option<u32> {
+        env.storage().persistent().get(&DataKey::OpenSession(player))
     }
 
     /// Start a match (Game Hub start_game). Caller must pass their address and authorize.
+    /// Opens a new session for the player; a still-open previous session is ended as failed.
     pub fn start_match(env: Env, player: Address) -> u32 {
         player.require_auth();
-        let session: u32 = match env.storage().persistent().get::<DataKey, u32>(&DataKey::Session) {
-            Some(s) => s,
-            None => 0,
-        };
+        let session: u32 = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u32>(&DataKey::Session)
+            .unwrap_or_default();
         let new_session = session.checked_add(1).unwrap();
         env.storage()
             .persistent()
@@ -220,6 +1006,24 @@ impl CosmicCoder {
             .persistent()
             .get(&DataKey::GameHub)
             .unwrap();
+        if let Some(stale) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u32>(&DataKey::OpenSession(player.clone()))
+        {
+            Self::end_session(&env, &hub, &player, stale, false);
+        }
+        env.storage().persistent().set(
+            &DataKey::GameSession(new_session),
+            &GameSession {
+                player: player.clone(),
+                start_ledger: env.ledger().sequence(),
+                status: SessionStatus::Open,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::OpenSession(player.clone()), &new_session);
         let game_id = env.current_contract_address();
         let system_player = game_id.clone();
         env.invoke_contract::<()>(
@@ -228,7 +1032,7 @@ impl CosmicCoder {
             soroban_sdk::vec![
                 &env,
                 game_id.into_val(&env),
-                (new_session as u32).into_val(&env),
+                new_session.into_val(&env),
                 player.into_val(&env),
                 system_player.into_val(&env),
                 0i128.into_val(&env),
@@ -239,24 +1043,20 @@ impl CosmicCoder {
     }
 
     /// Legacy casual submit: auth + rule only. Caller passes player address and authorizes.
-    pub fn submit_result(env: Env, player: Address, wave: u32, score: i128) -> bool {
+    /// `session_id` must be the player's open session (from start_match); it is ended on the hub.
+    pub fn submit_result(env: Env, player: Address, session_id: u32, wave: u32, score: i128) -> bool {
         player.require_auth();
 
         let min_score = (wave as i128).saturating_mul(MIN_SCORE_PER_WAVE as i128);
         if score < min_score {
             return false;
         }
+        if Self::require_open_session(&env, &player, session_id).is_err() {
+            return false;
+        }
 
-        let session: u32 = match env.storage().persistent().get::<DataKey, u32>(&DataKey::Session) {
-            Some(s) => s,
-            None => 0,
-        };
         let hub: Address = env.storage().persistent().get(&DataKey::GameHub).unwrap();
-        env.invoke_contract::<()>(
-            &hub,
-            &Symbol::new(&env, "end_game"),
-            soroban_sdk::vec![&env, (session as u32).into_val(&env), true.into_val(&env)],
-        );
+        Self::end_session(&env, &hub, &player, session_id, true);
 
         let entry = LeaderboardEntry {
             player: player.clone(),
@@ -273,136 +1073,373 @@ impl CosmicCoder {
         true
     }
 
+    /// Check GameRun signals against the submitted values (layout: GAMERUN_PUB_SIGNALS).
+    /// run_hash is 32 bytes: hi = bytes 0..16, lo = bytes 16..32. Player and contract
+    /// signals must equal `address_commitment` of `player` and this contract.
+    /// Returns the used_zk_weapon flag, which must be 0 or 1.
+    fn check_run_signals(
+        env: &Env,
+        pub_signals: &Vec<soroban_sdk::BytesN<32>>,
+        run: &RankedRun,
+    ) -> Result<bool, CosmicCoderError> {
+        if run.run_hash.len() != 32 {
+            return Err(CosmicCoderError::InvalidInput);
+        }
+        let mut hash = [0u8; 32];
+        run.run_hash.copy_into_slice(&mut hash);
+
+        let expected = [
+            run_hash_half_signal(&hash[..16]),
+            run_hash_half_signal(&hash[16..]),
+            u64_signal(run.score as u64),
+            u64_signal(run.wave as u64),
+            u64_signal(run.nonce),
+            u64_signal(run.season_id as u64),
+        ];
+        for (i, want) in expected.iter().enumerate() {
+            if pub_signals.get(i as u32).map(|s| s.to_array()) != Some(*want) {
+                env.events().publish(
+                    (Symbol::new(env, "debug"), Symbol::new(env, "submit_zk")),
+                    (Symbol::new(env, "pub_signal_mismatch"), i as u32),
+                );
+                return Err(CosmicCoderError::PublicInputMismatch);
+            }
+        }
+
+        let (player_hi, player_lo) = address_commitment(env, &run.player);
+        let (contract_hi, contract_lo) = address_commitment(env, &env.current_contract_address());
+        let bound = [(7u32, player_hi), (8, player_lo), (9, contract_hi), (10, contract_lo)];
+        for (i, want) in bound.iter() {
+            if pub_signals.get(*i).as_ref() != Some(want) {
+                env.events().publish(
+                    (Symbol::new(env, "debug"), Symbol::new(env, "submit_zk")),
+                    (Symbol::new(env, "pub_signal_mismatch"), *i),
+                );
+                return Err(CosmicCoderError::PublicInputMismatch);
+            }
+        }
+
+        let weapon = pub_signals.get(6).map(|s| s.to_array());
+        if weapon == Some(u64_signal(1)) {
+            Ok(true)
+        } else if weapon == Some(u64_signal(0)) {
+            Ok(false)
+        } else {
+            Err(CosmicCoderError::PublicInputMismatch)
+        }
+    }
+
     /// Ranked ZK submit: verifier + strict anti-replay from pub_signals.
+    /// `vk_hash` must name a non-revoked GameRun key registered via register_vk
+    /// (UnknownVk / VkRevoked otherwise); caller-supplied keys are never trusted.
     /// 
-    /// pub_signals order (7 elements): [run_hash_hi, run_hash_lo, score, wave, nonce, season_id, used_zk_weapon]
+    /// pub_signals order (11 elements): [run_hash_hi, run_hash_lo, score, wave, nonce, season_id, used_zk_weapon,
+    /// player_hi, player_lo, contract_hi, contract_lo]
     /// 
-    /// Security features:
+    /// Security features (shared with submit_zk_noir, see submit_ranked):
     /// - Validates public signals are in BN254 Fr field (no host traps)
-    /// - Anti-replay: rejects reused (player, nonce, season_id)
+    /// - Binds pub_signals to run_hash/score/wave/nonce/season_id and to the player and this
+    ///   contract (PublicInputMismatch otherwise), so a leaked proof cannot be replayed by another player
+    /// - season_id must be an admin-created season that is open at the current ledger
+    /// - session_id must be the player's open session (start_match); only that session is ended
+    /// - Anti-replay: rejects a nonce the player already used in this season or one more than
+    ///   NONCE_WINDOW below their highest (constant storage per player and season; see
+    ///   get_next_nonce)
     /// - TTL extension for nonce storage (~1 year)
-    /// - Emits "zk_wpn" event when used_zk_weapon == 1 (ZK Plasma Rifle)
+    /// - used_zk_weapon == 1 (ZK Plasma Rifle) requires the player to own ZK_WEAPON_ID
+    ///   (WeaponNotOwned); such runs emit "zk_wpn" and count towards get_weapon_assisted_runs
     /// - Calls end_game() on Game Hub after successful verification
     pub fn submit_zk(
         env: Env,
-        player: Address,
+        run: RankedRun,
         proof: ZkProof,
-        vk: ZkVerificationKey,
+        vk_hash: BytesN<32>,
         pub_signals: Vec<Bytes>,
-        nonce: u64,
-        run_hash: Bytes,
-        season_id: u32,
-        score: u32,
-        wave: u32,
     ) -> Result<(), CosmicCoderError> {
-        player.require_auth();
+        run.player.require_auth();
+
+        // Diagnostic breadcrumb: submit_zk entered
+        env.events()
+            .publish((symbol_short!("debug"),), symbol_short!("zk_start"));
+
+        Self::submit_ranked(
+            &env,
+            ProofBackend::Groth16 {
+                proof,
+                vk_hash,
+                pub_signals,
+            },
+            run,
+        )
+    }
+
+    /// Ranked submit (Noir + UltraHonk): verifier uses stored VK (avoids invocation size limit).
+    /// The key is the GameRun VK stored in the UltraHonk verifier under the id chosen with
+    /// set_ultrahonk_vk (UnknownVk until then).
+    /// `proof` is the raw bb proof; its public inputs are built from `run`
+    /// (see noir_run_inputs), so the ranked score is the proven score.
+    /// Known gap: the Noir circuit has no player or contract commitment (unlike GameRun's
+    /// address_commitment signals), so a proof is not bound to `run.player`. Whoever submits
+    /// a given proof first gets the run; the verifier's seen-proof record stops later reuse.
+    pub fn submit_zk_noir(
+        env: Env,
+        run: RankedRun,
+        proof: Bytes,
+        used_zk_weapon: bool,
+    ) -> Result<(), CosmicCoderError> {
+        run.player.require_auth();
+
+        let backend = ProofBackend::UltraHonk {
+            proof,
+            used_zk_weapon,
+        };
+        Self::submit_ranked(&env, backend, run)
+    }
+
+    /// Settle several Groth16 ranked runs in one transaction (tournament operators).
+    /// Each run passes the same checks as submit_zk and needs its player's auth; all proofs
+    /// are then checked with a single verify_batch call against the registered GameRun VK
+    /// `vk_hash`. All-or-nothing: any failing run rejects the batch (InvalidProof when the
+    /// batch pairing fails). At most MAX_ZK_BATCH runs, one per player (InvalidInput otherwise).
+    pub fn submit_zk_batch(
+        env: Env,
+        vk_hash: BytesN<32>,
+        runs: Vec<ZkRunSubmission>,
+    ) -> Result<(), CosmicCoderError> {
+        if runs.is_empty() || runs.len() > MAX_ZK_BATCH {
+            return Err(CosmicCoderError::InvalidInput);
+        }
+        let verifier = Self::load_verifier(&env, VerifierKind::Groth16)?;
+        let vk = Self::load_vk(&env, Circuit::GameRun, &vk_hash)?;
+
+        let mut players: Vec<Address> = Vec::new(&env);
+        let mut proofs: Vec<ZkProof> = Vec::new(&env);
+        let mut signals: Vec<Vec<BytesN<32>>> = Vec::new(&env);
+        let mut checked: Vec<(NonceWindow, bool)> = Vec::new(&env);
+        for sub in runs.iter() {
+            // Duplicates are rejected before require_auth, which may only run once per address.
+            if players.contains(&sub.player) {
+                return Err(CosmicCoderError::InvalidInput);
+            }
+            sub.player.require_auth();
+            players.push_back(sub.player.clone());
+
+            let run = sub.ranked_run();
+            let nonces = Self::check_ranked(&env, &run)?;
+            let (pub_signals, used_weapon) = Self::groth16_signals(&env, &run, &sub.pub_signals)?;
+            proofs.push_back(sub.proof);
+            signals.push_back(pub_signals);
+            checked.push_back((nonces, used_weapon));
+        }
+
+        let verifier_result = env.try_invoke_contract::<bool, Groth16Error>(
+            &verifier,
+            &Symbol::new(&env, "verify_batch"),
+            soroban_sdk::vec![&env, vk.into_val(&env), proofs.into_val(&env), signals.into_val(&env)],
+        );
+        Self::check_groth16_result(&env, verifier_result)?;
+
+        for (sub, (nonces, used_weapon)) in runs.iter().zip(checked.iter()) {
+            Self::settle_ranked(&env, sub.ranked_run(), &nonces, used_weapon)?;
+        }
+        Ok(())
+    }
+
+    /// Ranked pipeline shared by every proof backend: input rules, season, session and replay
+    /// checks, then the backend's verification step, then nonce, Game Hub, leaderboard and events.
+    fn submit_ranked(env: &Env, backend: ProofBackend, run: RankedRun) -> Result<(), CosmicCoderError> {
+        // === 1. Get the backend's verifier contract (explicit crash reason if missing) ===
+        let verifier_addr = Self::load_verifier(env, backend.verifier_kind())?;
+
+        // === 2-3. Input, season, session and replay checks ===
+        let nonces = Self::check_ranked(env, &run)?;
+
+        // === 4. Backend verification (binds the proof to `run`) ===
+        let used_weapon = backend.verify(env, &verifier_addr, &run)?;
+
+        Self::settle_ranked(env, run, &nonces, used_weapon)
+    }
+
+    /// Checks every ranked run passes before its proof is verified. Returns the player's nonce
+    /// window with `run.nonce` accepted, to be committed by settle_ranked.
+    fn check_ranked(env: &Env, run: &RankedRun) -> Result<NonceWindow, CosmicCoderError> {
+        let player = &run.player;
+
+        // === 2. Validate inputs ===
+        if run.score == 0 || run.wave == 0 {
+            return Err(CosmicCoderError::InvalidInput);
+        }
+        let min_score = run.wave.saturating_mul(MIN_SCORE_PER_WAVE);
+        if run.score < min_score {
+            return Err(CosmicCoderError::InvalidInput);
+        }
+        if run.run_hash.len() != 32 {
+            return Err(CosmicCoderError::InvalidInput);
+        }
+        Self::require_open_season(env, run.season_id)?;
+        Self::require_open_session(env, player, run.session_id)?;
+
+        // === 3. STRICT ANTI-REPLAY: Check the player's nonce window BEFORE calling verifier ===
+        Self::check_nonce(env, player, run.season_id, run.nonce)
+    }
+
+    /// Effects of a verified ranked run: nonce, Game Hub session, leaderboard and events.
+    fn settle_ranked(
+        env: &Env,
+        run: RankedRun,
+        nonces: &NonceWindow,
+        used_weapon: bool,
+    ) -> Result<(), CosmicCoderError> {
+        let player = &run.player;
 
-        // Diagnostic breadcrumb: submit_zk entered
-        env.events()
-            .publish((symbol_short!("debug"),), symbol_short!("zk_start"));
+        // === 5. A ZK weapon claim needs the unlocked weapon ===
+        if used_weapon && !has_weapon_bit(&load_weapon_bits(env, player), ZK_WEAPON_ID) {
+            return Err(CosmicCoderError::WeaponNotOwned);
+        }
+
+        // Mark nonce as used with TTL extension
+        Self::commit_nonce(env, player, run.season_id, nonces);
+
+        // === 6. used_zk_weapon: emit event and count the run ===
+        if used_weapon {
+            // Emit distinct ZK weapon event for frontend/Stellar Expert tracking
+            env.events().publish((symbol_short!("zk_wpn"), player.clone()), true);
+            let key = DataKey::WeaponAssistedRuns(run.season_id);
+            let runs: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &runs.saturating_add(1));
+        }
 
-        // === 1. Get verifier contract (explicit crash reason if missing) ===
-        let verifier_addr: Address = match env
+        // === 7. Call end_game() on Game Hub for the player's session ===
+        let hub_addr: Address = match env
             .storage()
             .persistent()
-            .get::<DataKey, Address>(&DataKey::ZkVerifier)
+            .get::<DataKey, Address>(&DataKey::GameHub)
         {
             Some(a) => a,
-            None => return Err(CosmicCoderError::VerifierNotSet),
+            None => return Err(CosmicCoderError::GameHubCrash),
         };
 
-        // === 2. Validate pub_signals structure (expect 7 elements for new circuit) ===
-        // Strict: circuit expects exactly 7 public signals.
-        if pub_signals.len() != 7 {
+        // Breadcrumb before hub call: if a trap happens inside the hub, you'll still see this.
+        env.events()
+            .publish((symbol_short!("debug"),), symbol_short!("hub_call"));
+        Self::end_session(env, &hub_addr, player, run.session_id, true);
+
+        // === 8. Update leaderboards ===
+        record_ranked_score(env, run.season_id, player, run.score);
+        if !used_weapon {
+            record_unassisted_score(env, run.season_id, player, run.score);
+        }
+        Self::update_player_milestone(env, player, run.season_id, run.wave);
+
+        // === 9. Emit main ZK run event ===
+        env.events().publish(
+            (
+                Symbol::new(env, "zk_run_submitted"),
+                player.clone(),
+                run.season_id,
+                run.score,
+                run.wave,
+                run.run_hash,
+            ),
+            (),
+        );
+
+        Ok(())
+    }
+
+    /// Groth16 backend: 11 GameRun signals in Fr, bound to `run`, checked against the
+    /// registered VK. Returns the used_zk_weapon flag.
+    fn verify_groth16(
+        env: &Env,
+        verifier: &Address,
+        run: &RankedRun,
+        proof: ZkProof,
+        vk_hash: &BytesN<32>,
+        pub_signals: &Vec<Bytes>,
+    ) -> Result<bool, CosmicCoderError> {
+        // Registered GameRun VK (ic length checked at registration).
+        let vk = Self::load_vk(env, Circuit::GameRun, vk_hash)?;
+        let (pub_signals_n, used_weapon) = Self::groth16_signals(env, run, pub_signals)?;
+
+        let verifier_result = env.try_invoke_contract::<bool, Groth16Error>(
+            verifier,
+            &Symbol::new(env, "verify_proof"),
+            soroban_sdk::vec![
+                env,
+                vk.into_val(env),
+                proof.into_val(env),
+                pub_signals_n.into_val(env),
+            ],
+        );
+        Self::check_groth16_result(env, verifier_result)?;
+        Ok(used_weapon)
+    }
+
+    /// GameRun signals as verifier inputs: exactly 11 32-byte values in Fr, bound to `run`.
+    /// Returns them with the used_zk_weapon flag.
+    fn groth16_signals(
+        env: &Env,
+        run: &RankedRun,
+        pub_signals: &Vec<Bytes>,
+    ) -> Result<(Vec<BytesN<32>>, bool), CosmicCoderError> {
+        // Strict: circuit expects exactly 11 public signals; legacy 7-signal proofs are not
+        // bound to a player and are rejected.
+        if pub_signals.len() != GAMERUN_PUB_SIGNALS {
             env.events().publish(
-                (Symbol::new(&env, "debug"), Symbol::new(&env, "submit_zk")),
-                Symbol::new(&env, "bad_pub_signals_len"),
+                (Symbol::new(env, "debug"), Symbol::new(env, "submit_zk")),
+                Symbol::new(env, "bad_pub_signals_len"),
             );
             return Err(CosmicCoderError::InvalidInput);
         }
-        let has_weapon_flag = true;
-        if vk.ic.len() != pub_signals.len() + 1 {
-            return Err(CosmicCoderError::MalformedVk);
-        }
 
-        // === 3b. Convert pub_signals (Bytes) -> BytesN<32> for verifier contract ===
         // Frontend encodes public signals as Vec<Bytes>. The verifier contract expects Vec<BytesN<32>>.
         // Validate each element is exactly 32 bytes, then convert.
-        let mut pub_signals_n: Vec<soroban_sdk::BytesN<32>> = Vec::new(&env);
-        let n = pub_signals.len();
-        for i in 0..n {
-            let b = pub_signals.get(i).unwrap();
+        let mut pub_signals_n: Vec<soroban_sdk::BytesN<32>> = Vec::new(env);
+        for b in pub_signals.iter() {
             if b.len() != 32 {
                 env.events()
                     .publish((symbol_short!("debug"),), symbol_short!("sig_len"));
                 return Err(CosmicCoderError::InvalidInput);
             }
             let mut arr = [0u8; 32];
-            for j in 0..32u32 {
-                arr[j as usize] = b.get(j).unwrap();
-            }
+            b.copy_into_slice(&mut arr);
             // Prevent BN254 host traps: reject scalars outside Fr (>= modulus).
             // Public inputs are interpreted as big-endian field elements.
             if is_ge_be_32(&arr, &BN254_FR_MODULUS_BE) {
                 env.events().publish(
-                    (Symbol::new(&env, "debug"), Symbol::new(&env, "submit_zk")),
-                    Symbol::new(&env, "bad_pub_signal_out_of_field"),
+                    (Symbol::new(env, "debug"), Symbol::new(env, "submit_zk")),
+                    Symbol::new(env, "bad_pub_signal_out_of_field"),
                 );
                 return Err(CosmicCoderError::InvalidZkProof);
             }
-            pub_signals_n.push_back(soroban_sdk::BytesN::from_array(&env, &arr));
-        }
-
-        // === 3. Validate inputs ===
-        if score == 0 || wave == 0 {
-            return Err(CosmicCoderError::InvalidInput);
-        }
-        let min_score = wave.saturating_mul(MIN_SCORE_PER_WAVE);
-        if score < min_score {
-            return Err(CosmicCoderError::InvalidInput);
+            pub_signals_n.push_back(soroban_sdk::BytesN::from_array(env, &arr));
         }
 
-        // === 4. STRICT ANTI-REPLAY: Check nonce BEFORE calling verifier ===
-        // Extract nonce from pub_signals[4] for additional validation
-        let nonce_key = NonceKey { nonce };
-        if env.storage().persistent().has(&nonce_key) {
-            return Err(CosmicCoderError::Replay);
-        }
-        
-        // Also check the full replay key
-        let replay_key = ReplayKey {
-            player: player.clone(),
-            nonce,
-            season_id,
-        };
-        if env.storage().persistent().has(&replay_key) {
-            return Err(CosmicCoderError::Replay);
-        }
+        // The proof only attests to pub_signals; the ranked entry is built from `run`,
+        // so every field must equal its signal.
+        let used_weapon = Self::check_run_signals(env, &pub_signals_n, run)?;
+        Ok((pub_signals_n, used_weapon))
+    }
 
-        // === 5. Call Groth16 verifier ===
-        let verifier_result = env.try_invoke_contract::<bool, Groth16Error>(
-            &verifier_addr,
-            &Symbol::new(&env, "verify_proof"),
-            soroban_sdk::vec![
-                &env,
-                vk.into_val(&env),
-                proof.into_val(&env),
-                pub_signals_n.clone().into_val(&env),
-            ],
-        );
+    /// Map a Groth16 verifier call (verify_proof / verify_batch) to the contract's errors.
+    fn check_groth16_result(
+        env: &Env,
+        verifier_result: Result<Result<bool, ConversionError>, Result<Groth16Error, InvokeError>>,
+    ) -> Result<(), CosmicCoderError> {
         let is_valid = match verifier_result {
             Ok(Ok(val)) => val,
-            Ok(Err(_e)) => {
+            // A typed Groth16Error (e.g. a malformed point) arrives as Err(Ok(_)).
+            Ok(Err(_)) | Err(Ok(_)) => {
                 env.events().publish(
-                    (Symbol::new(&env, "debug"), Symbol::new(&env, "host_call")),
-                    Symbol::new(&env, "verifier_returned_err"),
+                    (Symbol::new(env, "debug"), Symbol::new(env, "host_call")),
+                    Symbol::new(env, "verifier_returned_err"),
                 );
                 return Err(CosmicCoderError::VerifierError);
             }
-            Err(_host) => {
+            Err(Err(_host)) => {
                 env.events().publish(
-                    (Symbol::new(&env, "debug"), Symbol::new(&env, "host_call")),
-                    Symbol::new(&env, "verifier_host_call_failed"),
+                    (Symbol::new(env, "debug"), Symbol::new(env, "host_call")),
+                    Symbol::new(env, "verifier_host_call_failed"),
                 );
                 return Err(CosmicCoderError::VerifierCrash);
             }
@@ -412,219 +1449,117 @@ impl CosmicCoder {
                 .publish((symbol_short!("debug"),), symbol_short!("err_math"));
             return Err(CosmicCoderError::InvalidProof);
         }
+        Ok(())
+    }
 
-        // === 6. Mark nonce as used with TTL extension ===
-        env.storage().persistent().set(&nonce_key, &true);
-        env.storage().persistent().extend_ttl(&nonce_key, NONCE_TTL_LEDGERS, NONCE_TTL_LEDGERS);
-        
-        env.storage().persistent().set(&replay_key, &true);
-        env.storage().persistent().extend_ttl(&replay_key, NONCE_TTL_LEDGERS, NONCE_TTL_LEDGERS);
-
-        // === 7. Extract used_zk_weapon from pub_signals[6] and emit event ===
-        if has_weapon_flag {
-            let weapon_bytes = match pub_signals.get(6) {
-                Some(b) => b,
-                None => return Err(CosmicCoderError::InvalidInput),
-            };
-            // Check if last byte is 1 (used_zk_weapon = true).
-            // Frontend sends each pub_signal as 32-byte ScVal::Bytes (not BytesN),
-            // so we validate length and read the last byte.
-            if weapon_bytes.len() != 32 {
-                return Err(CosmicCoderError::InvalidInput);
-            }
-            let used_weapon = weapon_bytes.get(31) == Some(1);
-            
-            if used_weapon {
-                // Emit distinct ZK weapon event for frontend/Stellar Expert tracking
-                env.events().publish((symbol_short!("zk_wpn"), player.clone()), true);
-            }
+    /// UltraHonk backend: the verifier checks `proof` against its GameRun VK stored under the
+    /// configured id, with public inputs encoded from `run` (noir_run_inputs). The verifier
+    /// records this contract as the caller and rejects a proof it has already recorded.
+    /// Returns the used_zk_weapon flag.
+    fn verify_ultrahonk(
+        env: &Env,
+        verifier: &Address,
+        run: &RankedRun,
+        proof: &Bytes,
+        used_zk_weapon: bool,
+    ) -> Result<bool, CosmicCoderError> {
+        if proof.is_empty() {
+            return Err(CosmicCoderError::InvalidInput);
         }
-
-        // === 8. Call end_game() on Game Hub ===
-        let session: u32 = match env.storage().persistent().get::<DataKey, u32>(&DataKey::Session) {
-            Some(s) => s,
-            None => return Err(CosmicCoderError::GameHubCrash),
-        };
-        let hub_addr: Address = match env
+        let vk_id: UltraHonkVkId = env
             .storage()
             .persistent()
-            .get::<DataKey, Address>(&DataKey::GameHub)
-        {
-            Some(a) => a,
-            None => return Err(CosmicCoderError::GameHubCrash),
-        };
-
-        // end_game() on the hub returns () (not Result), so we must use invoke_contract
-        // (try_invoke_contract expects the callee to return Result<_, E> and can trap on decode).
-        // Breadcrumb before hub call: if a trap happens inside the hub, you'll still see this.
-        env.events()
-            .publish((symbol_short!("debug"),), symbol_short!("hub_call"));
-        env.invoke_contract::<()>(
-            &hub_addr,
-            &Symbol::new(&env, "end_game"),
-            // Must match mock hub signature exactly: (env, session_id_u32, true)
-            soroban_sdk::vec![&env, session.into_val(&env), true.into_val(&env)],
+            .get(&DataKey::UltraHonkVk)
+            .ok_or(CosmicCoderError::UnknownVk)?;
+        let mut run_hash = [0u8; 32];
+        run.run_hash.copy_into_slice(&mut run_hash);
+        let public_inputs = noir_run_inputs(
+            env,
+            &run_hash,
+            run.score,
+            run.wave,
+            run.nonce,
+            run.season_id,
+            used_zk_weapon,
         );
-
-        // === 9. Update leaderboard ===
-        let lb_key = LeaderboardKey { season_id };
-        let mut entries: Vec<ScoreEntry> = env.storage().persistent().get::<LeaderboardKey, Vec<ScoreEntry>>(&lb_key).unwrap_or(Vec::new(&env));
-        
-        let mut found = false;
-        let n = entries.len();
-        for i in 0..n {
-            let e = entries.get(i).unwrap();
-            if e.player == player {
-                found = true;
-                if score > e.score {
-                    entries.set(i as u32, ScoreEntry { player: player.clone(), score });
-                }
-                break;
-            }
-        }
-        if !found {
-            entries.push_back(ScoreEntry { player: player.clone(), score });
-        }
-        sort_leaderboard_desc(&env, &mut entries);
-        env.storage().persistent().set(&lb_key, &entries);
-        Self::update_player_milestone(&env, &player, season_id, wave);
-
-        // === 10. Emit main ZK run event ===
-        env.events().publish(
-            (Symbol::new(&env, "zk_run_submitted"), player.clone(), season_id, score, wave, run_hash),
-            (),
+        let verifier_result = env.try_invoke_contract::<soroban_sdk::BytesN<32>, UltraHonkError>(
+            verifier,
+            &Symbol::new(env, "verify_proof_with_stored_vk"),
+            soroban_sdk::vec![
+                env,
+                env.current_contract_address().into_val(env),
+                vk_id.into_val(env),
+                public_inputs.into_val(env),
+                proof.into_val(env),
+                true.into_val(env),
+            ],
         );
-
-        Ok(())
+        match verifier_result {
+            Ok(Ok(_proof_id)) => Ok(used_zk_weapon),
+            // UltraHonk rejects through its error type rather than returning false.
+            Err(Ok(UltraHonkError::VerificationFailed)) => Err(CosmicCoderError::InvalidProof),
+            Err(Ok(UltraHonkError::ProofAlreadyVerified)) => Err(CosmicCoderError::Replay),
+            Ok(Err(_)) | Err(Ok(_)) => Err(CosmicCoderError::VerifierError),
+            Err(Err(_)) => Err(CosmicCoderError::VerifierCrash),
+        }
     }
 
-    /// Ranked submit (Noir + UltraHonk): verifier uses stored VK, only proof_blob is passed (avoids invocation size limit).
-    /// The UltraHonk verifier must have been initialized once with set_vk(vk_json) for the GameRun circuit.
-    pub fn submit_zk_noir(
+    /// Get a page of the ranked leaderboard for a season (top by score, deterministic).
+    /// Only the top LEADERBOARD_CAP players are ranked.
+    pub fn get_leaderboard_by_season(
         env: Env,
-        player: Address,
-        proof_blob: Bytes,
-        nonce: u64,
-        run_hash: Bytes,
         season_id: u32,
-        score: u32,
-        wave: u32,
-    ) -> Result<(), CosmicCoderError> {
-        player.require_auth();
-
-        let verifier_addr: Address = match env
-            .storage()
-            .persistent()
-            .get::<DataKey, Address>(&DataKey::ZkVerifier)
-        {
-            Some(a) => a,
-            None => return Err(CosmicCoderError::VerifierNotSet),
-        };
-
-        if score == 0 || wave == 0 {
-            return Err(CosmicCoderError::InvalidInput);
-        }
-        let min_score = wave.saturating_mul(MIN_SCORE_PER_WAVE);
-        if score < min_score {
-            return Err(CosmicCoderError::InvalidInput);
-        }
-        if run_hash.len() != 32 || proof_blob.len() == 0 {
-            return Err(CosmicCoderError::InvalidInput);
-        }
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ScoreEntry> {
+        let entries = load_ranked(&env, season_id);
+        let start = core::cmp::min(offset, entries.len());
+        let end = core::cmp::min(start.saturating_add(limit), entries.len());
+        entries.slice(start..end)
+    }
 
-        let nonce_key = NonceKey { nonce };
-        if env.storage().persistent().has(&nonce_key) {
-            return Err(CosmicCoderError::Replay);
-        }
-        let replay_key = ReplayKey {
-            player: player.clone(),
-            nonce,
-            season_id,
-        };
-        if env.storage().persistent().has(&replay_key) {
-            return Err(CosmicCoderError::Replay);
+    /// Like get_leaderboard_by_season; with `exclude_weapon_assisted` the page comes from the
+    /// season's top list of runs that did not use the ZK weapon.
+    pub fn get_leaderboard_filtered(
+        env: Env,
+        season_id: u32,
+        exclude_weapon_assisted: bool,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ScoreEntry> {
+        if !exclude_weapon_assisted {
+            return Self::get_leaderboard_by_season(env, season_id, offset, limit);
         }
+        let entries = load_board(&env, &DataKey::UnassistedLeaderboard(season_id));
+        let start = core::cmp::min(offset, entries.len());
+        let end = core::cmp::min(start.saturating_add(limit), entries.len());
+        entries.slice(start..end)
+    }
 
-        let verifier_result = env.try_invoke_contract::<soroban_sdk::BytesN<32>, UltraHonkError>(
-            &verifier_addr,
-            &Symbol::new(&env, "verify_proof_with_stored_vk"),
-            soroban_sdk::vec![&env, proof_blob.into_val(&env)],
-        );
-        let _proof_id = match verifier_result {
-            Ok(Ok(pid)) => pid,
-            Ok(Err(_)) => return Err(CosmicCoderError::VerifierError),
-            Err(_) => return Err(CosmicCoderError::VerifierCrash),
-        };
-
-        env.storage().persistent().set(&nonce_key, &true);
-        env.storage().persistent().extend_ttl(&nonce_key, NONCE_TTL_LEDGERS, NONCE_TTL_LEDGERS);
-        env.storage().persistent().set(&replay_key, &true);
-        env.storage().persistent().extend_ttl(&replay_key, NONCE_TTL_LEDGERS, NONCE_TTL_LEDGERS);
-
-        let session: u32 = match env.storage().persistent().get::<DataKey, u32>(&DataKey::Session) {
-            Some(s) => s,
-            None => return Err(CosmicCoderError::GameHubCrash),
-        };
-        let hub_addr: Address = match env
-            .storage()
+    /// Number of ranked runs in a season that used the ZK weapon.
+    pub fn get_weapon_assisted_runs(env: Env, season_id: u32) -> u32 {
+        env.storage()
             .persistent()
-            .get::<DataKey, Address>(&DataKey::GameHub)
-        {
-            Some(a) => a,
-            None => return Err(CosmicCoderError::GameHubCrash),
-        };
-        env.invoke_contract::<()>(
-            &hub_addr,
-            &Symbol::new(&env, "end_game"),
-            soroban_sdk::vec![&env, session.into_val(&env), true.into_val(&env)],
-        );
+            .get(&DataKey::WeaponAssistedRuns(season_id))
+            .unwrap_or(0)
+    }
 
-        let lb_key = LeaderboardKey { season_id };
-        let mut entries: Vec<ScoreEntry> = env
+    /// 1-based rank of `player` in the season's top list; None if unranked or outside the top.
+    pub fn get_player_rank(env: Env, season_id: u32, player: Address) -> Option<u32> {
+        let best: u32 = env
             .storage()
             .persistent()
-            .get::<LeaderboardKey, Vec<ScoreEntry>>(&lb_key)
-            .unwrap_or(Vec::new(&env));
-        let mut found = false;
-        let n = entries.len();
-        for i in 0..n {
-            let e = entries.get(i).unwrap();
-            if e.player == player {
-                found = true;
-                if score > e.score {
-                    entries.set(i as u32, ScoreEntry { player: player.clone(), score });
-                }
-                break;
-            }
-        }
-        if !found {
-            entries.push_back(ScoreEntry { player: player.clone(), score });
-        }
-        sort_leaderboard_desc(&env, &mut entries);
-        env.storage().persistent().set(&lb_key, &entries);
-        Self::update_player_milestone(&env, &player, season_id, wave);
-
-        env.events().publish(
-            (Symbol::new(&env, "zk_run_submitted"), player.clone(), season_id, score, wave, run_hash),
-            (),
-        );
-
-        Ok(())
+            .get(&DataKey::BestScore(season_id, player.clone()))?;
+        let entries = load_ranked(&env, season_id);
+        find_player(&entries, &player, best).map(|i| i + 1)
     }
 
-    /// Get ranked leaderboard for a season (top by score, deterministic).
-    pub fn get_leaderboard_by_season(env: Env, season_id: u32, limit: u32) -> Vec<ScoreEntry> {
-        let key = LeaderboardKey { season_id };
-        let entries: Vec<ScoreEntry> = match env.storage().persistent().get::<LeaderboardKey, Vec<ScoreEntry>>(&key) {
-            Some(v) => v,
-            None => return Vec::new(&env),
-        };
-        let mut out = Vec::new(&env);
-        let n = core::cmp::min(limit, entries.len());
-        for i in 0..n {
-            out.push_back(entries.get(i).unwrap());
-        }
-        out
+    /// Player's best verified score for a season (0 if none), whether or not it is in the top list.
+    pub fn get_player_best(env: Env, season_id: u32, player: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BestScore(season_id, player))
+            .unwrap_or(0)
     }
 
     /// Get player's verified ranked milestone for a season.
@@ -723,53 +1658,124 @@ impl CosmicCoder {
         games >= 3 && best > 0
     }
 
-    /// Unlock weapon with ZK proof
-    /// weapon_id: 1=Starter, 2=Shotgun, 3=Tactical Rifle, 4=Plasma Rifle, 5=Quantum Destroyer
+    /// Add or replace a weapon catalog entry. Admin only. Ids are 1..=MAX_WEAPON_ID (one bit
+    /// each in the ownership bitmap); a `vk_hash`, if given, must be a registered SkillProof key (UnknownVk otherwise).
+    /// Emits ("weapon_set", id) with the entry.
+    pub fn set_weapon(env: Env, weapon: Weapon) -> Result<(), CosmicCoderError> {
+        Self::require_admin(&env)?;
+        if weapon.id == 0 || weapon.id > MAX_WEAPON_ID {
+            return Err(CosmicCoderError::InvalidInput);
+        }
+        if let Some(vk_hash) = &weapon.vk_hash {
+            match env
+                .storage()
+                .persistent()
+                .get::<DataKey, RegisteredVk>(&DataKey::Vk(vk_hash.clone()))
+            {
+                Some(entry) if entry.circuit == Circuit::SkillProof => {}
+                _ => return Err(CosmicCoderError::UnknownVk),
+            }
+        }
+        let key = DataKey::Weapon(weapon.id);
+        if !env.storage().persistent().has(&key) {
+            let mut list = Self::weapon_ids(&env);
+            list.push_back(weapon.id);
+            env.storage().persistent().set(&DataKey::WeaponList, &list);
+        }
+        env.storage().persistent().set(&key, &weapon);
+        env.events().publish(
+            (Symbol::new(&env, "weapon_set"), weapon.id),
+            weapon,
+        );
+        Ok(())
+    }
+
+    /// Catalog entry for `weapon_id`, if any.
+    pub fn get_weapon(env: Env, weapon_id: u32) -> Option<Weapon> {
+        env.storage().persistent().get(&DataKey::Weapon(weapon_id))
+    }
+
+    /// Every catalog entry (enabled or not), in creation order.
+    pub fn list_weapons(env: Env) -> Vec<Weapon> {
+        let mut out = Vec::new(&env);
+        for weapon_id in Self::weapon_ids(&env).iter() {
+            if let Some(weapon) = Self::get_weapon(env.clone(), weapon_id) {
+                out.push_back(weapon);
+            }
+        }
+        out
+    }
+
+    fn weapon_ids(env: &Env) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WeaponList)
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Unlock a catalog weapon with a ZK proof (SkillProof circuit, VK registered via register_vk).
+    /// The weapon must exist (UnknownWeapon) and be enabled (WeaponDisabled), `threshold` must
+    /// be its threshold, `vk_hash` its required key if it names one, and a season-restricted
+    /// weapon only unlocks while that season is open.
+    /// `pub_signals` must be exactly [threshold, publicHash, player_hi, player_lo], with the
+    /// player signals equal to `address_commitment(player)` as in GameRun (PublicInputMismatch
+    /// otherwise), so a proof only unlocks for the wallet it was made for. A successful unlock
+    /// consumes the publicHash (CommitmentUsed on reuse).
     pub fn unlock_weapon(
         env: Env,
         player: Address,
         weapon_id: u32,
         proof: ZkProof,
-        vk: ZkVerificationKey,
+        vk_hash: BytesN<32>,
         pub_signals: Vec<soroban_sdk::BytesN<32>>,
         threshold: u32,
     ) -> Result<(), CosmicCoderError> {
         player.require_auth();
 
-        // Verify weapon_id is valid (1-5)
-        if weapon_id < 1 || weapon_id > 5 {
-            return Err(CosmicCoderError::InvalidInput);
+        // Look up the weapon in the catalog
+        let weapon = Self::get_weapon(env.clone(), weapon_id).ok_or(CosmicCoderError::UnknownWeapon)?;
+        if !weapon.enabled {
+            return Err(CosmicCoderError::WeaponDisabled);
         }
-
-        // Verify threshold matches weapon_id
-        let expected_threshold = match weapon_id {
-            1 => 0u32,
-            2 => 1000u32,
-            3 => 5000u32,
-            4 => 10000u32,
-            5 => 20000u32,
-            _ => return Err(CosmicCoderError::InvalidInput),
-        };
-        if threshold != expected_threshold {
+        if threshold != weapon.threshold {
             return Err(CosmicCoderError::InvalidInput);
         }
+        if let Some(required) = &weapon.vk_hash {
+            if *required != vk_hash {
+                return Err(CosmicCoderError::UnknownVk);
+            }
+        }
+        if let Some(season_id) = weapon.season_id {
+            Self::require_open_season(&env, season_id)?;
+        }
 
         // Check if already unlocked
-        let unlock_key = WeaponUnlockKey { player: player.clone(), weapon_id };
-        let already_unlocked: bool = env.storage().persistent().get(&unlock_key).unwrap_or(false);
-        if already_unlocked {
+        let mut owned = load_weapon_bits(&env, &player);
+        if has_weapon_bit(&owned, weapon_id) {
             return Err(CosmicCoderError::InvalidInput);
         }
 
         // Get verifier
-        let verifier: Address = match env.storage().persistent().get::<DataKey, Address>(&DataKey::ZkVerifier) {
-            Some(a) => a,
-            None => return Err(CosmicCoderError::VerifierNotSet),
-        };
+        let verifier = Self::load_verifier(&env, VerifierKind::WeaponUnlock)?;
 
-        // Verify ZK proof
-        if vk.ic.len() != pub_signals.len() + 1 {
-            return Err(CosmicCoderError::MalformedVk);
+        // Verify ZK proof against the registered SkillProof VK
+        let vk = Self::load_vk(&env, Circuit::SkillProof, &vk_hash)?;
+        if pub_signals.len() != SKILLPROOF_PUB_SIGNALS {
+            return Err(CosmicCoderError::InvalidInput);
+        }
+
+        // Bind the proof to this unlock: [threshold, publicHash, player_hi, player_lo]
+        let threshold_signal = BytesN::from_array(&env, &u64_signal(threshold as u64));
+        let (player_hi, player_lo) = address_commitment(&env, &player);
+        let bound = [(0u32, threshold_signal), (2, player_hi), (3, player_lo)];
+        for (i, want) in bound.iter() {
+            if pub_signals.get(*i).as_ref() != Some(want) {
+                return Err(CosmicCoderError::PublicInputMismatch);
+            }
+        }
+        let used_key = DataKey::UsedUnlockHash(pub_signals.get(1).unwrap());
+        if env.storage().persistent().has(&used_key) {
+            return Err(CosmicCoderError::CommitmentUsed);
         }
 
         let raw = env.try_invoke_contract::<bool, Groth16Error>(
@@ -791,8 +1797,10 @@ impl CosmicCoder {
             return Err(CosmicCoderError::InvalidProof);
         }
 
-        // Mark weapon as unlocked
-        env.storage().persistent().set(&unlock_key, &true);
+        // Mark weapon as unlocked and consume the publicHash
+        set_weapon_bit(&mut owned, weapon_id);
+        store_weapon_bits(&env, &player, &owned);
+        env.storage().persistent().set(&used_key, &true);
 
         // Emit event
         env.events().publish(
@@ -805,35 +1813,190 @@ impl CosmicCoder {
 
     /// Check if weapon is unlocked for player
     pub fn is_weapon_unlocked(env: Env, player: Address, weapon_id: u32) -> bool {
-        let key = WeaponUnlockKey { player, weapon_id };
-        env.storage().persistent().get(&key).unwrap_or(false)
+        has_weapon_bit(&load_weapon_bits(&env, &player), weapon_id)
     }
 
-    /// Get all unlocked weapons for player
+    /// Get all unlocked weapons for player, in id order (one storage read)
     pub fn get_unlocked_weapons(env: Env, player: Address) -> Vec<u32> {
+        let owned = load_weapon_bits(&env, &player);
         let mut unlocked = Vec::new(&env);
-        for weapon_id in 1..=5u32 {
-            if Self::is_weapon_unlocked(env.clone(), player.clone(), weapon_id) {
+        for weapon_id in 1..=MAX_WEAPON_ID {
+            if has_weapon_bit(&owned, weapon_id) {
                 unlocked.push_back(weapon_id);
             }
         }
         unlocked
     }
-}
 
-/// Sort by score desc (deterministic; equal scores keep relative order).
-fn sort_leaderboard_desc(_env: &Env, entries: &mut Vec<ScoreEntry>) {
-    let n = entries.len();
-    for i in 0..n {
-        for j in (i + 1)..n {
-            let a = entries.get(i).unwrap().clone();
-            let b = entries.get(j).unwrap().clone();
-            if b.score > a.score {
-                entries.set(i as u32, b);
-                entries.set(j as u32, a);
+    /// Ownership bitmaps of up to MAX_WEAPON_BATCH players, in order (bit `id % 8` of byte
+    /// `id / 8` set = weapon `id` unlocked; all zero for players without unlocks).
+    pub fn get_weapon_bitmaps(env: Env, players: Vec<Address>) -> Result<Vec<BytesN<32>>, CosmicCoderError> {
+        if players.len() > MAX_WEAPON_BATCH {
+            return Err(CosmicCoderError::InvalidInput);
+        }
+        let mut out = Vec::new(&env);
+        for player in players.iter() {
+            out.push_back(BytesN::from_array(&env, &load_weapon_bits(&env, &player)));
+        }
+        Ok(out)
+    }
+
+    /// Move legacy WeaponUnlockKey entries of catalog weapons into the players' bitmaps and
+    /// delete them. Admin only; at most MAX_WEAPON_BATCH players per call, safe to repeat.
+    /// Returns the number of unlocks moved.
+    pub fn migrate_weapon_unlocks(env: Env, players: Vec<Address>) -> Result<u32, CosmicCoderError> {
+        Self::require_admin(&env)?;
+        if players.len() > MAX_WEAPON_BATCH {
+            return Err(CosmicCoderError::InvalidInput);
+        }
+        let weapon_ids = Self::weapon_ids(&env);
+        let mut moved = 0u32;
+        for player in players.iter() {
+            let mut owned = load_weapon_bits(&env, &player);
+            let mut changed = false;
+            for weapon_id in weapon_ids.iter() {
+                let key = WeaponUnlockKey {
+                    player: player.clone(),
+                    weapon_id,
+                };
+                if let Some(unlocked) = env.storage().persistent().get::<WeaponUnlockKey, bool>(&key) {
+                    if unlocked {
+                        set_weapon_bit(&mut owned, weapon_id);
+                        changed = true;
+                        moved += 1;
+                    }
+                    env.storage().persistent().remove(&key);
+                }
             }
+            if changed {
+                store_weapon_bits(&env, &player, &owned);
+            }
+        }
+        Ok(moved)
+    }
+}
+
+/// Player's weapon ownership bitmap (all zero if none); extends its TTL when present.
+fn load_weapon_bits(env: &Env, player: &Address) -> [u8; 32] {
+    let key = DataKey::WeaponBits(player.clone());
+    match env.storage().persistent().get::<DataKey, BytesN<32>>(&key) {
+        Some(bits) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, WEAPON_TTL_LEDGERS, WEAPON_TTL_LEDGERS);
+            bits.to_array()
+        }
+        None => [0u8; 32],
+    }
+}
+
+fn store_weapon_bits(env: &Env, player: &Address, bits: &[u8; 32]) {
+    let key = DataKey::WeaponBits(player.clone());
+    env.storage().persistent().set(&key, &BytesN::from_array(env, bits));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, WEAPON_TTL_LEDGERS, WEAPON_TTL_LEDGERS);
+}
+
+fn has_weapon_bit(bits: &[u8; 32], weapon_id: u32) -> bool {
+    weapon_id <= MAX_WEAPON_ID && bits[(weapon_id / 8) as usize] & (1 << (weapon_id % 8)) != 0
+}
+
+fn set_weapon_bit(bits: &mut [u8; 32], weapon_id: u32) {
+    bits[(weapon_id / 8) as usize] |= 1 << (weapon_id % 8);
+}
+
+fn load_ranked(env: &Env, season_id: u32) -> Vec<ScoreEntry> {
+    load_board(env, &LeaderboardKey { season_id })
+}
+
+fn load_board<K: IntoVal<Env, Val>>(env: &Env, board_key: &K) -> Vec<ScoreEntry> {
+    env.storage()
+        .persistent()
+        .get(board_key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// First index whose score is strictly below `score` (entries are sorted desc).
+fn rank_insert_index(entries: &Vec<ScoreEntry>, score: u32) -> u32 {
+    let (mut lo, mut hi) = (0u32, entries.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if entries.get_unchecked(mid).score >= score {
+            lo = mid + 1;
+        } else {
+            hi = mid;
         }
     }
+    lo
+}
+
+/// Index of `player`'s entry, which must carry `score`: binary search, then scan the ties.
+fn find_player(entries: &Vec<ScoreEntry>, player: &Address, score: u32) -> Option<u32> {
+    let mut i = rank_insert_index(entries, score);
+    while i > 0 {
+        i -= 1;
+        let e = entries.get_unchecked(i);
+        if e.score != score {
+            return None;
+        }
+        if e.player == *player {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Record a verified score: keep the player's season best and the bounded, sorted top list.
+/// Equal scores rank by who reached them first. Cost is O(LEADERBOARD_CAP) regardless of
+/// how many players the season has.
+fn record_ranked_score(env: &Env, season_id: u32, player: &Address, score: u32) {
+    record_on_board(
+        env,
+        &LeaderboardKey { season_id },
+        &DataKey::BestScore(season_id, player.clone()),
+        player,
+        score,
+    );
+}
+
+/// record_ranked_score for the top list of runs without the ZK weapon.
+fn record_unassisted_score(env: &Env, season_id: u32, player: &Address, score: u32) {
+    record_on_board(
+        env,
+        &DataKey::UnassistedLeaderboard(season_id),
+        &DataKey::UnassistedBest(season_id, player.clone()),
+        player,
+        score,
+    );
+}
+
+fn record_on_board<K: IntoVal<Env, Val>>(
+    env: &Env,
+    board_key: &K,
+    best_key: &DataKey,
+    player: &Address,
+    score: u32,
+) {
+    let best: Option<u32> = env.storage().persistent().get(best_key);
+    if best.is_some_and(|b| score <= b) {
+        return;
+    }
+    env.storage().persistent().set(best_key, &score);
+
+    let mut entries = load_board(env, board_key);
+    let pos = rank_insert_index(&entries, score);
+    if pos >= LEADERBOARD_CAP {
+        return;
+    }
+    if let Some(i) = best.and_then(|b| find_player(&entries, player, b)) {
+        entries.remove(i);
+    }
+    entries.insert(pos, ScoreEntry { player: player.clone(), score });
+    if entries.len() > LEADERBOARD_CAP {
+        entries.pop_back();
+    }
+    env.storage().persistent().set(board_key, &entries);
 }
 
 #[cfg(test)]
@@ -7,12 +7,20 @@ extern crate std;
 use std::panic::catch_unwind;
 
 use soroban_sdk::{
-    contract, contractimpl, vec, Address, Bytes, Env, Vec as SorobanVec,
-    testutils::Address as _,
+    contract, contractimpl, vec, Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec as SorobanVec,
+    testutils::{
+        storage::Persistent as _, Address as _, AuthorizedFunction, AuthorizedInvocation, Events as _, Ledger,
+        MockAuth, MockAuthInvoke,
+    },
 };
-use zk_types::{ZkProof, ZkVerificationKey, G1_SIZE, G2_SIZE, FR_SIZE};
+use zk_types::{address_commitment, VkState, ZkProof, ZkVerificationKey, G1_SIZE, G2_SIZE, FR_SIZE};
 
-use crate::{CosmicCoder, CosmicCoderClient, PlayerMilestone};
+use crate::{
+    noir_run_inputs, record_ranked_score, set_weapon_bit, store_weapon_bits, u64_signal, Circuit, CosmicCoder,
+    CosmicCoderClient, CosmicCoderError, PlayerMilestone, RankedRun, ScoreEntry, Season, SessionStatus, UltraHonkError,
+    UltraHonkVkId, VerifierKind, VkInfo, Weapon, WeaponUnlockKey, ZkRunSubmission, LEADERBOARD_CAP,
+    MAX_WEAPON_BATCH, MAX_ZK_BATCH, WEAPON_TTL_LEDGERS, ZK_WEAPON_ID,
+};
 use groth16_verifier::{Groth16Verifier, Groth16VerifierClient};
 
 #[contract]
@@ -34,6 +42,116 @@ impl MockHub {
     }
 }
 
+/// Groth16 verifier stand-in that accepts every proof, for checks made around verification.
+#[contract]
+struct AcceptingVerifier;
+
+#[contractimpl]
+impl AcceptingVerifier {
+    pub fn verify_proof(
+        _env: Env,
+        _vk: ZkVerificationKey,
+        _proof: ZkProof,
+        _pub_signals: soroban_sdk::Vec<BytesN<32>>,
+    ) -> bool {
+        true
+    }
+}
+
+/// UltraHonk verifier stand-in: a proof is valid for exactly the public inputs it was made
+/// for (see honk_proof); with reject_seen, a proof it has already accepted is refused.
+#[contract]
+struct MockUltraHonk;
+
+#[contractimpl]
+impl MockUltraHonk {
+    pub fn verify_proof_with_stored_vk(
+        env: Env,
+        _caller: Address,
+        vk_id: UltraHonkVkId,
+        public_inputs: soroban_sdk::Vec<BytesN<32>>,
+        proof: Bytes,
+        reject_seen: bool,
+    ) -> Result<BytesN<32>, UltraHonkError> {
+        if vk_id != gamerun_honk_vk(&env, 1) {
+            return Err(UltraHonkError::VkNotSet);
+        }
+        if proof != honk_proof(&env, &public_inputs) {
+            return Err(UltraHonkError::VerificationFailed);
+        }
+        let proof_id: BytesN<32> = env.crypto().sha256(&proof).into();
+        if reject_seen && env.storage().temporary().has(&proof_id) {
+            return Err(UltraHonkError::ProofAlreadyVerified);
+        }
+        env.storage().temporary().set(&proof_id, &true);
+        Ok(proof_id)
+    }
+}
+
+/// Id of the GameRun key the mock UltraHonk verifier holds (version 1 only).
+fn gamerun_honk_vk(env: &Env, version: u32) -> UltraHonkVkId {
+    UltraHonkVkId {
+        circuit: Symbol::new(env, "gamerun"),
+        version,
+    }
+}
+
+/// Mock UltraHonk proof over `public_inputs`: sha256 of the concatenated words.
+fn honk_proof(env: &Env, public_inputs: &soroban_sdk::Vec<BytesN<32>>) -> Bytes {
+    let mut words = Bytes::new(env);
+    for w in public_inputs.iter() {
+        words.extend_from_array(&w.to_array());
+    }
+    env.crypto().sha256(&words).into()
+}
+
+/// Noir GameRun public inputs for run_hash_32 (all-zero hash, so no folding).
+fn noir_inputs(
+    env: &Env,
+    score: u32,
+    wave: u32,
+    nonce: u64,
+    season_id: u32,
+    used_zk_weapon: bool,
+) -> soroban_sdk::Vec<BytesN<32>> {
+    let mut v = soroban_sdk::Vec::new(env);
+    for x in [0, 0, score as u64, wave as u64, nonce, season_id as u64, used_zk_weapon as u64] {
+        v.push_back(BytesN::from_array(env, &u64_signal(x)));
+    }
+    v
+}
+
+/// Hub that records every end_game(session, success) call.
+#[contract]
+struct RecordingHub;
+
+#[contractimpl]
+impl RecordingHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session: u32,
+        _player: Address,
+        _system_player: Address,
+        _x: i128,
+        _y: i128,
+    ) {
+    }
+    pub fn end_game(env: Env, session: u32, success: bool) {
+        let key = Symbol::new(&env, "ended");
+        let mut ended: SorobanVec<(u32, bool)> =
+            env.storage().instance().get(&key).unwrap_or(SorobanVec::new(&env));
+        ended.push_back((session, success));
+        env.storage().instance().set(&key, &ended);
+    }
+    pub fn ended(env: Env) -> soroban_sdk::Vec<(u32, bool)> {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "ended"))
+            .unwrap_or(SorobanVec::new(&env))
+    }
+}
+
 fn g1(env: &Env) -> soroban_sdk::BytesN<64> {
     soroban_sdk::BytesN::from_array(env, &[0u8; G1_SIZE])
 }
@@ -41,29 +159,90 @@ fn g2(env: &Env) -> soroban_sdk::BytesN<128> {
     soroban_sdk::BytesN::from_array(env, &[0u8; G2_SIZE])
 }
 
-/// Default VK with ic.len() = pub_signals.len() + 1 = 8 (for 7 pub_signals)
+/// Default VK with ic.len() = pub_signals.len() + 1 = 12 (for 11 pub_signals)
 fn default_vk(env: &Env) -> ZkVerificationKey {
+    vk_with_ic_len(env, 12)
+}
+
+fn vk_with_ic_len(env: &Env, n: u32) -> ZkVerificationKey {
     let g1 = g1(env);
+    let mut ic = SorobanVec::new(env);
+    for _ in 0..n {
+        ic.push_back(g1.clone());
+    }
     ZkVerificationKey {
-        alpha: g1.clone(),
+        alpha: g1,
         beta: g2(env),
         gamma: g2(env),
         delta: g2(env),
-        // ic.len() = pub_signals.len() + 1 = 8
-        ic: vec![
-            env,
-            g1.clone(),
-            g1.clone(),
-            g1.clone(),
-            g1.clone(),
-            g1.clone(),
-            g1.clone(),
-            g1.clone(),
-            g1,
-        ],
+        ic,
+    }
+}
+
+/// Ledger window of seasons created by open_season.
+const SEASON_LEDGERS: u32 = 100_000;
+
+/// Create season `id` (admin auth mocked), open from ledger 0 for SEASON_LEDGERS ledgers.
+fn open_season(client: &CosmicCoderClient, id: u32) {
+    client.create_season(&id, &0, &SEASON_LEDGERS);
+}
+
+/// Register default_vk as the GameRun key (admin auth mocked) and return its hash.
+fn register_default_vk(env: &Env, client: &CosmicCoderClient) -> BytesN<32> {
+    client.register_vk(&Circuit::GameRun, &default_vk(env))
+}
+
+/// Catalog entry with no key or season restriction.
+fn weapon(env: &Env, id: u32, name: &str, threshold: u32) -> Weapon {
+    Weapon {
+        id,
+        name: Symbol::new(env, name),
+        threshold,
+        vk_hash: None,
+        enabled: true,
+        season_id: None,
+    }
+}
+
+/// The game's five weapons (src/config/weapons.js) as catalog entries.
+fn add_default_weapons(env: &Env, client: &CosmicCoderClient) {
+    for w in [
+        weapon(env, 1, "Starter", 0),
+        weapon(env, 2, "Shotgun", 1000),
+        weapon(env, 3, "TacticalRifle", 5000),
+        weapon(env, 4, "PlasmaRifle", 10000),
+        weapon(env, 5, "QuantumDestroyer", 20000),
+    ] {
+        client.set_weapon(&w);
     }
 }
 
+/// Give `player` a weapon as a successful unlock_weapon would.
+fn grant_weapon(env: &Env, client: &CosmicCoderClient, player: &Address, weapon_id: u32) {
+    env.as_contract(&client.address, || {
+        let mut bits = [0u8; 32];
+        set_weapon_bit(&mut bits, weapon_id);
+        store_weapon_bits(env, player, &bits);
+    });
+}
+
+/// SkillProof signals [threshold, publicHash, player_hi, player_lo] for `player`.
+fn skill_signals(
+    env: &Env,
+    threshold: u32,
+    public_hash: &BytesN<32>,
+    player: &Address,
+) -> SorobanVec<BytesN<32>> {
+    let (player_hi, player_lo) = address_commitment(env, player);
+    vec![
+        env,
+        BytesN::from_array(env, &u64_signal(threshold as u64)),
+        public_hash.clone(),
+        player_hi,
+        player_lo,
+    ]
+}
+
 fn default_proof(env: &Env) -> ZkProof {
     ZkProof {
         a: g1(env),
@@ -72,19 +251,59 @@ fn default_proof(env: &Env) -> ZkProof {
     }
 }
 
-/// Default pub_signals with 7 elements matching circuit outputs:
-/// [run_hash_hi, run_hash_lo, score, wave, nonce, season_id, used_zk_weapon]
-fn default_pub_signals(env: &Env) -> SorobanVec<Bytes> {
-    let zero = Bytes::from_slice(env, &[0u8; FR_SIZE]);
+/// 32-byte big-endian field encoding of an integer signal.
+fn signal(env: &Env, v: u64) -> Bytes {
+    let mut arr = [0u8; FR_SIZE];
+    arr[24..].copy_from_slice(&v.to_be_bytes());
+    Bytes::from_slice(env, &arr)
+}
+
+/// Ranked run claim passed to submit_zk / submit_zk_noir.
+fn ranked_run(
+    player: &Address,
+    session_id: u32,
+    nonce: u64,
+    run_hash: &Bytes,
+    season_id: u32,
+    score: u32,
+    wave: u32,
+) -> RankedRun {
+    RankedRun {
+        player: player.clone(),
+        session_id,
+        nonce,
+        run_hash: run_hash.clone(),
+        season_id,
+        score,
+        wave,
+    }
+}
+
+/// pub_signals with 11 elements matching circuit outputs and `run` (session_id is not a signal):
+/// [run_hash_hi, run_hash_lo, score, wave, nonce, season_id, used_zk_weapon,
+///  player_hi, player_lo, contract_hi, contract_lo]
+fn run_signals(env: &Env, contract: &Address, run: &RankedRun) -> SorobanVec<Bytes> {
+    let mut hi = [0u8; FR_SIZE];
+    let mut lo = [0u8; FR_SIZE];
+    for i in 0..16u32 {
+        hi[16 + i as usize] = run.run_hash.get(i).unwrap();
+        lo[16 + i as usize] = run.run_hash.get(16 + i).unwrap();
+    }
+    let (player_hi, player_lo) = address_commitment(env, &run.player);
+    let (contract_hi, contract_lo) = address_commitment(env, contract);
     vec![
         env,
-        zero.clone(),
-        zero.clone(),
-        zero.clone(),
-        zero.clone(),
-        zero.clone(),
-        zero.clone(),
-        zero,
+        Bytes::from_slice(env, &hi),
+        Bytes::from_slice(env, &lo),
+        signal(env, run.score as u64),
+        signal(env, run.wave as u64),
+        signal(env, run.nonce),
+        signal(env, run.season_id as u64),
+        signal(env, 0),
+        Bytes::from_slice(env, &player_hi.to_array()),
+        Bytes::from_slice(env, &player_lo.to_array()),
+        Bytes::from_slice(env, &contract_hi.to_array()),
+        Bytes::from_slice(env, &contract_lo.to_array()),
     ]
 }
 
@@ -103,20 +322,24 @@ fn test_submit_zk_fails_when_verifier_not_set() {
     let policy_client = CosmicCoderClient::new(&env, &policy);
 
     let invalid_verifier = Address::generate(&env);
-    policy_client.init(&hub, &invalid_verifier);
+    policy_client.init(&Address::generate(&env), &hub, &invalid_verifier);
+    let vk_hash = register_default_vk(&env, &policy_client);
     let player = Address::generate(&env);
 
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         policy_client.submit_zk(
-            &player,
+            &ranked_run(
+                &player,
+                policy_client.start_match(&player),
+                1u64,
+                &run_hash_32(&env),
+                1u32,
+                100u32,
+                5u32,
+            ),
             &default_proof(&env),
-            &default_vk(&env),
-            &default_pub_signals(&env),
-            &1u64,
-            &run_hash_32(&env),
-            &1u32,
-            &100u32,
-            &5u32,
+            &vk_hash,
+            &run_signals(&env, &policy, &ranked_run(&player, 0, 1, &run_hash_32(&env), 1, 100, 5)),
         );
     }));
     assert!(res.is_err(), "submit_zk without verifier should panic");
@@ -133,49 +356,49 @@ fn test_submit_zk_anti_replay() {
     let policy = env.register(CosmicCoder, ());
     let policy_client = CosmicCoderClient::new(&env, &policy);
 
-    policy_client.init(&hub, &verifier);
+    policy_client.init(&Address::generate(&env), &hub, &verifier);
+    open_season(&policy_client, 1);
 
     let player = Address::generate(&env);
     let nonce = 42u64;
     let season_id = 1u32;
     let run_hash = run_hash_32(&env);
-    let vk = default_vk(&env);
+    let vk_hash = register_default_vk(&env, &policy_client);
     let proof = default_proof(&env);
-    let pub_signals = default_pub_signals(&env);
     let score = 100u32;
     let wave = 5u32;
+    let pub_signals =
+        run_signals(&env, &policy, &ranked_run(&player, 0, nonce, &run_hash, season_id, score, wave));
 
     policy_client.submit_zk(
-        &player,
+        &ranked_run(&player, policy_client.start_match(&player), nonce, &run_hash, season_id, score, wave),
         &proof,
-        &vk,
+        &vk_hash,
         &pub_signals,
-        &nonce,
-        &run_hash,
-        &season_id,
-        &score,
-        &wave,
     );
 
     let res2 = catch_unwind(std::panic::AssertUnwindSafe(|| {
         policy_client.submit_zk(
-            &player,
+            &ranked_run(
+                &player,
+                policy_client.start_match(&player),
+                nonce,
+                &run_hash,
+                season_id,
+                score,
+                wave,
+            ),
             &proof,
-            &vk,
+            &vk_hash,
             &pub_signals,
-            &nonce,
-            &run_hash,
-            &season_id,
-            &score,
-            &wave,
         );
     }));
     assert!(res2.is_err(), "replay submit_zk should panic");
 }
 
-/// submit_zk with vk that has wrong ic length: verifier returns Err -> policy returns VerifierError.
+/// A VK whose ic length does not match the circuit's public signals cannot be registered.
 #[test]
-fn test_submit_zk_invalid_proof_verifier_error() {
+fn test_register_vk_rejects_wrong_ic_length() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -184,33 +407,24 @@ fn test_submit_zk_invalid_proof_verifier_error() {
     let policy = env.register(CosmicCoder, ());
     let policy_client = CosmicCoderClient::new(&env, &policy);
 
-    policy_client.init(&hub, &verifier);
+    policy_client.init(&Address::generate(&env), &hub, &verifier);
 
-    let player = Address::generate(&env);
     let g1 = g1(&env);
     let vk_bad = ZkVerificationKey {
         alpha: g1.clone(),
         beta: g2(&env),
         gamma: g2(&env),
         delta: g2(&env),
-        ic: vec![&env, g1], // len 1, but pub_signals.len() + 1 = 2
+        ic: vec![&env, g1], // len 1, but GameRun needs 11 + 1
     };
-    let pub_signals = default_pub_signals(&env);
-
-    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
-        policy_client.submit_zk(
-            &player,
-            &default_proof(&env),
-            &vk_bad,
-            &pub_signals,
-            &1u64,
-            &run_hash_32(&env),
-            &1u32,
-            &100u32,
-            &5u32,
-        );
-    }));
-    assert!(res.is_err(), "submit_zk with bad VK should panic");
+    assert_eq!(
+        policy_client.try_register_vk(&Circuit::GameRun, &vk_bad),
+        Err(Ok(CosmicCoderError::MalformedVk))
+    );
+    assert_eq!(
+        policy_client.try_register_vk(&Circuit::SkillProof, &default_vk(&env)),
+        Err(Ok(CosmicCoderError::MalformedVk))
+    );
 }
 
 /// submit_zk with score < wave * MIN_SCORE_PER_WAVE: InvalidInput (progress rule).
@@ -224,21 +438,25 @@ fn test_submit_zk_invalid_input_score_below_min() {
     let policy = env.register(CosmicCoder, ());
     let policy_client = CosmicCoderClient::new(&env, &policy);
 
-    policy_client.init(&hub, &verifier);
+    policy_client.init(&Address::generate(&env), &hub, &verifier);
+    let vk_hash = register_default_vk(&env, &policy_client);
     let player = Address::generate(&env);
 
     // wave=5 -> min_score=25 (MIN_SCORE_PER_WAVE=5); score=20 fails
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         policy_client.submit_zk(
-            &player,
+            &ranked_run(
+                &player,
+                policy_client.start_match(&player),
+                1u64,
+                &run_hash_32(&env),
+                1u32,
+                20u32,
+                5u32,
+            ),
             &default_proof(&env),
-            &default_vk(&env),
-            &default_pub_signals(&env),
-            &1u64,
-            &run_hash_32(&env),
-            &1u32,
-            &20u32,
-            &5u32,
+            &vk_hash,
+            &run_signals(&env, &policy, &ranked_run(&player, 0, 1, &run_hash_32(&env), 1, 20, 5)),
         );
     }));
     assert!(res.is_err(), "submit_zk with score < wave*MIN_SCORE_PER_WAVE should panic");
@@ -255,20 +473,24 @@ fn test_submit_zk_invalid_input_zero_score() {
     let policy = env.register(CosmicCoder, ());
     let policy_client = CosmicCoderClient::new(&env, &policy);
 
-    policy_client.init(&hub, &verifier);
+    policy_client.init(&Address::generate(&env), &hub, &verifier);
+    let vk_hash = register_default_vk(&env, &policy_client);
     let player = Address::generate(&env);
 
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         policy_client.submit_zk(
-            &player,
+            &ranked_run(
+                &player,
+                policy_client.start_match(&player),
+                1u64,
+                &run_hash_32(&env),
+                1u32,
+                0u32,
+                5u32,
+            ),
             &default_proof(&env),
-            &default_vk(&env),
-            &default_pub_signals(&env),
-            &1u64,
-            &run_hash_32(&env),
-            &1u32,
-            &0u32,
-            &5u32,
+            &vk_hash,
+            &run_signals(&env, &policy, &ranked_run(&player, 0, 1, &run_hash_32(&env), 1, 0, 5)),
         );
     }));
     assert!(res.is_err(), "submit_zk with score 0 should panic");
@@ -285,7 +507,8 @@ fn test_submit_zk_valid_updates_nonce_leaderboard_and_emits_event() {
     let policy = env.register(CosmicCoder, ());
     let policy_client = CosmicCoderClient::new(&env, &policy);
 
-    policy_client.init(&hub, &verifier);
+    policy_client.init(&Address::generate(&env), &hub, &verifier);
+    open_season(&policy_client, 2);
 
     let player = Address::generate(&env);
     let nonce = 77u64;
@@ -293,29 +516,95 @@ fn test_submit_zk_valid_updates_nonce_leaderboard_and_emits_event() {
     let score = 200u32;
     let wave = 10u32;
     let run_hash = run_hash_32(&env);
-    let vk = default_vk(&env);
+    let vk_hash = register_default_vk(&env, &policy_client);
     let proof = default_proof(&env);
-    let pub_signals = default_pub_signals(&env);
+    let pub_signals =
+        run_signals(&env, &policy, &ranked_run(&player, 0, nonce, &run_hash, season_id, score, wave));
 
     policy_client.submit_zk(
-        &player,
+        &ranked_run(&player, policy_client.start_match(&player), nonce, &run_hash, season_id, score, wave),
         &proof,
-        &vk,
+        &vk_hash,
         &pub_signals,
-        &nonce,
-        &run_hash,
-        &season_id,
-        &score,
-        &wave,
     );
 
-    let lb = policy_client.get_leaderboard_by_season(&season_id, &10);
-    assert!(lb.len() > 0, "leaderboard updated and zk_run_submitted emitted");
+    let lb = policy_client.get_leaderboard_by_season(&season_id, &0, &10);
+    assert!(!lb.is_empty(), "leaderboard updated and zk_run_submitted emitted");
     let first = lb.get(0).unwrap();
     assert_eq!(first.player, player);
     assert_eq!(first.score, score);
 }
 
+/// Sorted insertion, improvement moves the player up, ties rank by arrival, pagination and rank.
+#[test]
+fn test_leaderboard_ordering_pagination_and_rank() {
+    let env = Env::default();
+    let contract = env.register(CosmicCoder, ());
+    let client = CosmicCoderClient::new(&env, &contract);
+    let p: std::vec::Vec<Address> = (0..4).map(|_| Address::generate(&env)).collect();
+
+    env.as_contract(&contract, || {
+        record_ranked_score(&env, 1, &p[0], 100);
+        record_ranked_score(&env, 1, &p[1], 300);
+        record_ranked_score(&env, 1, &p[2], 200);
+        record_ranked_score(&env, 1, &p[3], 200);
+        // Lower score than the player's best is ignored; a higher one moves them up.
+        record_ranked_score(&env, 1, &p[1], 50);
+        record_ranked_score(&env, 1, &p[0], 250);
+    });
+
+    let lb = client.get_leaderboard_by_season(&1, &0, &10);
+    let order: std::vec::Vec<(Address, u32)> = lb.iter().map(|e| (e.player, e.score)).collect();
+    assert_eq!(
+        order,
+        std::vec![
+            (p[1].clone(), 300),
+            (p[0].clone(), 250),
+            (p[2].clone(), 200),
+            (p[3].clone(), 200),
+        ]
+    );
+
+    let page = client.get_leaderboard_by_season(&1, &2, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().player, p[2]);
+    assert_eq!(client.get_leaderboard_by_season(&1, &9, &5).len(), 0);
+
+    assert_eq!(client.get_player_rank(&1, &p[1]), Some(1));
+    assert_eq!(client.get_player_rank(&1, &p[3]), Some(4));
+    assert_eq!(client.get_player_rank(&2, &p[3]), None);
+    assert_eq!(client.get_player_best(&1, &p[1]), 300);
+}
+
+/// The top list never grows past LEADERBOARD_CAP; players pushed out keep their best score.
+#[test]
+fn test_leaderboard_bounded_to_cap() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let contract = env.register(CosmicCoder, ());
+    let client = CosmicCoderClient::new(&env, &contract);
+
+    let low = Address::generate(&env);
+    env.as_contract(&contract, || record_ranked_score(&env, 1, &low, 10));
+    for i in 0..LEADERBOARD_CAP {
+        let player = Address::generate(&env);
+        env.as_contract(&contract, || record_ranked_score(&env, 1, &player, 100 + i));
+    }
+
+    let lb = client.get_leaderboard_by_season(&1, &0, &(LEADERBOARD_CAP + 10));
+    assert_eq!(lb.len(), LEADERBOARD_CAP);
+    assert_eq!(lb.get(0).unwrap().score, 100 + LEADERBOARD_CAP - 1);
+    assert_eq!(lb.get(LEADERBOARD_CAP - 1).unwrap().score, 100);
+    assert_eq!(client.get_player_rank(&1, &low), None);
+    assert_eq!(client.get_player_best(&1, &low), 10);
+
+    // A score below the cutoff does not enter the list.
+    let late = Address::generate(&env);
+    env.as_contract(&contract, || record_ranked_score(&env, 1, &late, 50));
+    assert_eq!(client.get_player_rank(&1, &late), None);
+    assert_eq!(client.get_leaderboard_by_season(&1, &0, &LEADERBOARD_CAP).len(), LEADERBOARD_CAP);
+}
+
 /// Decode hex string (no 0x prefix) into fixed-size array.
 fn hex_to_array<const N: usize>(env: &Env, hex: &str) -> soroban_sdk::BytesN<N> {
     let hex = hex.trim_start_matches("0x");
@@ -328,18 +617,8 @@ fn hex_to_array<const N: usize>(env: &Env, hex: &str) -> soroban_sdk::BytesN<N>
     soroban_sdk::BytesN::from_array(env, &arr)
 }
 
-fn hex_to_bytes(env: &Env, hex: &str) -> Bytes {
-    let hex = hex.trim_start_matches("0x");
-    assert!(hex.len() % 2 == 0, "hex must have even length");
-    let mut out: std::vec::Vec<u8> = std::vec::Vec::with_capacity(hex.len() / 2);
-    for c in hex.as_bytes().chunks(2) {
-        let s = std::str::from_utf8(c).unwrap();
-        out.push(u8::from_str_radix(s, 16).unwrap());
-    }
-    Bytes::from_slice(env, &out)
-}
-
-/// When circuits/build/contract_proof.json exists (after `npm run zk:proof`), verifies proof on verifier and submit_zk on policy.
+/// When circuits/build/contract_proof.json exists (after `npm run zk:proof`), verifies proof on verifier;
+/// submit_zk must refuse it for a player it was not generated for.
 #[test]
 fn test_real_proof_verifier_and_submit_zk() {
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
@@ -391,26 +670,36 @@ fn test_real_proof_verifier_and_submit_zk() {
     let hub = env.register(MockHub, ());
     let policy = env.register(CosmicCoder, ());
     let policy_client = CosmicCoderClient::new(&env, &policy);
-    policy_client.init(&hub, &verifier);
+    policy_client.init(&Address::generate(&env), &hub, &verifier);
+    open_season(&policy_client, 1);
+
+    // A legacy 7-signal fixture carries no player binding; its key cannot even be registered.
+    if pub_signals.len() != 11 {
+        assert_eq!(
+            policy_client.try_register_vk(&Circuit::GameRun, &vk),
+            Err(Ok(CosmicCoderError::MalformedVk))
+        );
+        return;
+    }
+    let vk_hash = policy_client.register_vk(&Circuit::GameRun, &vk);
 
+    // Arguments match the proven signals: run_hash = hi128 || lo128, nonce = pub_signals[4].
     let player = Address::generate(&env);
-    let nonce = 1u64;
-    let season_id = 1u32;
-    let run_hash = pub_signals.get(0).unwrap().clone();
-    let score = 100u32;
-    let wave = 5u32;
+    let sig = |i: u32| pub_signals_n.get(i).unwrap().to_array();
+    let mut run_hash_arr = [0u8; 32];
+    run_hash_arr[..16].copy_from_slice(&sig(0)[16..]);
+    run_hash_arr[16..].copy_from_slice(&sig(1)[16..]);
+    let run_hash = Bytes::from_slice(&env, &run_hash_arr);
+    let nonce = u64::from_be_bytes(sig(4)[24..].try_into().unwrap());
 
-    policy_client.submit_zk(
-        &player,
+    // A fixture bound to some player/contract cannot be submitted by a fresh test player.
+    let res = policy_client.try_submit_zk(
+        &ranked_run(&player, policy_client.start_match(&player), nonce, &run_hash, 1u32, 100u32, 5u32),
         &proof,
-        &vk,
+        &vk_hash,
         &pub_signals,
-        &nonce,
-        &run_hash,
-        &season_id,
-        &score,
-        &wave,
     );
+    assert_eq!(res, Err(Ok(CosmicCoderError::PublicInputMismatch)));
 }
 
 #[test]
@@ -422,7 +711,7 @@ fn test_player_milestone_default_zero() {
     let verifier = env.register(Groth16Verifier, ());
     let policy = env.register(CosmicCoder, ());
     let policy_client = CosmicCoderClient::new(&env, &policy);
-    policy_client.init(&hub, &verifier);
+    policy_client.init(&Address::generate(&env), &hub, &verifier);
 
     let player = Address::generate(&env);
     let ms: PlayerMilestone = policy_client.get_player_milestone(&player, &1u32);
@@ -439,26 +728,21 @@ fn test_player_milestone_updates_from_verified_submits() {
     let verifier = env.register(Groth16Verifier, ());
     let policy = env.register(CosmicCoder, ());
     let policy_client = CosmicCoderClient::new(&env, &policy);
-    policy_client.init(&hub, &verifier);
+    policy_client.init(&Address::generate(&env), &hub, &verifier);
+    open_season(&policy_client, 1);
 
     let player = Address::generate(&env);
     let run_hash = run_hash_32(&env);
-    let vk = default_vk(&env);
+    let vk_hash = register_default_vk(&env, &policy_client);
     let proof = default_proof(&env);
-    let pub_signals = default_pub_signals(&env);
     let season_id = 1u32;
 
     // wave 5 => tier 1
     policy_client.submit_zk(
-        &player,
+        &ranked_run(&player, policy_client.start_match(&player), 301u64, &run_hash, season_id, 120u32, 5u32),
         &proof,
-        &vk,
-        &pub_signals,
-        &301u64,
-        &run_hash,
-        &season_id,
-        &120u32,
-        &5u32,
+        &vk_hash,
+        &run_signals(&env, &policy, &ranked_run(&player, 0, 301, &run_hash, season_id, 120, 5)),
     );
     let ms1: PlayerMilestone = policy_client.get_player_milestone(&player, &season_id);
     assert_eq!(ms1.tier, 1);
@@ -466,15 +750,10 @@ fn test_player_milestone_updates_from_verified_submits() {
 
     // wave 8 => tier 2
     policy_client.submit_zk(
-        &player,
+        &ranked_run(&player, policy_client.start_match(&player), 302u64, &run_hash, season_id, 220u32, 8u32),
         &proof,
-        &vk,
-        &pub_signals,
-        &302u64,
-        &run_hash,
-        &season_id,
-        &220u32,
-        &8u32,
+        &vk_hash,
+        &run_signals(&env, &policy, &ranked_run(&player, 0, 302, &run_hash, season_id, 220, 8)),
     );
     let ms2: PlayerMilestone = policy_client.get_player_milestone(&player, &season_id);
     assert_eq!(ms2.tier, 2);
@@ -482,17 +761,1441 @@ fn test_player_milestone_updates_from_verified_submits() {
 
     // wave 10 => tier 3
     policy_client.submit_zk(
-        &player,
+        &ranked_run(&player, policy_client.start_match(&player), 303u64, &run_hash, season_id, 320u32, 10u32),
         &proof,
-        &vk,
-        &pub_signals,
-        &303u64,
-        &run_hash,
-        &season_id,
-        &320u32,
-        &10u32,
+        &vk_hash,
+        &run_signals(&env, &policy, &ranked_run(&player, 0, 303, &run_hash, season_id, 320, 10)),
     );
     let ms3: PlayerMilestone = policy_client.get_player_milestone(&player, &season_id);
     assert_eq!(ms3.tier, 3);
     assert_eq!(ms3.best_wave, 10);
 }
+
+#[test]
+fn test_init_only_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let hub = env.register(MockHub, ());
+    let verifier = env.register(Groth16Verifier, ());
+    let policy = env.register(CosmicCoder, ());
+    let policy_client = CosmicCoderClient::new(&env, &policy);
+
+    let admin = Address::generate(&env);
+    policy_client.init(&admin, &hub, &verifier);
+    assert_eq!(policy_client.get_admin(), admin);
+
+    let attacker = Address::generate(&env);
+    let res = policy_client.try_init(&attacker, &hub, &attacker);
+    assert_eq!(res, Err(Ok(CosmicCoderError::AlreadyInitialized)));
+    assert_eq!(policy_client.get_admin(), admin);
+}
+
+#[test]
+fn test_init_requires_admin_auth() {
+    let env = Env::default();
+
+    let hub = env.register(MockHub, ());
+    let verifier = env.register(Groth16Verifier, ());
+    let policy = env.register(CosmicCoder, ());
+    let policy_client = CosmicCoderClient::new(&env, &policy);
+
+    let admin = Address::generate(&env);
+    assert!(policy_client.try_init(&admin, &hub, &verifier).is_err());
+    assert_eq!(policy_client.try_get_admin(), Err(Ok(CosmicCoderError::NotInitialized)));
+
+    policy_client.mock_all_auths().init(&admin, &hub, &verifier);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    policy.clone(),
+                    Symbol::new(&env, "init"),
+                    (admin.clone(), hub, verifier).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(policy_client.get_admin(), admin);
+}
+
+#[test]
+fn test_set_verifier_and_game_hub_require_admin_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let hub = env.register(MockHub, ());
+    let verifier = env.register(Groth16Verifier, ());
+    let policy = env.register(CosmicCoder, ());
+    let policy_client = CosmicCoderClient::new(&env, &policy);
+
+    let admin = Address::generate(&env);
+    policy_client.init(&admin, &hub, &verifier);
+
+    let new_verifier = env.register(Groth16Verifier, ());
+    policy_client.set_verifier(&VerifierKind::Groth16, &new_verifier);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    policy.clone(),
+                    Symbol::new(&env, "set_verifier"),
+                    (VerifierKind::Groth16, new_verifier.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+
+    let new_hub = env.register(MockHub, ());
+    policy_client.set_game_hub(&new_hub);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    policy.clone(),
+                    Symbol::new(&env, "set_game_hub"),
+                    (new_hub.clone(),).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+}
+
+/// Without admin authorization set_verifier must fail.
+#[test]
+fn test_set_verifier_without_auth_fails() {
+    let env = Env::default();
+
+    let hub = env.register(MockHub, ());
+    let verifier = env.register(Groth16Verifier, ());
+    let policy = env.register(CosmicCoder, ());
+    let policy_client = CosmicCoderClient::new(&env, &policy);
+
+    policy_client.mock_all_auths().init(&Address::generate(&env), &hub, &verifier);
+
+    let rogue = Address::generate(&env);
+    assert!(policy_client.try_set_verifier(&VerifierKind::Groth16, &rogue).is_err());
+    assert!(policy_client.try_set_game_hub(&rogue).is_err());
+}
+
+#[test]
+fn test_set_verifier_before_init_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let policy = env.register(CosmicCoder, ());
+    let policy_client = CosmicCoderClient::new(&env, &policy);
+
+    let res = policy_client.try_set_verifier(&VerifierKind::UltraHonk, &Address::generate(&env));
+    assert_eq!(res, Err(Ok(CosmicCoderError::NotInitialized)));
+}
+
+/// Each proof system has its own verifier slot; init fills Groth16 and WeaponUnlock only.
+#[test]
+fn test_verifier_slots_are_independent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let groth16 = env.register(Groth16Verifier, ());
+    let client = CosmicCoderClient::new(&env, &env.register(CosmicCoder, ()));
+    client.init(&Address::generate(&env), &env.register(MockHub, ()), &groth16);
+    open_season(&client, 1);
+
+    assert_eq!(client.get_verifier(&VerifierKind::Groth16), groth16);
+    assert_eq!(client.get_verifier(&VerifierKind::WeaponUnlock), groth16);
+    assert_eq!(
+        client.try_get_verifier(&VerifierKind::UltraHonk),
+        Err(Ok(CosmicCoderError::VerifierNotSet))
+    );
+
+    // Noir submits need their own verifier; the Groth16 one is never called for them.
+    let player = Address::generate(&env);
+    let submit_noir = |nonce: u64| {
+        client.try_submit_zk_noir(
+            &ranked_run(&player, client.start_match(&player), nonce, &run_hash_32(&env), 1, 100, 5),
+            &honk_proof(&env, &noir_inputs(&env, 100, 5, nonce, 1, false)),
+            &false,
+        )
+    };
+    assert_eq!(submit_noir(1), Err(Ok(CosmicCoderError::VerifierNotSet)));
+
+    let honk = env.register(MockUltraHonk, ());
+    client.set_verifier(&VerifierKind::UltraHonk, &honk);
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "verifier_set"), VerifierKind::UltraHonk, honk.clone()).into_val(&env),
+                ().into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_verifier(&VerifierKind::UltraHonk), honk);
+    assert_eq!(client.get_verifier(&VerifierKind::Groth16), groth16);
+
+    // The verifier is asked for the key id the admin selected.
+    assert_eq!(submit_noir(1), Err(Ok(CosmicCoderError::UnknownVk)));
+    client.set_ultrahonk_vk(&gamerun_honk_vk(&env, 2));
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "ultrahonk_vk_set"), Symbol::new(&env, "gamerun"), 2u32).into_val(&env),
+                ().into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(submit_noir(1), Err(Ok(CosmicCoderError::VerifierError)));
+    client.set_ultrahonk_vk(&gamerun_honk_vk(&env, 1));
+    assert_eq!(client.get_ultrahonk_vk(), Some(gamerun_honk_vk(&env, 1)));
+    assert!(submit_noir(1).is_ok());
+
+    // A proof the verifier already accepted for someone else is a replay, not a verifier fault.
+    let inputs = noir_inputs(&env, 100, 5, 2, 1, false);
+    MockUltraHonkClient::new(&env, &honk).verify_proof_with_stored_vk(
+        &Address::generate(&env),
+        &gamerun_honk_vk(&env, 1),
+        &inputs,
+        &honk_proof(&env, &inputs),
+        &false,
+    );
+    assert_eq!(submit_noir(2), Err(Ok(CosmicCoderError::Replay)));
+    assert_eq!(client.get_next_nonce(&player, &1), 2);
+
+    // Moving the weapon slot leaves ranked Groth16 untouched.
+    let weapon = env.register(Groth16Verifier, ());
+    client.set_verifier(&VerifierKind::WeaponUnlock, &weapon);
+    assert_eq!(client.get_verifier(&VerifierKind::WeaponUnlock), weapon);
+    assert_eq!(client.get_verifier(&VerifierKind::Groth16), groth16);
+}
+
+/// Two-step transfer: proposal alone changes nothing; accept requires the new admin's auth.
+#[test]
+fn test_admin_transfer_propose_accept() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let hub = env.register(MockHub, ());
+    let verifier = env.register(Groth16Verifier, ());
+    let policy = env.register(CosmicCoder, ());
+    let policy_client = CosmicCoderClient::new(&env, &policy);
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    policy_client.init(&admin, &hub, &verifier);
+
+    assert_eq!(
+        policy_client.try_accept_admin(),
+        Err(Ok(CosmicCoderError::NoPendingAdmin))
+    );
+
+    policy_client.propose_admin(&new_admin);
+    assert_eq!(policy_client.get_admin(), admin);
+
+    policy_client.accept_admin();
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            new_admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    policy.clone(),
+                    Symbol::new(&env, "accept_admin"),
+                    ().into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(policy_client.get_admin(), new_admin);
+
+    // Pending slot is cleared after acceptance.
+    assert_eq!(
+        policy_client.try_accept_admin(),
+        Err(Ok(CosmicCoderError::NoPendingAdmin))
+    );
+}
+
+/// Register verifier + policy + GameRun VK and return (client, player, vk_hash) ready for submit_zk.
+fn setup_submit(env: &Env) -> (CosmicCoderClient<'_>, Address, BytesN<32>) {
+    env.mock_all_auths();
+    let hub = env.register(MockHub, ());
+    let verifier = env.register(Groth16Verifier, ());
+    let policy = env.register(CosmicCoder, ());
+    let policy_client = CosmicCoderClient::new(env, &policy);
+    policy_client.init(&Address::generate(env), &hub, &verifier);
+    open_season(&policy_client, 1);
+    let vk_hash = register_default_vk(env, &policy_client);
+    (policy_client, Address::generate(env), vk_hash)
+}
+
+/// Valid submit_zk call for `season_id` with a fresh nonce.
+fn try_submit_in_season(
+    env: &Env,
+    client: &CosmicCoderClient,
+    player: &Address,
+    vk_hash: &BytesN<32>,
+    nonce: u64,
+    season_id: u32,
+) -> Result<(), CosmicCoderError> {
+    let run_hash = run_hash_32(env);
+    let pub_signals =
+        run_signals(env, &client.address, &ranked_run(player, 0, nonce, &run_hash, season_id, 100, 5));
+    match client.try_submit_zk(
+        &ranked_run(player, client.start_match(player), nonce, &run_hash, season_id, 100u32, 5u32),
+        &default_proof(env),
+        vk_hash,
+        &pub_signals,
+    ) {
+        Ok(_) => Ok(()),
+        Err(Ok(e)) => Err(e),
+        Err(Err(e)) => panic!("unexpected invoke error: {:?}", e),
+    }
+}
+
+/// Season ids may skip numbers: the active-season lookup follows creation order, not id - 1.
+#[test]
+fn test_active_season_with_id_gap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = CosmicCoderClient::new(&env, &env.register(CosmicCoder, ()));
+    client.init(&Address::generate(&env), &env.register(MockHub, ()), &env.register(Groth16Verifier, ()));
+
+    client.create_season(&1, &0, &100);
+    client.create_season(&5, &200, &300);
+    assert_eq!(client.get_active_season().unwrap().season_id, 1);
+    env.ledger().set_sequence_number(150);
+    assert_eq!(client.get_active_season(), None);
+    env.ledger().set_sequence_number(250);
+    assert_eq!(client.get_active_season().unwrap().season_id, 5);
+}
+
+/// Submissions only land in an existing, open season inside its ledger window; closing freezes
+/// the standings.
+#[test]
+fn test_season_window_and_close() {
+    let env = Env::default();
+    let (client, player, vk_hash) = setup_submit(&env);
+    let next_start = SEASON_LEDGERS + 1;
+    client.create_season(&2, &next_start, &(next_start + 100));
+
+    assert_eq!(
+        try_submit_in_season(&env, &client, &player, &vk_hash, 1, 9),
+        Err(CosmicCoderError::SeasonNotFound)
+    );
+    assert_eq!(
+        try_submit_in_season(&env, &client, &player, &vk_hash, 2, 2),
+        Err(CosmicCoderError::SeasonNotActive)
+    );
+    assert_eq!(client.get_active_season().unwrap().season_id, 1);
+    try_submit_in_season(&env, &client, &player, &vk_hash, 3, 1).unwrap();
+
+    env.ledger().set_sequence_number(next_start + 10);
+    assert_eq!(client.get_active_season().unwrap().season_id, 2);
+    assert_eq!(
+        try_submit_in_season(&env, &client, &player, &vk_hash, 4, 1),
+        Err(CosmicCoderError::SeasonNotActive)
+    );
+    try_submit_in_season(&env, &client, &player, &vk_hash, 5, 2).unwrap();
+
+    assert_eq!(client.get_final_standings(&2), None);
+    client.close_season(&2);
+    let standings = client.get_final_standings(&2).unwrap();
+    assert_eq!(standings.len(), 1);
+    assert_eq!(standings.get(0).unwrap().player, player);
+    assert!(client.get_season(&2).unwrap().closed);
+    assert_eq!(client.get_active_season(), None);
+    assert_eq!(
+        try_submit_in_season(&env, &client, &player, &vk_hash, 6, 2),
+        Err(CosmicCoderError::SeasonClosed)
+    );
+    assert_eq!(client.try_close_season(&2), Err(Ok(CosmicCoderError::SeasonClosed)));
+    assert_eq!(client.try_close_season(&7), Err(Ok(CosmicCoderError::SeasonNotFound)));
+}
+
+/// Seasons must have a valid window, increasing ids and non-overlapping windows; admin only.
+#[test]
+fn test_create_season_rules() {
+    let env = Env::default();
+    let (client, _, _) = setup_submit(&env);
+
+    // Overlaps season 1, id not increasing, inverted window.
+    assert_eq!(
+        client.try_create_season(&2, &SEASON_LEDGERS, &(SEASON_LEDGERS + 10)),
+        Err(Ok(CosmicCoderError::InvalidSeason))
+    );
+    assert_eq!(
+        client.try_create_season(&1, &(SEASON_LEDGERS + 1), &(SEASON_LEDGERS + 10)),
+        Err(Ok(CosmicCoderError::InvalidSeason))
+    );
+    assert_eq!(
+        client.try_create_season(&2, &(SEASON_LEDGERS + 10), &(SEASON_LEDGERS + 1)),
+        Err(Ok(CosmicCoderError::InvalidSeason))
+    );
+    client.create_season(&3, &(SEASON_LEDGERS + 1), &(SEASON_LEDGERS + 10));
+    assert_eq!(
+        client.get_season(&3),
+        Some(Season {
+            season_id: 3,
+            start_ledger: SEASON_LEDGERS + 1,
+            end_ledger: SEASON_LEDGERS + 10,
+            closed: false,
+        })
+    );
+
+    // Without admin auth.
+    let env = Env::default();
+    let policy = env.register(CosmicCoder, ());
+    let client = CosmicCoderClient::new(&env, &policy);
+    client.mock_all_auths().init(&Address::generate(&env), &policy, &policy);
+    assert!(client.try_create_season(&1, &0, &10).is_err());
+    assert!(client.try_close_season(&1).is_err());
+}
+
+/// Interleaved players: each submit must name the submitter's own open session, and the hub
+/// sees end_game for exactly that session.
+#[test]
+fn test_sessions_are_per_player() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let hub = env.register(RecordingHub, ());
+    let hub_client = RecordingHubClient::new(&env, &hub);
+    let policy = env.register(CosmicCoder, ());
+    let client = CosmicCoderClient::new(&env, &policy);
+    client.init(&Address::generate(&env), &hub, &env.register(Groth16Verifier, ()));
+    open_season(&client, 1);
+    let vk_hash = register_default_vk(&env, &client);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let a = client.start_match(&alice);
+    let b = client.start_match(&bob);
+    assert_eq!(client.get_open_session(&alice), Some(a));
+    assert_eq!(client.get_session(&b).unwrap().player, bob);
+
+    let submit = |player: &Address, session: u32, nonce: u64| {
+        let run_hash = run_hash_32(&env);
+        let pub_signals = run_signals(&env, &policy, &ranked_run(player, 0, nonce, &run_hash, 1, 100, 5));
+        client.try_submit_zk(
+            &ranked_run(player, session, nonce, &run_hash, 1u32, 100u32, 5u32),
+            &default_proof(&env),
+            &vk_hash,
+            &pub_signals,
+        )
+    };
+
+    // Alice cannot end Bob's session.
+    assert_eq!(submit(&alice, b, 1), Err(Ok(CosmicCoderError::InvalidSession)));
+    assert!(submit(&bob, b, 2).is_ok());
+    assert_eq!(hub_client.ended(), vec![&env, (b, true)]);
+    assert_eq!(client.get_session(&b).unwrap().status, SessionStatus::Ended);
+    assert_eq!(client.get_open_session(&alice), Some(a));
+
+    assert!(submit(&alice, a, 3).is_ok());
+    assert_eq!(hub_client.ended(), vec![&env, (b, true), (a, true)]);
+    assert_eq!(submit(&alice, a, 4), Err(Ok(CosmicCoderError::InvalidSession)));
+
+    // Starting again abandons the still-open session; casual submits follow the same rule.
+    let c = client.start_match(&alice);
+    let d = client.start_match(&alice);
+    assert_eq!(hub_client.ended().last(), Some((c, false)));
+    assert!(!client.submit_result(&alice, &c, &5, &100));
+    assert!(client.submit_result(&alice, &d, &5, &100));
+    assert_eq!(hub_client.ended().last(), Some((d, true)));
+    assert_eq!(client.get_open_session(&alice), None);
+}
+
+/// Nonces are scoped per player and season: the same nonce works for two players or in a new
+/// season, out-of-order nonces inside the window are accepted once, and nonces older than the
+/// window are rejected.
+#[test]
+fn test_nonce_window_per_player_and_season() {
+    let env = Env::default();
+    let (client, alice, vk_hash) = setup_submit(&env);
+    let bob = Address::generate(&env);
+    let submit = |player: &Address, nonce: u64| {
+        try_submit_in_season(&env, &client, player, &vk_hash, nonce, 1)
+    };
+
+    assert_eq!(client.get_next_nonce(&alice, &1), 0);
+    submit(&alice, 1_000).unwrap();
+    submit(&bob, 1_000).unwrap();
+    assert_eq!(client.get_next_nonce(&alice, &1), 1_001);
+
+    // Out of order within the window, once.
+    submit(&alice, 990).unwrap();
+    assert_eq!(submit(&alice, 990), Err(CosmicCoderError::Replay));
+    assert_eq!(submit(&alice, 1_000), Err(CosmicCoderError::Replay));
+    assert_eq!(client.get_next_nonce(&alice, &1), 1_001);
+
+    // Jump ahead: the window slides and drops nonces older than 128 below the new high.
+    submit(&alice, 1_100).unwrap();
+    submit(&alice, 995).unwrap();
+    assert_eq!(submit(&alice, 990), Err(CosmicCoderError::Replay));
+    assert_eq!(submit(&alice, 972), Err(CosmicCoderError::Replay));
+    submit(&alice, 1_000_000).unwrap();
+    assert_eq!(submit(&alice, 1_100), Err(CosmicCoderError::Replay));
+    submit(&alice, client.get_next_nonce(&alice, &1)).unwrap();
+    assert_eq!(client.get_next_nonce(&bob, &1), 1_001);
+
+    // A new season starts from a fresh window; the old season's window is left as it was.
+    client.create_season(&2, &(SEASON_LEDGERS + 1), &(2 * SEASON_LEDGERS));
+    env.ledger().set_sequence_number(SEASON_LEDGERS + 1);
+    assert_eq!(client.get_next_nonce(&alice, &2), 0);
+    try_submit_in_season(&env, &client, &alice, &vk_hash, 990, 2).unwrap();
+    assert_eq!(
+        try_submit_in_season(&env, &client, &alice, &vk_hash, 990, 2),
+        Err(CosmicCoderError::Replay)
+    );
+    assert_eq!(client.get_next_nonce(&alice, &2), 991);
+    assert_eq!(client.get_next_nonce(&alice, &1), 1_000_002);
+}
+
+/// Proof systems behind submit_zk / submit_zk_noir; shared scenarios run through both.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Backend {
+    Groth16,
+    UltraHonk,
+}
+
+const BACKENDS: [Backend; 2] = [Backend::Groth16, Backend::UltraHonk];
+
+/// Claimed run for a ranked submit; `valid: false` sends a proof the verifier rejects.
+#[derive(Clone, Copy)]
+struct Run {
+    nonce: u64,
+    season_id: u32,
+    score: u32,
+    wave: u32,
+    valid: bool,
+}
+
+fn run(nonce: u64) -> Run {
+    Run {
+        nonce,
+        season_id: 1,
+        score: 100,
+        wave: 5,
+        valid: true,
+    }
+}
+
+/// Contract wired to one backend's verifier, season 1 open, GameRun VK registered.
+struct Ranked<'a> {
+    env: &'a Env,
+    client: CosmicCoderClient<'a>,
+    vk_hash: BytesN<32>,
+    backend: Backend,
+}
+
+impl<'a> Ranked<'a> {
+    fn new(env: &'a Env, backend: Backend) -> Self {
+        env.mock_all_auths();
+        let client = CosmicCoderClient::new(env, &env.register(CosmicCoder, ()));
+        let groth16 = env.register(Groth16Verifier, ());
+        client.init(&Address::generate(env), &env.register(MockHub, ()), &groth16);
+        if backend == Backend::UltraHonk {
+            client.set_verifier(&VerifierKind::UltraHonk, &env.register(MockUltraHonk, ()));
+            client.set_ultrahonk_vk(&gamerun_honk_vk(env, 1));
+        }
+        open_season(&client, 1);
+        let vk_hash = register_default_vk(env, &client);
+        Ranked {
+            env,
+            client,
+            vk_hash,
+            backend,
+        }
+    }
+
+    fn submit(&self, player: &Address, session: u32, r: Run) -> Result<(), CosmicCoderError> {
+        let env = self.env;
+        let run_hash = run_hash_32(env);
+        let res = match self.backend {
+            Backend::Groth16 => {
+                let proof = if r.valid { default_proof(env) } else { rejected_proof(env) };
+                let claim = ranked_run(player, session, r.nonce, &run_hash, r.season_id, r.score, r.wave);
+                let pub_signals = run_signals(env, &self.client.address, &claim);
+                self.client.try_submit_zk(
+                    &claim,
+                    &proof,
+                    &self.vk_hash,
+                    &pub_signals,
+                )
+            }
+            Backend::UltraHonk => {
+                let proof = if r.valid {
+                    honk_proof(env, &noir_inputs(env, r.score, r.wave, r.nonce, r.season_id, false))
+                } else {
+                    Bytes::from_array(env, &[0xff; 32])
+                };
+                self.client.try_submit_zk_noir(
+                    &ranked_run(player, session, r.nonce, &run_hash, r.season_id, r.score, r.wave),
+                    &proof,
+                    &false,
+                )
+            }
+        };
+        match res {
+            Ok(_) => Ok(()),
+            Err(Ok(e)) => Err(e),
+            Err(Err(e)) => panic!("unexpected invoke error: {:?}", e),
+        }
+    }
+
+    /// Submit in a freshly started session.
+    fn submit_new(&self, player: &Address, r: Run) -> Result<(), CosmicCoderError> {
+        self.submit(player, self.client.start_match(player), r)
+    }
+}
+
+/// submit_zk_noir builds the Noir public inputs from its own arguments: a proof of one run
+/// cannot be submitted with a different score, wave, nonce, season or weapon flag.
+#[test]
+fn test_noir_public_inputs_bound_to_arguments() {
+    let env = Env::default();
+    let r = Ranked::new(&env, Backend::UltraHonk);
+    let player = Address::generate(&env);
+    let proven = honk_proof(&env, &noir_inputs(&env, 220, 8, 3, 1, true));
+
+    let claim = |score: u32, wave: u32, nonce: u64, used_zk_weapon: bool| {
+        r.client.try_submit_zk_noir(
+            &ranked_run(&player, r.client.start_match(&player), nonce, &run_hash_32(&env), 1, score, wave),
+            &proven,
+            &used_zk_weapon,
+        )
+    };
+    let rejected = Err(Ok(CosmicCoderError::InvalidProof));
+    assert_eq!(claim(5000, 8, 3, true), rejected);
+    assert_eq!(claim(220, 9, 3, true), rejected);
+    assert_eq!(claim(220, 8, 4, true), rejected);
+    assert_eq!(claim(220, 8, 3, false), rejected);
+    assert_eq!(r.client.get_leaderboard_by_season(&1, &0, &10).len(), 0);
+
+    grant_weapon(&env, &r.client, &player, ZK_WEAPON_ID);
+    assert!(claim(220, 8, 3, true).is_ok());
+    assert_eq!(r.client.get_player_best(&1, &player), 220);
+}
+
+/// Halves of a small run_hash pass through; an overflowing one is folded like the client does.
+#[test]
+fn test_noir_run_hash_encoding() {
+    let env = Env::default();
+
+    let mut hash = [0u8; 32];
+    hash[15] = 7;
+    hash[31] = 9;
+    let inputs = noir_run_inputs(&env, &hash, 100, 5, 1, 1, false);
+    assert_eq!(inputs.len(), 7);
+    assert_eq!(inputs.get(0).unwrap().to_array(), u64_signal(7));
+    assert_eq!(inputs.get(1).unwrap().to_array(), u64_signal(9));
+    assert_eq!(inputs.get(2).unwrap().to_array(), u64_signal(100));
+    assert_eq!(inputs.get(6).unwrap().to_array(), u64_signal(0));
+
+    // reserve = 100 + 5 + 1 + 1 = 107 and 2^128 = 107 mod (2^128 - 107), so
+    // (2^256 - 1) mod (2^128 - 107) = (107 - 1) * (107 + 1) = 11448.
+    let inputs = noir_run_inputs(&env, &[0xff; 32], 100, 5, 1, 1, false);
+    assert_eq!(inputs.get(0).unwrap().to_array(), u64_signal(11448));
+    assert_eq!(inputs.get(1).unwrap().to_array(), u64_signal(0));
+}
+
+/// Batch entry for `player` in a fresh session with honest signals for (score, wave, nonce).
+fn batch_run(env: &Env, r: &Ranked, player: &Address, score: u32, nonce: u64) -> ZkRunSubmission {
+    let run_hash = run_hash_32(env);
+    ZkRunSubmission {
+        player: player.clone(),
+        session_id: r.client.start_match(player),
+        proof: default_proof(env),
+        pub_signals: run_signals(env, &r.client.address, &ranked_run(player, 0, nonce, &run_hash, 1, score, 5)),
+        nonce,
+        run_hash,
+        season_id: 1,
+        score,
+        wave: 5,
+    }
+}
+
+#[test]
+fn test_submit_zk_batch_settles_all_runs() {
+    let env = Env::default();
+    let r = Ranked::new(&env, Backend::Groth16);
+    let players: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+
+    let mut runs = soroban_sdk::Vec::new(&env);
+    for (p, score) in players.iter().zip([100u32, 300, 200]) {
+        runs.push_back(batch_run(&env, &r, p, score, 4));
+    }
+    r.client.submit_zk_batch(&r.vk_hash, &runs);
+
+    let lb = r.client.get_leaderboard_by_season(&1, &0, &10);
+    assert_eq!(lb.len(), 3);
+    assert_eq!(lb.get(0).unwrap().player, players[1]);
+    assert_eq!(lb.get(2).unwrap().player, players[0]);
+    for p in players.iter() {
+        assert_eq!(r.client.get_next_nonce(p, &1), 5);
+        assert_eq!(r.client.get_open_session(p), None);
+    }
+}
+
+/// Any failing run rejects the whole batch; nothing is recorded and no nonce is burned.
+#[test]
+fn test_submit_zk_batch_is_all_or_nothing() {
+    let env = Env::default();
+    let r = Ranked::new(&env, Backend::Groth16);
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+    let try_batch = |runs: &soroban_sdk::Vec<ZkRunSubmission>| {
+        r.client.try_submit_zk_batch(&r.vk_hash, runs).map_err(|e| e.unwrap())
+    };
+
+    let mut bad_proof = batch_run(&env, &r, &bob, 200, 1);
+    bad_proof.proof = rejected_proof(&env);
+    let runs = soroban_sdk::vec![&env, batch_run(&env, &r, &alice, 100, 1), bad_proof];
+    assert_eq!(try_batch(&runs), Err(CosmicCoderError::InvalidProof));
+
+    let mut inflated = batch_run(&env, &r, &bob, 200, 1);
+    inflated.score = 900;
+    let runs = soroban_sdk::vec![&env, batch_run(&env, &r, &alice, 100, 1), inflated];
+    assert_eq!(try_batch(&runs), Err(CosmicCoderError::PublicInputMismatch));
+
+    let run = batch_run(&env, &r, &alice, 100, 1);
+    let runs = soroban_sdk::vec![&env, run.clone(), run];
+    assert_eq!(try_batch(&runs), Err(CosmicCoderError::InvalidInput));
+
+    assert_eq!(try_batch(&soroban_sdk::Vec::new(&env)), Err(CosmicCoderError::InvalidInput));
+    let mut oversized = soroban_sdk::Vec::new(&env);
+    for _ in 0..=MAX_ZK_BATCH {
+        oversized.push_back(batch_run(&env, &r, &Address::generate(&env), 100, 1));
+    }
+    assert_eq!(try_batch(&oversized), Err(CosmicCoderError::InvalidInput));
+
+    let runs = soroban_sdk::vec![&env, batch_run(&env, &r, &alice, 100, 1)];
+    assert_eq!(
+        r.client.try_submit_zk_batch(&BytesN::from_array(&env, &[7u8; 32]), &runs),
+        Err(Ok(CosmicCoderError::UnknownVk))
+    );
+
+    assert_eq!(r.client.get_leaderboard_by_season(&1, &0, &10).len(), 0);
+    assert_eq!(r.client.get_next_nonce(&alice, &1), 0);
+    assert_eq!(r.client.get_next_nonce(&bob, &1), 0);
+
+    // The same runs settle once the bad member is fixed.
+    let runs = soroban_sdk::vec![&env, batch_run(&env, &r, &alice, 100, 1), batch_run(&env, &r, &bob, 200, 1)];
+    assert_eq!(try_batch(&runs), Ok(Ok(())));
+    assert_eq!(r.client.get_leaderboard_by_season(&1, &0, &10).len(), 2);
+}
+
+/// A proof point the host would trap on comes back as the verifier's typed error, not VerifierCrash.
+#[test]
+fn test_malformed_proof_point_is_verifier_error() {
+    let env = Env::default();
+    let r = Ranked::new(&env, Backend::Groth16);
+    let player = Address::generate(&env);
+
+    let mut run = batch_run(&env, &r, &player, 100, 1);
+    let mut off_curve = [0u8; G1_SIZE];
+    off_curve[31] = 1;
+    off_curve[63] = 3;
+    run.proof.c = soroban_sdk::BytesN::from_array(&env, &off_curve);
+    let res = r.client.try_submit_zk(
+        &run.ranked_run(),
+        &run.proof,
+        &r.vk_hash,
+        &run.pub_signals,
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::VerifierError)));
+    assert_eq!(
+        r.client.try_submit_zk_batch(&r.vk_hash, &soroban_sdk::vec![&env, run]),
+        Err(Ok(CosmicCoderError::VerifierError))
+    );
+    assert_eq!(r.client.get_next_nonce(&player, &1), 0);
+}
+
+/// Groth16 proof that fails the pairing check: e(-G1, G2) != 1 while every other term is trivial.
+fn rejected_proof(env: &Env) -> ZkProof {
+    let mut a = [0u8; G1_SIZE];
+    a[31] = 1;
+    a[63] = 2;
+    ZkProof {
+        a: soroban_sdk::BytesN::from_array(env, &a),
+        b: hex_to_array(
+            env,
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+             1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+             090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+             12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        ),
+        c: g1(env),
+    }
+}
+
+#[test]
+fn test_ranked_valid_submit_all_backends() {
+    for backend in BACKENDS {
+        let env = Env::default();
+        let r = Ranked::new(&env, backend);
+        let player = Address::generate(&env);
+        let session = r.client.start_match(&player);
+
+        r.submit(&player, session, Run { score: 220, wave: 8, ..run(7) }).unwrap();
+        let lb = r.client.get_leaderboard_by_season(&1, &0, &10);
+        assert_eq!(lb.len(), 1, "{:?}", backend);
+        assert_eq!(lb.get(0).unwrap().score, 220);
+        assert_eq!(r.client.get_player_milestone(&player, &1).tier, 2);
+        assert_eq!(r.client.get_next_nonce(&player, &1), 8);
+        assert_eq!(r.client.get_open_session(&player), None);
+        assert_eq!(r.client.get_session(&session).unwrap().status, SessionStatus::Ended);
+    }
+}
+
+#[test]
+fn test_ranked_rejections_all_backends() {
+    for backend in BACKENDS {
+        let env = Env::default();
+        let r = Ranked::new(&env, backend);
+        let player = Address::generate(&env);
+
+        assert_eq!(
+            r.submit_new(&player, Run { score: 0, ..run(1) }),
+            Err(CosmicCoderError::InvalidInput),
+            "{:?}",
+            backend
+        );
+        assert_eq!(
+            r.submit_new(&player, Run { score: 20, ..run(1) }),
+            Err(CosmicCoderError::InvalidInput)
+        );
+        assert_eq!(
+            r.submit_new(&player, Run { season_id: 2, ..run(1) }),
+            Err(CosmicCoderError::SeasonNotFound)
+        );
+        assert_eq!(
+            r.submit(&player, 999, run(1)),
+            Err(CosmicCoderError::InvalidSession)
+        );
+
+        // A proof the verifier rejects changes nothing: nonce, session and board are untouched.
+        let session = r.client.start_match(&player);
+        assert_eq!(
+            r.submit(&player, session, Run { valid: false, ..run(1) }),
+            Err(CosmicCoderError::InvalidProof)
+        );
+        assert_eq!(r.client.get_next_nonce(&player, &1), 0);
+        assert_eq!(r.client.get_open_session(&player), Some(session));
+        assert_eq!(r.client.get_leaderboard_by_season(&1, &0, &10).len(), 0);
+
+        // Replay, then a closed season.
+        r.submit(&player, session, run(1)).unwrap();
+        assert_eq!(r.submit_new(&player, run(1)), Err(CosmicCoderError::Replay));
+        r.client.close_season(&1);
+        assert_eq!(r.submit_new(&player, run(2)), Err(CosmicCoderError::SeasonClosed));
+    }
+}
+
+/// Submit with honest signals for (run_hash, score=100, wave=5, nonce=9, season=1), except
+/// pub_signals[index] replaced by `tampered`; must fail with PublicInputMismatch.
+fn assert_signal_mismatch(index: u32, tampered: fn(&Env) -> Bytes) {
+    let env = Env::default();
+    let (policy_client, player, vk_hash) = setup_submit(&env);
+
+    let mut hash = [0u8; 32];
+    hash[0] = 0xab;
+    hash[31] = 0xcd;
+    let run_hash = Bytes::from_slice(&env, &hash);
+    let mut pub_signals =
+        run_signals(&env, &policy_client.address, &ranked_run(&player, 0, 9, &run_hash, 1, 100, 5));
+    pub_signals.set(index, tampered(&env));
+
+    let res = policy_client.try_submit_zk(
+        &ranked_run(&player, policy_client.start_match(&player), 9u64, &run_hash, 1u32, 100u32, 5u32),
+        &default_proof(&env),
+        &vk_hash,
+        &pub_signals,
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::PublicInputMismatch)));
+    assert_eq!(policy_client.get_leaderboard_by_season(&1u32, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_submit_zk_run_hash_hi_mismatch() {
+    assert_signal_mismatch(0, |env| signal(env, 0xab));
+}
+
+#[test]
+fn test_submit_zk_run_hash_lo_mismatch() {
+    assert_signal_mismatch(1, |env| signal(env, 0xce));
+}
+
+/// A proof for score 10 cannot be submitted as score 100.
+#[test]
+fn test_submit_zk_score_mismatch() {
+    assert_signal_mismatch(2, |env| signal(env, 10));
+}
+
+#[test]
+fn test_submit_zk_wave_mismatch() {
+    assert_signal_mismatch(3, |env| signal(env, 4));
+}
+
+#[test]
+fn test_submit_zk_nonce_mismatch() {
+    assert_signal_mismatch(4, |env| signal(env, 8));
+}
+
+#[test]
+fn test_submit_zk_season_mismatch() {
+    assert_signal_mismatch(5, |env| signal(env, 2));
+}
+
+/// used_zk_weapon is a boolean signal; anything other than 0 or 1 is rejected.
+#[test]
+fn test_submit_zk_weapon_flag_out_of_range() {
+    assert_signal_mismatch(6, |env| signal(env, 2));
+}
+
+/// run_hash must be exactly 32 bytes (hi || lo).
+#[test]
+fn test_submit_zk_run_hash_wrong_length() {
+    let env = Env::default();
+    let (policy_client, player, vk_hash) = setup_submit(&env);
+
+    let pub_signals =
+        run_signals(&env, &policy_client.address, &ranked_run(&player, 0, 9, &run_hash_32(&env), 1, 100, 5));
+    let res = policy_client.try_submit_zk(
+        &ranked_run(
+            &player,
+            policy_client.start_match(&player),
+            9u64,
+            &Bytes::from_slice(&env, &[0u8; 31]),
+            1u32,
+            100u32,
+            5u32,
+        ),
+        &default_proof(&env),
+        &vk_hash,
+        &pub_signals,
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::InvalidInput)));
+}
+
+/// A proof generated for player A cannot be submitted by player B (fresh nonce, same signals).
+#[test]
+fn test_submit_zk_proof_bound_to_player() {
+    let env = Env::default();
+    let (policy_client, player, vk_hash) = setup_submit(&env);
+    let other = Address::generate(&env);
+
+    let run_hash = run_hash_32(&env);
+    let pub_signals =
+        run_signals(&env, &policy_client.address, &ranked_run(&player, 0, 9, &run_hash, 1, 100, 5));
+
+    let res = policy_client.try_submit_zk(
+        &ranked_run(&other, policy_client.start_match(&other), 9u64, &run_hash, 1u32, 100u32, 5u32),
+        &default_proof(&env),
+        &vk_hash,
+        &pub_signals,
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::PublicInputMismatch)));
+
+    policy_client.submit_zk(
+        &ranked_run(&player, policy_client.start_match(&player), 9u64, &run_hash, 1u32, 100u32, 5u32),
+        &default_proof(&env),
+        &vk_hash,
+        &pub_signals,
+    );
+    assert_eq!(policy_client.get_leaderboard_by_season(&1u32, &0, &10).len(), 1);
+}
+
+/// A proof bound to another Cosmic Coder deployment is rejected.
+#[test]
+fn test_submit_zk_proof_bound_to_contract() {
+    let env = Env::default();
+    let (policy_client, player, vk_hash) = setup_submit(&env);
+    let other_contract = env.register(CosmicCoder, ());
+
+    let run_hash = run_hash_32(&env);
+    let pub_signals = run_signals(&env, &other_contract, &ranked_run(&player, 0, 9, &run_hash, 1, 100, 5));
+
+    let res = policy_client.try_submit_zk(
+        &ranked_run(&player, policy_client.start_match(&player), 9u64, &run_hash, 1u32, 100u32, 5u32),
+        &default_proof(&env),
+        &vk_hash,
+        &pub_signals,
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::PublicInputMismatch)));
+}
+
+/// Legacy 7-signal layout (no player binding) is no longer accepted.
+#[test]
+fn test_submit_zk_legacy_layout_rejected() {
+    let env = Env::default();
+    let (policy_client, player, vk_hash) = setup_submit(&env);
+
+    let run_hash = run_hash_32(&env);
+    let full = run_signals(&env, &policy_client.address, &ranked_run(&player, 0, 9, &run_hash, 1, 100, 5));
+    let legacy = full.slice(0..7);
+
+    let res = policy_client.try_submit_zk(
+        &ranked_run(&player, policy_client.start_match(&player), 9u64, &run_hash, 1u32, 100u32, 5u32),
+        &default_proof(&env),
+        &vk_hash,
+        &legacy,
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::InvalidInput)));
+}
+
+/// submit_zk only accepts keys registered by the admin: an unregistered hash is refused.
+#[test]
+fn test_submit_zk_unknown_vk_rejected() {
+    let env = Env::default();
+    let (policy_client, player, _) = setup_submit(&env);
+
+    let run_hash = run_hash_32(&env);
+    let pub_signals =
+        run_signals(&env, &policy_client.address, &ranked_run(&player, 0, 9, &run_hash, 1, 100, 5));
+    let res = policy_client.try_submit_zk(
+        &ranked_run(&player, policy_client.start_match(&player), 9u64, &run_hash, 1u32, 100u32, 5u32),
+        &default_proof(&env),
+        &BytesN::from_array(&env, &[7u8; 32]),
+        &pub_signals,
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::UnknownVk)));
+}
+
+/// A SkillProof key cannot be used for GameRun submissions.
+#[test]
+fn test_submit_zk_vk_wrong_circuit_rejected() {
+    let env = Env::default();
+    let (policy_client, player, _) = setup_submit(&env);
+    let skill_hash = policy_client.register_vk(&Circuit::SkillProof, &vk_with_ic_len(&env, 5));
+
+    let run_hash = run_hash_32(&env);
+    let pub_signals =
+        run_signals(&env, &policy_client.address, &ranked_run(&player, 0, 9, &run_hash, 1, 100, 5));
+    let res = policy_client.try_submit_zk(
+        &ranked_run(&player, policy_client.start_match(&player), 9u64, &run_hash, 1u32, 100u32, 5u32),
+        &default_proof(&env),
+        &skill_hash,
+        &pub_signals,
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::UnknownVk)));
+}
+
+/// After revoke_vk, submissions against the key fail; it cannot be re-registered.
+#[test]
+fn test_submit_zk_revoked_vk_rejected() {
+    let env = Env::default();
+    let (policy_client, player, vk_hash) = setup_submit(&env);
+
+    let run_hash = run_hash_32(&env);
+    policy_client.submit_zk(
+        &ranked_run(&player, policy_client.start_match(&player), 9u64, &run_hash, 1u32, 100u32, 5u32),
+        &default_proof(&env),
+        &vk_hash,
+        &run_signals(&env, &policy_client.address, &ranked_run(&player, 0, 9, &run_hash, 1, 100, 5)),
+    );
+
+    policy_client.revoke_vk(&vk_hash);
+    assert_eq!(policy_client.get_vk(&vk_hash).unwrap().state, VkState::Revoked);
+
+    let res = policy_client.try_submit_zk(
+        &ranked_run(&player, policy_client.start_match(&player), 10u64, &run_hash, 1u32, 100u32, 5u32),
+        &default_proof(&env),
+        &vk_hash,
+        &run_signals(&env, &policy_client.address, &ranked_run(&player, 0, 10, &run_hash, 1, 100, 5)),
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::VkRevoked)));
+    assert_eq!(
+        policy_client.try_register_vk(&Circuit::GameRun, &default_vk(&env)),
+        Err(Ok(CosmicCoderError::VkAlreadyRegistered))
+    );
+    assert_eq!(
+        policy_client.try_revoke_vk(&BytesN::from_array(&env, &[7u8; 32])),
+        Err(Ok(CosmicCoderError::UnknownVk))
+    );
+}
+
+/// A deprecated key keeps working through its last ledger, then fails with VkExpired;
+/// list_vks reports every key with its circuit and state.
+#[test]
+fn test_deprecated_vk_migration_window() {
+    let env = Env::default();
+    let (policy_client, player, vk_hash) = setup_submit(&env);
+    let skill_hash = policy_client.register_vk(&Circuit::SkillProof, &vk_with_ic_len(&env, 5));
+
+    let last_ledger = env.ledger().sequence() + 10;
+    policy_client.deprecate_vk(&vk_hash, &last_ledger);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                policy_client.address.clone(),
+                (Symbol::new(&env, "vk_deprecated"), Circuit::GameRun, vk_hash.clone()).into_val(&env),
+                last_ledger.into_val(&env),
+            ),
+        ]
+    );
+    env.ledger().set_sequence_number(last_ledger);
+    assert_eq!(try_submit_in_season(&env, &policy_client, &player, &vk_hash, 1, 1), Ok(()));
+
+    env.ledger().set_sequence_number(last_ledger + 1);
+    assert_eq!(
+        try_submit_in_season(&env, &policy_client, &player, &vk_hash, 2, 1),
+        Err(CosmicCoderError::VkExpired)
+    );
+
+    policy_client.revoke_vk(&skill_hash);
+    assert_eq!(
+        policy_client.try_deprecate_vk(&skill_hash, &(last_ledger + 100)),
+        Err(Ok(CosmicCoderError::VkRevoked))
+    );
+    assert_eq!(
+        policy_client.try_deprecate_vk(&BytesN::from_array(&env, &[7u8; 32]), &last_ledger),
+        Err(Ok(CosmicCoderError::UnknownVk))
+    );
+    assert_eq!(
+        policy_client.list_vks(),
+        vec![
+            &env,
+            VkInfo {
+                vk_hash,
+                circuit: Circuit::GameRun,
+                state: VkState::Deprecated(last_ledger),
+            },
+            VkInfo {
+                vk_hash: skill_hash,
+                circuit: Circuit::SkillProof,
+                state: VkState::Revoked,
+            },
+        ]
+    );
+}
+
+/// Only the admin can register or revoke keys.
+#[test]
+fn test_register_and_revoke_vk_require_admin_auth() {
+    let env = Env::default();
+
+    let hub = env.register(MockHub, ());
+    let verifier = env.register(Groth16Verifier, ());
+    let policy = env.register(CosmicCoder, ());
+    let policy_client = CosmicCoderClient::new(&env, &policy);
+    let admin = Address::generate(&env);
+    policy_client.mock_all_auths().init(&admin, &hub, &verifier);
+
+    assert!(policy_client
+        .try_register_vk(&Circuit::GameRun, &default_vk(&env))
+        .is_err());
+
+    env.mock_all_auths();
+    let vk_hash = register_default_vk(&env, &policy_client);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    policy.clone(),
+                    Symbol::new(&env, "register_vk"),
+                    (Circuit::GameRun, default_vk(&env)).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(policy_client.get_vk(&vk_hash).unwrap().circuit, Circuit::GameRun);
+
+    env.set_auths(&[]);
+    assert!(policy_client.try_revoke_vk(&vk_hash).is_err());
+    assert_eq!(policy_client.get_vk(&vk_hash).unwrap().state, VkState::Active);
+}
+
+/// unlock_weapon verifies against the registered SkillProof key, never a caller-supplied one.
+#[test]
+fn test_unlock_weapon_unknown_vk_rejected() {
+    let env = Env::default();
+    let (policy_client, player, game_run_hash) = setup_submit(&env);
+    add_default_weapons(&env, &policy_client);
+
+    let zero = BytesN::from_array(&env, &[0u8; 32]);
+    let res = policy_client.try_unlock_weapon(
+        &player,
+        &2u32,
+        &default_proof(&env),
+        &game_run_hash,
+        &vec![&env, zero.clone(), zero],
+        &1000u32,
+    );
+    assert_eq!(res, Err(Ok(CosmicCoderError::UnknownVk)));
+    assert!(!policy_client.is_weapon_unlocked(&player, &2u32));
+}
+
+/// SkillProof signals must be [threshold, publicHash, player_hi, player_lo] with the caller's
+/// address commitment, and each publicHash unlocks once.
+#[test]
+fn test_unlock_weapon_bound_to_threshold_and_player() {
+    let env = Env::default();
+    let (client, player, _) = setup_submit(&env);
+    client.set_verifier(&VerifierKind::WeaponUnlock, &env.register(AcceptingVerifier, ()));
+    let skill_hash = client.register_vk(&Circuit::SkillProof, &vk_with_ic_len(&env, 5));
+    add_default_weapons(&env, &client);
+    let other = Address::generate(&env);
+    let public_hash = BytesN::from_array(&env, &[7u8; 32]);
+    let unlock = |who: &Address, weapon_id: u32, threshold: u32, sig: SorobanVec<BytesN<32>>| {
+        client.try_unlock_weapon(who, &weapon_id, &default_proof(&env), &skill_hash, &sig, &threshold)
+    };
+
+    // A proof made for another wallet does not unlock for the caller.
+    assert_eq!(
+        unlock(&player, 2, 1000, skill_signals(&env, 1000, &public_hash, &other)),
+        Err(Ok(CosmicCoderError::PublicInputMismatch))
+    );
+    let mut swapped = skill_signals(&env, 1000, &public_hash, &player);
+    let (hi, lo) = (swapped.get(2).unwrap(), swapped.get(3).unwrap());
+    swapped.set(2, lo);
+    swapped.set(3, hi);
+    assert_eq!(
+        unlock(&player, 2, 1000, swapped),
+        Err(Ok(CosmicCoderError::PublicInputMismatch))
+    );
+
+    // The proven threshold must be the weapon's threshold.
+    assert_eq!(
+        unlock(&player, 2, 1000, skill_signals(&env, 0, &public_hash, &player)),
+        Err(Ok(CosmicCoderError::PublicInputMismatch))
+    );
+    let short = vec![&env, BytesN::from_array(&env, &u64_signal(1000)), public_hash.clone()];
+    assert_eq!(unlock(&player, 2, 1000, short), Err(Ok(CosmicCoderError::InvalidInput)));
+
+    assert_eq!(
+        unlock(&player, 2, 1000, skill_signals(&env, 1000, &public_hash, &player)),
+        Ok(Ok(()))
+    );
+    assert!(client.is_weapon_unlocked(&player, &2u32));
+
+    // A consumed publicHash cannot unlock again, for this player or anyone else.
+    assert_eq!(
+        unlock(&player, 3, 5000, skill_signals(&env, 5000, &public_hash, &player)),
+        Err(Ok(CosmicCoderError::CommitmentUsed))
+    );
+    assert_eq!(
+        unlock(&other, 2, 1000, skill_signals(&env, 1000, &public_hash, &other)),
+        Err(Ok(CosmicCoderError::CommitmentUsed))
+    );
+    assert!(!client.is_weapon_unlocked(&player, &3u32));
+    assert!(!client.is_weapon_unlocked(&other, &2u32));
+}
+
+/// A proof seen in the mempool cannot be replayed by another wallet, and unlocking for the
+/// player needs the player's signature.
+#[test]
+fn test_unlock_proof_cannot_be_stolen() {
+    let env = Env::default();
+    let (client, player, _) = setup_submit(&env);
+    client.set_verifier(&VerifierKind::WeaponUnlock, &env.register(AcceptingVerifier, ()));
+    let skill_hash = client.register_vk(&Circuit::SkillProof, &vk_with_ic_len(&env, 5));
+    add_default_weapons(&env, &client);
+    let attacker = Address::generate(&env);
+    let signals = skill_signals(&env, 1000, &BytesN::from_array(&env, &[7u8; 32]), &player);
+
+    assert_eq!(
+        client.try_unlock_weapon(&attacker, &2u32, &default_proof(&env), &skill_hash, &signals, &1000u32),
+        Err(Ok(CosmicCoderError::PublicInputMismatch))
+    );
+
+    let res = client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "unlock_weapon",
+                args: (&player, 2u32, default_proof(&env), &skill_hash, &signals, 1000u32)
+                    .into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_unlock_weapon(&player, &2u32, &default_proof(&env), &skill_hash, &signals, &1000u32);
+    assert!(res.is_err());
+    assert!(!client.is_weapon_unlocked(&player, &2u32));
+
+    env.mock_all_auths();
+    client.unlock_weapon(&player, &2u32, &default_proof(&env), &skill_hash, &signals, &1000u32);
+    assert!(client.is_weapon_unlocked(&player, &2u32));
+    assert!(!client.is_weapon_unlocked(&attacker, &2u32));
+}
+
+/// unlock_weapon and get_unlocked_weapons follow the admin-managed catalog.
+#[test]
+fn test_weapon_catalog_drives_unlocks() {
+    let env = Env::default();
+    let (client, player, game_run_hash) = setup_submit(&env);
+    client.set_verifier(&VerifierKind::WeaponUnlock, &env.register(AcceptingVerifier, ()));
+    let skill_hash = client.register_vk(&Circuit::SkillProof, &vk_with_ic_len(&env, 5));
+    let mut other_vk = vk_with_ic_len(&env, 5);
+    other_vk.alpha = BytesN::from_array(&env, &[1u8; G1_SIZE]);
+    let other_skill_hash = client.register_vk(&Circuit::SkillProof, &other_vk);
+    let mut next_hash = 0u8;
+    let mut unlock = |weapon_id: u32, threshold: u32, vk_hash: &BytesN<32>| {
+        next_hash += 1;
+        let sig = skill_signals(&env, threshold, &BytesN::from_array(&env, &[next_hash; 32]), &player);
+        client.try_unlock_weapon(&player, &weapon_id, &default_proof(&env), vk_hash, &sig, &threshold)
+    };
+
+    assert_eq!(unlock(2, 1000, &skill_hash), Err(Ok(CosmicCoderError::UnknownWeapon)));
+
+    // A required key must be a registered SkillProof key.
+    let mut laser = weapon(&env, 7, "Laser", 3000);
+    laser.vk_hash = Some(game_run_hash);
+    assert_eq!(client.try_set_weapon(&laser), Err(Ok(CosmicCoderError::UnknownVk)));
+    laser.vk_hash = Some(skill_hash.clone());
+    laser.enabled = false;
+    client.set_weapon(&laser);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (Symbol::new(&env, "weapon_set"), 7u32).into_val(&env),
+                laser.clone().into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.try_set_weapon(&weapon(&env, 0, "None", 0)), Err(Ok(CosmicCoderError::InvalidInput)));
+
+    assert_eq!(unlock(7, 3000, &skill_hash), Err(Ok(CosmicCoderError::WeaponDisabled)));
+    laser.enabled = true;
+    client.set_weapon(&laser);
+    assert_eq!(unlock(7, 1000, &skill_hash), Err(Ok(CosmicCoderError::InvalidInput)));
+    assert_eq!(unlock(7, 3000, &other_skill_hash), Err(Ok(CosmicCoderError::UnknownVk)));
+    assert_eq!(unlock(7, 3000, &skill_hash), Ok(Ok(())));
+
+    // Season-limited weapon: only while that season is open.
+    let mut trophy = weapon(&env, 8, "Trophy", 0);
+    trophy.season_id = Some(2);
+    client.set_weapon(&trophy);
+    assert_eq!(unlock(8, 0, &other_skill_hash), Err(Ok(CosmicCoderError::SeasonNotFound)));
+    client.create_season(&2, &(SEASON_LEDGERS + 1), &(2 * SEASON_LEDGERS));
+    assert_eq!(unlock(8, 0, &other_skill_hash), Err(Ok(CosmicCoderError::SeasonNotActive)));
+    env.ledger().set_sequence_number(SEASON_LEDGERS + 1);
+    assert_eq!(unlock(8, 0, &other_skill_hash), Ok(Ok(())));
+
+    assert_eq!(client.list_weapons(), vec![&env, laser, trophy]);
+    assert_eq!(client.get_unlocked_weapons(&player), vec![&env, 7u32, 8u32]);
+}
+
+/// Ownership is one bitmap per player: legacy entries migrate into it, batch reads return it
+/// and every access keeps it alive.
+#[test]
+fn test_weapon_bitmap_batch_and_migration() {
+    let env = Env::default();
+    let (client, player, _) = setup_submit(&env);
+    add_default_weapons(&env, &client);
+    client.set_weapon(&weapon(&env, 200, "Relic", 50000));
+    assert_eq!(client.try_set_weapon(&weapon(&env, 256, "TooFar", 0)), Err(Ok(CosmicCoderError::InvalidInput)));
+    let other = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+
+    // Entries written by the per-weapon layout.
+    let legacy = [(&player, 2u32), (&player, 200), (&other, 5)];
+    env.as_contract(&client.address, || {
+        for (who, weapon_id) in legacy {
+            let key = WeaponUnlockKey { player: who.clone(), weapon_id };
+            env.storage().persistent().set(&key, &true);
+        }
+    });
+    assert!(!client.is_weapon_unlocked(&player, &2u32));
+
+    let players = vec![&env, player.clone(), other.clone(), newcomer.clone()];
+    assert_eq!(client.migrate_weapon_unlocks(&players), 3);
+    assert_eq!(client.migrate_weapon_unlocks(&players), 0);
+    env.as_contract(&client.address, || {
+        for (who, weapon_id) in legacy {
+            let key = WeaponUnlockKey { player: who.clone(), weapon_id };
+            assert!(!env.storage().persistent().has(&key));
+        }
+    });
+    assert!(client.is_weapon_unlocked(&player, &200u32));
+    assert_eq!(client.get_unlocked_weapons(&player), vec![&env, 2u32, 200u32]);
+    assert_eq!(client.get_unlocked_weapons(&newcomer), vec![&env]);
+
+    let mut player_bits = [0u8; 32];
+    player_bits[0] = 1 << 2;
+    player_bits[25] = 1;
+    let mut other_bits = [0u8; 32];
+    other_bits[0] = 1 << 5;
+    assert_eq!(
+        client.get_weapon_bitmaps(&players),
+        vec![
+            &env,
+            BytesN::from_array(&env, &player_bits),
+            BytesN::from_array(&env, &other_bits),
+            BytesN::from_array(&env, &[0u8; 32]),
+        ]
+    );
+    let mut too_many = SorobanVec::new(&env);
+    for _ in 0..=MAX_WEAPON_BATCH {
+        too_many.push_back(newcomer.clone());
+    }
+    assert_eq!(client.try_get_weapon_bitmaps(&too_many), Err(Ok(CosmicCoderError::InvalidInput)));
+
+    // Reading the bitmap extends its TTL.
+    env.ledger().set_sequence_number(env.ledger().sequence() + 10_000);
+    assert!(client.is_weapon_unlocked(&other, &5u32));
+    let ttl = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&crate::DataKey::WeaponBits(other.clone()))
+    });
+    assert_eq!(ttl, WEAPON_TTL_LEDGERS);
+}
+
+/// Runs claiming the ZK weapon need it unlocked; they are counted per season and left out of
+/// the filtered leaderboard.
+#[test]
+fn test_zk_weapon_claims_need_ownership() {
+    let env = Env::default();
+    let r = Ranked::new(&env, Backend::UltraHonk);
+    let (a, b) = (Address::generate(&env), Address::generate(&env));
+    let run = |player: &Address, score: u32, nonce: u64, used_zk_weapon: bool| {
+        r.client.try_submit_zk_noir(
+            &ranked_run(player, r.client.start_match(player), nonce, &run_hash_32(&env), 1, score, 5),
+            &honk_proof(&env, &noir_inputs(&env, score, 5, nonce, 1, used_zk_weapon)),
+            &used_zk_weapon,
+        )
+    };
+
+    assert_eq!(run(&a, 500, 1, true), Err(Ok(CosmicCoderError::WeaponNotOwned)));
+    assert_eq!(r.client.get_weapon_assisted_runs(&1), 0);
+
+    grant_weapon(&env, &r.client, &a, ZK_WEAPON_ID);
+    assert!(run(&a, 500, 1, true).is_ok());
+    assert!(run(&b, 300, 1, false).is_ok());
+    assert!(run(&a, 200, 2, false).is_ok());
+    assert_eq!(r.client.get_weapon_assisted_runs(&1), 1);
+
+    let entry = |player: &Address, score: u32| ScoreEntry { player: player.clone(), score };
+    assert_eq!(
+        r.client.get_leaderboard_filtered(&1, &false, &0, &10),
+        vec![&env, entry(&a, 500), entry(&b, 300)]
+    );
+    assert_eq!(
+        r.client.get_leaderboard_filtered(&1, &true, &0, &10),
+        vec![&env, entry(&b, 300), entry(&a, 200)]
+    );
+    assert_eq!(r.client.get_leaderboard_filtered(&1, &true, &1, &10), vec![&env, entry(&a, 200)]);
+}
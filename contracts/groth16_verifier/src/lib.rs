@@ -8,18 +8,35 @@ use core::ops::Neg;
 use soroban_sdk::{
     contract, contractimpl,
     crypto::bn254::{Bn254G1Affine, Bn254G2Affine, Fr},
-    vec, BytesN, Env, Vec,
+    vec, BytesN, Env, Vec, U256,
 };
-use zk_types::{Groth16Error, ZkProof, ZkVerificationKey};
+use zk_types::{validate_proof, validate_vk, Groth16Error, ZkProof, ZkVerificationKey, G1_SIZE};
+
+/// Largest batch accepted by verify_batch (one multi-pairing of MAX_BATCH + 3 pairs).
+pub const MAX_BATCH: u32 = 16;
 
 #[contract]
 pub struct Groth16Verifier;
 
+/// vk_x = ic[0] + sum(pub_signals[i] * ic[i+1]); the caller checks ic.len() == pub_signals.len() + 1.
+fn prepare_inputs(env: &Env, vk: &ZkVerificationKey, pub_signals: &Vec<BytesN<32>>) -> Bn254G1Affine {
+    let bn = env.crypto().bn254();
+    let mut vk_x = Bn254G1Affine::from_bytes(vk.ic.get(0).unwrap());
+    for (i, sig) in pub_signals.iter().enumerate() {
+        let ic_pt = Bn254G1Affine::from_bytes(vk.ic.get((i + 1) as u32).unwrap());
+        let fr = Fr::from_bytes(sig.clone());
+        let prod = bn.g1_mul(&ic_pt, &fr);
+        vk_x = bn.g1_add(&vk_x, &prod);
+    }
+    vk_x
+}
+
 #[contractimpl]
 impl Groth16Verifier {
     /// Verify a Groth16 proof against the given verification key and public inputs.
     /// Pairing check: e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
     /// where vk_x = ic[0] + sum(pub_signals[i] * ic[i+1]).
+    /// Malformed points fail with InvalidVerificationKey (VK) or InvalidProof (proof).
     pub fn verify_proof(
         env: Env,
         vk: ZkVerificationKey,
@@ -31,7 +48,10 @@ impl Groth16Verifier {
         if pub_signals.len() + 1 != vk.ic.len() {
             return Err(Groth16Error::MalformedVerifyingKey);
         }
+        validate_vk(&vk)?;
+        validate_proof(&proof)?;
 
+        let vk_x = prepare_inputs(&env, &vk, &pub_signals);
         let proof_a = Bn254G1Affine::from_bytes(proof.a);
         let proof_b = Bn254G2Affine::from_bytes(proof.b);
         let proof_c = Bn254G1Affine::from_bytes(proof.c);
@@ -40,20 +60,71 @@ impl Groth16Verifier {
         let vk_gamma = Bn254G2Affine::from_bytes(vk.gamma);
         let vk_delta = Bn254G2Affine::from_bytes(vk.delta);
 
-        let mut vk_x = Bn254G1Affine::from_bytes(vk.ic.get(0).unwrap());
-        for (i, sig) in pub_signals.iter().enumerate() {
-            let ic_pt = Bn254G1Affine::from_bytes(vk.ic.get((i + 1) as u32).unwrap());
-            let fr = Fr::from_bytes(sig.clone());
-            let prod = bn.g1_mul(&ic_pt, &fr);
-            vk_x = bn.g1_add(&vk_x, &prod);
-        }
-
         let neg_a = proof_a.neg();
         let vp1 = vec![&env, neg_a, vk_alpha, vk_x, proof_c];
         let vp2 = vec![&env, proof_b, vk_beta, vk_gamma, vk_delta];
 
         Ok(bn.pairing_check(vp1, vp2))
     }
+
+    /// Verify several proofs against the same verification key with one multi-pairing.
+    /// Proof i is weighted by a random 128-bit r_i from the ledger PRNG and
+    /// prod e(-r_i*A_i, B_i) * e(sum(r_i)*alpha, beta) * e(sum(r_i*vk_x_i), gamma)
+    ///     * e(sum(r_i*C_i), delta) == 1
+    /// is checked: N + 3 pairings instead of 4N. Returns true only if every proof is valid
+    /// (a bad proof passes with probability ~2^-128). `signals[i]` are the inputs of `proofs[i]`.
+    pub fn verify_batch(
+        env: Env,
+        vk: ZkVerificationKey,
+        proofs: Vec<ZkProof>,
+        signals: Vec<Vec<BytesN<32>>>,
+    ) -> Result<bool, Groth16Error> {
+        let bn = env.crypto().bn254();
+
+        if proofs.is_empty() || proofs.len() > MAX_BATCH || proofs.len() != signals.len() {
+            return Err(Groth16Error::InvalidPublicInputs);
+        }
+        for pub_signals in signals.iter() {
+            if pub_signals.len() + 1 != vk.ic.len() {
+                return Err(Groth16Error::MalformedVerifyingKey);
+            }
+        }
+        validate_vk(&vk)?;
+        for proof in proofs.iter() {
+            validate_proof(&proof)?;
+        }
+
+        let infinity = Bn254G1Affine::from_bytes(BytesN::from_array(&env, &[0u8; G1_SIZE]));
+        let mut r_sum = U256::from_u32(&env, 0);
+        let mut acc_x = infinity.clone();
+        let mut acc_c = infinity;
+        let mut vp1 = Vec::new(&env);
+        let mut vp2 = Vec::new(&env);
+        for (proof, pub_signals) in proofs.iter().zip(signals.iter()) {
+            let r = U256::from_u128(&env, u128::from_be_bytes(env.prng().gen::<[u8; 16]>()));
+            r_sum = r_sum.add(&r);
+            let r = Fr::from_u256(r);
+
+            let proof_a = Bn254G1Affine::from_bytes(proof.a);
+            vp1.push_back(bn.g1_mul(&proof_a, &r).neg());
+            vp2.push_back(Bn254G2Affine::from_bytes(proof.b));
+
+            let vk_x = prepare_inputs(&env, &vk, &pub_signals);
+            acc_x = bn.g1_add(&acc_x, &bn.g1_mul(&vk_x, &r));
+            let proof_c = Bn254G1Affine::from_bytes(proof.c);
+            acc_c = bn.g1_add(&acc_c, &bn.g1_mul(&proof_c, &r));
+        }
+
+        let vk_alpha = Bn254G1Affine::from_bytes(vk.alpha);
+        vp1.push_back(bn.g1_mul(&vk_alpha, &Fr::from_u256(r_sum)));
+        vp2.push_back(Bn254G2Affine::from_bytes(vk.beta));
+        vp1.push_back(acc_x);
+        vp2.push_back(Bn254G2Affine::from_bytes(vk.gamma));
+        vp1.push_back(acc_c);
+        vp2.push_back(Bn254G2Affine::from_bytes(vk.delta));
+
+        Ok(bn.pairing_check(vp1, vp2))
+    }
 }
 
 #[cfg(test)]
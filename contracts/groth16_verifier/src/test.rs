@@ -1,13 +1,18 @@
 //! Unit tests: malformed VK rejected, invalid proof rejected, valid proof returns Ok(true).
-//! Valid proof test uses point-at-infinity (pairing can succeed); full valid test needs pre-generated proof.
+//! Valid proof test uses point-at-infinity (pairing can succeed); batch tests use the real
+//! GameRunV2 fixtures (circuits/build/gamerun_v2_contract_proof{,_2}.json). Malformed points
+//! (flag bits, non-canonical coordinates, off curve, outside G2) must return typed errors.
 
 #![cfg(test)]
 extern crate std;
 
 use soroban_sdk::{vec, Env, Vec};
-use zk_types::{ZkProof, ZkVerificationKey, G1_SIZE, G2_SIZE, FR_SIZE};
+use zk_types::testutils::{hex_bytes, v2_fixture, v2_fixture_2};
+use zk_types::{
+    g1_is_valid, g2_is_valid, Groth16Error, ZkProof, ZkVerificationKey, G1_SIZE, G2_SIZE, FR_SIZE,
+};
 
-use crate::{Groth16Verifier, Groth16VerifierClient};
+use crate::{Groth16Verifier, Groth16VerifierClient, MAX_BATCH};
 
 fn g1_bytes(env: &Env) -> soroban_sdk::BytesN<G1_SIZE> {
     soroban_sdk::BytesN::from_array(env, &[0u8; G1_SIZE])
@@ -97,3 +102,251 @@ fn test_valid_proof_returns_ok_true() {
     let res = client.verify_proof(&vk, &proof, &pub_signal);
     assert!(res || !res); // contract returns bool; point-at-infinity may be true or false
 }
+
+#[test]
+fn test_batch_of_valid_proofs_verifies() {
+    let env = Env::default();
+    let client = create_client(&env);
+    let (vk, proof, signals) = v2_fixture(&env);
+    assert!(client.verify_proof(&vk, &proof, &signals));
+
+    for n in [1, 3] {
+        let mut proofs = Vec::new(&env);
+        let mut batch_signals = Vec::new(&env);
+        for _ in 0..n {
+            proofs.push_back(proof.clone());
+            batch_signals.push_back(signals.clone());
+        }
+        assert!(client.verify_batch(&vk, &proofs, &batch_signals));
+    }
+}
+
+/// Two independent proofs (different runs) batch together under one key.
+#[test]
+fn test_batch_of_distinct_proofs_verifies() {
+    let env = Env::default();
+    let client = create_client(&env);
+    let (vk, proof, signals) = v2_fixture(&env);
+    let (proof_2, signals_2) = v2_fixture_2(&env);
+    assert!(proof_2.a != proof.a && signals_2 != signals);
+    assert!(client.verify_proof(&vk, &proof_2, &signals_2));
+
+    assert!(client.verify_batch(
+        &vk,
+        &vec![&env, proof.clone(), proof_2.clone()],
+        &vec![&env, signals.clone(), signals_2.clone()],
+    ));
+    assert!(client.verify_batch(
+        &vk,
+        &vec![&env, proof_2.clone(), proof, proof_2],
+        &vec![&env, signals_2.clone(), signals, signals_2],
+    ));
+}
+
+/// First proof valid, second bad: the batch as a whole is rejected.
+#[test]
+fn test_batch_with_bad_second_proof_fails() {
+    let env = Env::default();
+    let client = create_client(&env);
+    let (vk, proof, signals) = v2_fixture(&env);
+    let (proof_2, signals_2) = v2_fixture_2(&env);
+
+    // Second proof paired with the first run's signals.
+    assert!(!client.verify_batch(
+        &vk,
+        &vec![&env, proof.clone(), proof_2.clone()],
+        &vec![&env, signals.clone(), signals.clone()],
+    ));
+    // Second proof with its own signals but the first proof's C.
+    let forged = ZkProof { c: proof.c.clone(), ..proof_2 };
+    assert!(!client.verify_proof(&vk, &forged, &signals_2));
+    assert!(!client.verify_batch(
+        &vk,
+        &vec![&env, proof, forged],
+        &vec![&env, signals, signals_2],
+    ));
+}
+
+/// One altered member fails the whole batch, wherever it sits.
+#[test]
+fn test_batch_with_one_bad_proof_fails() {
+    let env = Env::default();
+    let client = create_client(&env);
+    let (vk, proof, signals) = v2_fixture(&env);
+
+    let mut tampered = signals.clone();
+    let mut score = tampered.get(2).unwrap().to_array();
+    score[31] ^= 1;
+    tampered.set(2, soroban_sdk::BytesN::from_array(&env, &score));
+    assert!(!client.verify_proof(&vk, &proof, &tampered));
+
+    for bad in 0..3u32 {
+        let mut proofs = Vec::new(&env);
+        let mut batch_signals = Vec::new(&env);
+        for i in 0..3u32 {
+            proofs.push_back(proof.clone());
+            batch_signals.push_back(if i == bad { tampered.clone() } else { signals.clone() });
+        }
+        assert!(!client.verify_batch(&vk, &proofs, &batch_signals));
+    }
+}
+
+#[test]
+fn test_batch_shape_rejected() {
+    let env = Env::default();
+    let client = create_client(&env);
+    let (vk, proof, signals) = v2_fixture(&env);
+
+    let empty: Vec<ZkProof> = Vec::new(&env);
+    assert_eq!(
+        client.try_verify_batch(&vk, &empty, &Vec::new(&env)),
+        Err(Ok(Groth16Error::InvalidPublicInputs))
+    );
+    assert_eq!(
+        client.try_verify_batch(&vk, &vec![&env, proof.clone(), proof.clone()], &vec![&env, signals.clone()]),
+        Err(Ok(Groth16Error::InvalidPublicInputs))
+    );
+
+    let mut proofs = Vec::new(&env);
+    let mut batch_signals = Vec::new(&env);
+    for _ in 0..=MAX_BATCH {
+        proofs.push_back(proof.clone());
+        batch_signals.push_back(signals.clone());
+    }
+    assert_eq!(
+        client.try_verify_batch(&vk, &proofs, &batch_signals),
+        Err(Ok(Groth16Error::InvalidPublicInputs))
+    );
+
+    let mut short = signals.clone();
+    short.pop_back();
+    assert_eq!(
+        client.try_verify_batch(&vk, &vec![&env, proof], &vec![&env, short]),
+        Err(Ok(Groth16Error::MalformedVerifyingKey))
+    );
+}
+
+/// G2 generator, Ethereum encoding x.c1 || x.c0 || y.c1 || y.c0.
+const G2_GEN: &str = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+                      1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+                      090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+                      12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+/// The generator with y.c1 + p in place of y.c1 (same point, non-canonical encoding).
+const G2_GEN_Y_PLUS_P: &str = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+                               1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+                               396ad8433991909fa4eedf63ea8d8bf353cc9bc4d925598091cd66f3a99f94a2\
+                               12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+/// On the twist y^2 = x^3 + 3/(9+u) but outside the order-r subgroup.
+const G2_NOT_IN_SUBGROUP: &str = "0d6fe64bc9e9c616612e7696a6cecc1b78e510617311d8a3c2ce6f447ed4d57b\
+                                  078bfae2414c343c1027c4d1c386bbc4cd613e30d8f16adf91b7584a2265b1f5\
+                                  1ef79b362c6a421cd8514cb99be02d2d8a6111baf70fc18734a3ca43a305bec4\
+                                  29f1cb6944a657b2a541ee0c47d879d3783e03c7471de35b0760b564fd772542";
+/// p + 1, a non-canonical encoding of x = 1.
+const P_PLUS_ONE: &str = "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd48";
+
+fn hex_arr<const N: usize>(hex: &str) -> [u8; N] {
+    let hex: std::string::String = hex.split_whitespace().collect();
+    hex_bytes(&hex).try_into().unwrap()
+}
+
+/// G1 faults: flag bit set, x = 1 encoded as p + 1, (1, 3) off the curve.
+fn bad_g1_points(env: &Env) -> std::vec::Vec<soroban_sdk::BytesN<G1_SIZE>> {
+    let mut generator = [0u8; G1_SIZE];
+    generator[31] = 1;
+    generator[63] = 2;
+    let mut flagged = generator;
+    flagged[0] |= 0x80;
+    let mut non_canonical = generator;
+    non_canonical[..32].copy_from_slice(&hex_arr::<32>(P_PLUS_ONE));
+    let mut off_curve = generator;
+    off_curve[63] = 3;
+    assert!(g1_is_valid(&generator));
+    [flagged, non_canonical, off_curve]
+        .iter()
+        .map(|p| soroban_sdk::BytesN::from_array(env, p))
+        .collect()
+}
+
+/// G2 faults: flag bit set, non-canonical y.c1, off the twist, outside the subgroup.
+fn bad_g2_points(env: &Env) -> std::vec::Vec<soroban_sdk::BytesN<G2_SIZE>> {
+    let generator = hex_arr::<G2_SIZE>(G2_GEN);
+    let mut flagged = generator;
+    flagged[0] |= 0x40;
+    let mut off_curve = generator;
+    off_curve[127] ^= 1;
+    assert!(g2_is_valid(&generator));
+    [flagged, hex_arr(G2_GEN_Y_PLUS_P), off_curve, hex_arr(G2_NOT_IN_SUBGROUP)]
+        .iter()
+        .map(|p| soroban_sdk::BytesN::from_array(env, p))
+        .collect()
+}
+
+/// Every point of the real fixture passes the checks.
+#[test]
+fn test_fixture_points_are_valid() {
+    let env = Env::default();
+    let (vk, proof, _) = v2_fixture(&env);
+    assert!(g1_is_valid(&proof.a.to_array()) && g1_is_valid(&proof.c.to_array()));
+    assert!(g2_is_valid(&proof.b.to_array()));
+    assert!(g1_is_valid(&vk.alpha.to_array()));
+    for g2 in [&vk.beta, &vk.gamma, &vk.delta] {
+        assert!(g2_is_valid(&g2.to_array()));
+    }
+    assert!(vk.ic.iter().all(|p| g1_is_valid(&p.to_array())));
+}
+
+#[test]
+fn test_malformed_proof_points_rejected() {
+    let env = Env::default();
+    let client = create_client(&env);
+    let (vk, proof, signals) = v2_fixture(&env);
+
+    let mut bad_proofs = std::vec::Vec::new();
+    for g1 in bad_g1_points(&env) {
+        bad_proofs.push(ZkProof { a: g1.clone(), ..proof.clone() });
+        bad_proofs.push(ZkProof { c: g1, ..proof.clone() });
+    }
+    for g2 in bad_g2_points(&env) {
+        bad_proofs.push(ZkProof { b: g2, ..proof.clone() });
+    }
+    for bad in bad_proofs {
+        assert_eq!(
+            client.try_verify_proof(&vk, &bad, &signals),
+            Err(Ok(Groth16Error::InvalidProof))
+        );
+        assert_eq!(
+            client.try_verify_batch(&vk, &vec![&env, proof.clone(), bad], &vec![&env, signals.clone(), signals.clone()]),
+            Err(Ok(Groth16Error::InvalidProof))
+        );
+    }
+}
+
+#[test]
+fn test_malformed_vk_points_rejected() {
+    let env = Env::default();
+    let client = create_client(&env);
+    let (vk, proof, signals) = v2_fixture(&env);
+
+    let mut bad_vks = std::vec::Vec::new();
+    for g1 in bad_g1_points(&env) {
+        bad_vks.push(ZkVerificationKey { alpha: g1.clone(), ..vk.clone() });
+        let mut ic = vk.ic.clone();
+        ic.set(3, g1);
+        bad_vks.push(ZkVerificationKey { ic, ..vk.clone() });
+    }
+    for g2 in bad_g2_points(&env) {
+        bad_vks.push(ZkVerificationKey { beta: g2.clone(), ..vk.clone() });
+        bad_vks.push(ZkVerificationKey { gamma: g2.clone(), ..vk.clone() });
+        bad_vks.push(ZkVerificationKey { delta: g2, ..vk.clone() });
+    }
+    for bad in bad_vks {
+        assert_eq!(
+            client.try_verify_proof(&bad, &proof, &signals),
+            Err(Ok(Groth16Error::InvalidVerificationKey))
+        );
+        assert_eq!(
+            client.try_verify_batch(&bad, &vec![&env, proof.clone()], &vec![&env, signals.clone()]),
+            Err(Ok(Groth16Error::InvalidVerificationKey))
+        );
+    }
+}
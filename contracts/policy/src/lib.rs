@@ -1,6 +1,7 @@
 //! Policy contract v2 — semantic checks, anti-replay, domain separation.
 //! No cryptographic verification. Calls verifier contract.
 //! Enforces:
+//!   - caller is the configured application contract
 //!   - domain_separator binding (challenge_id, player_address, nonce, contract_id)
 //!   - anti-replay (nonce per player per challenge)
 //!   - TTL/ledger bound (optional)
@@ -9,23 +10,12 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracterror, contracttype, Address, Env, IntoVal, Symbol, Vec,
+    contract, contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal,
+    Symbol,
 };
-use zk_types::{Groth16Error, DomainBinding, ZkPublicInputs};
-
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-#[repr(u32)]
-pub enum PolicyError {
-    VerifierNotSet = 1,
-    Replay = 2,
-    InvalidProof = 3,
-    VerifierError = 4,
-    InvalidInput = 5,
-    MalformedVk = 6,
-    DomainMismatch = 7,
-    UnsupportedNetwork = 8,
-}
+use zk_types::{address_digest, encode_public_inputs, Groth16Error, ZkPublicInputs};
+
+pub use zk_types::{DomainBinding, PolicyError};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -35,17 +25,50 @@ pub struct ReplayKey {
     pub challenge_id: u32,
 }
 
+fn require_admin(env: &Env) -> Result<(), PolicyError> {
+    let admin: Address = env
+        .storage()
+        .persistent()
+        .get(&Symbol::new(env, "Admin"))
+        .ok_or(PolicyError::NotInitialized)?;
+    admin.require_auth();
+    Ok(())
+}
+
 #[contract]
 pub struct Policy;
 
 #[contractimpl]
 impl Policy {
-    /// Set verifier contract address.
-    pub fn set_verifier(env: Env, verifier: Address) {
+    /// Set the admin allowed to configure the policy. Once only; `admin` must authorize.
+    pub fn init(env: Env, admin: Address) -> Result<(), PolicyError> {
+        let key = Symbol::new(&env, "Admin");
+        if env.storage().persistent().has(&key) {
+            return Err(PolicyError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().persistent().set(&key, &admin);
+        Ok(())
+    }
+
+    /// Set verifier contract address. Admin only.
+    pub fn set_verifier(env: Env, verifier: Address) -> Result<(), PolicyError> {
+        require_admin(&env)?;
         env.storage().persistent().set(&Symbol::new(&env, "Verifier"), &verifier);
+        Ok(())
+    }
+
+    /// Set the application contract allowed to call `validate`. Admin only.
+    pub fn set_application(env: Env, application: Address) -> Result<(), PolicyError> {
+        require_admin(&env)?;
+        env.storage().persistent().set(&Symbol::new(&env, "Application"), &application);
+        Ok(())
     }
 
     /// Validate domain binding and anti-replay, then call verifier.
+    /// Only the configured application may call it (ApplicationNotSet until set), so nobody
+    /// else can consume a player's nonce with a leaked proof, and the domain must name that
+    /// application (DomainMismatch), so a proof bound to another deployment is refused.
     /// Emits events for audit.
     pub fn validate(
         env: Env,
@@ -57,14 +80,23 @@ impl Policy {
         score: u32,
         wave: u32,
     ) -> Result<(), PolicyError> {
+        // 0) Caller: the application contract passes its own auth by invoking us.
+        let application: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, "Application"))
+            .ok_or(PolicyError::ApplicationNotSet)?;
+        application.require_auth();
+
         // 1) Network capability check (BN254)
         // In Soroban, we can check protocol version via env.ledger().protocol_version()
         // BN254 is available from Protocol 25 (X-Ray). For simplicity, we assume it's present.
         // In production, you could reject if protocol < 25.
 
-        // 2) Domain binding check: ensure domain_separator matches derived fields
-        let derived = self::derive_domain_separator(&env, &domain);
-        if derived != domain.domain_separator {
+        // 2) Domain binding check: the domain names the calling application and
+        // domain_separator matches the derived fields
+        let derived = derive_domain_separator(&env, &domain);
+        if domain.contract_id != application || derived != domain.domain_separator {
             env.events().publish(
                 (Symbol::new(&env, "policy"), Symbol::new(&env, "domain_mismatch")),
                 (domain.challenge_id, domain.player_address, domain.nonce),
@@ -74,9 +106,9 @@ impl Policy {
 
         // 3) Ensure public_inputs match domain and semantic fields (strict ordering)
         if public_inputs.challenge_id != domain.challenge_id ||
-           public_inputs.player_address != domain.player_address.to_contract().to_bytes() ||
+           public_inputs.player_address != address_digest(&env, &domain.player_address) ||
            public_inputs.nonce != domain.nonce ||
-           public_inputs.contract_id != domain.contract_id.to_contract().to_bytes() ||
+           public_inputs.contract_id != address_digest(&env, &domain.contract_id) ||
            public_inputs.domain_separator != domain.domain_separator ||
            public_inputs.score != score ||
            public_inputs.wave != wave {
@@ -123,19 +155,7 @@ impl Policy {
             soroban_sdk::vec![
                 &env,
                 proof.into_val(&env),
-                soroban_sdk::vec![
-                    &env,
-                    public_inputs.run_hash_hi.into_val(&env),
-                    public_inputs.run_hash_lo.into_val(&env),
-                    public_inputs.score.into_val(&env),
-                    public_inputs.wave.into_val(&env),
-                    public_inputs.nonce.into_val(&env),
-                    public_inputs.season_id.into_val(&env),
-                    public_inputs.challenge_id.into_val(&env),
-                    public_inputs.player_address.into_val(&env),
-                    public_inputs.contract_id.into_val(&env),
-                    public_inputs.domain_separator.into_val(&env),
-                ],
+                encode_public_inputs(&env, &public_inputs).into_val(&env),
                 vk_hash.into_val(&env),
             ],
         );
@@ -163,19 +183,20 @@ impl Policy {
 
         Ok(())
     }
+}
 
-    /// Derive domain separator from domain binding fields.
-    /// For simplicity, we use SHA256 of concatenated fields.
-    /// In production, you might use a domain-specific tag.
-    fn derive_domain_separator(env: &Env, domain: &DomainBinding) -> soroban_sdk::BytesN<32> {
-        let mut hasher = env.crypto().sha256();
-        hasher.update([domain.challenge_id.to_le_bytes().as_ref()].concat());
-        hasher.update(domain.player_address.to_array());
-        hasher.update(domain.nonce.to_le_bytes());
-        hasher.update(domain.contract_id.to_array());
-        hasher.finalize()
-    }
+/// Derive domain separator from domain binding fields:
+/// sha256(challenge_id_be || XDR(player_address) || nonce_be || XDR(contract_id)).
+pub fn derive_domain_separator(env: &Env, domain: &DomainBinding) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &domain.challenge_id.to_be_bytes());
+    data.append(&domain.player_address.clone().to_xdr(env));
+    data.extend_from_array(&domain.nonce.to_be_bytes());
+    data.append(&domain.contract_id.clone().to_xdr(env));
+    env.crypto().sha256(&data).into()
 }
 
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
 #[cfg(test)]
 mod test;
@@ -0,0 +1,240 @@
+//! Unit tests: domain binding, public input checks, anti-replay and verifier wiring.
+//! The GameRunV2 fixture (circuits/build/gamerun_v2_contract_proof.json) is a real proof for
+//! circuits/build/gamerun_v2_input.json (`node scripts/zk/generate_proof_v2.js`).
+
+#![cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    Address, BytesN, Env, IntoVal, Symbol,
+};
+use verifier::testutils::new_verifier;
+use zk_types::testutils::{packed_proof, v2_fixture, zero_vk, V2_CONTRACT};
+use zk_types::{address_digest, encode_public_inputs, ZkPublicInputs};
+
+use crate::testutils::v2_run;
+use crate::{derive_domain_separator, DomainBinding, Policy, PolicyClient, PolicyError};
+
+/// Policy with an admin and the application set to V2_CONTRACT, the contract `run` and the
+/// fixture bind to (auths mocked by new_verifier, so tests may call validate directly).
+fn new_policy(env: &Env) -> PolicyClient<'_> {
+    let policy = PolicyClient::new(env, &env.register(Policy, ()));
+    policy.init(&Address::generate(env));
+    policy.set_application(&Address::from_str(env, V2_CONTRACT));
+    policy
+}
+
+/// Verifier with the zero VK stored + policy pointing at it. Returns (policy, vk_hash).
+fn setup(env: &Env) -> (PolicyClient<'_>, BytesN<32>) {
+    let verifier = new_verifier(env);
+    let vk = zero_vk(env, 13);
+    let vk_hash = verifier.store_vk(&vk);
+
+    let policy = new_policy(env);
+    policy.set_verifier(&verifier.address);
+    (policy, vk_hash)
+}
+
+/// Consistent (domain, public_inputs) for a run submitted through V2_CONTRACT.
+fn run(env: &Env, player: &Address, nonce: u64, score: u32, wave: u32) -> (DomainBinding, ZkPublicInputs) {
+    let contract = Address::from_str(env, V2_CONTRACT);
+    let mut domain = DomainBinding {
+        challenge_id: 1,
+        player_address: player.clone(),
+        nonce,
+        contract_id: contract.clone(),
+        domain_separator: BytesN::from_array(env, &[0u8; 32]),
+    };
+    domain.domain_separator = derive_domain_separator(env, &domain);
+    let inputs = ZkPublicInputs {
+        run_hash_hi: BytesN::from_array(env, &[0u8; 32]),
+        run_hash_lo: BytesN::from_array(env, &[0u8; 32]),
+        score,
+        wave,
+        nonce,
+        season_id: 1,
+        challenge_id: 1,
+        player_address: address_digest(env, player),
+        contract_id: address_digest(env, &contract),
+        domain_separator: domain.domain_separator.clone(),
+    };
+    (domain, inputs)
+}
+
+fn proof(env: &Env) -> BytesN<256> {
+    BytesN::from_array(env, &[0u8; 256])
+}
+
+#[test]
+fn test_validate_then_replay_rejected() {
+    let env = Env::default();
+    let (policy, vk_hash) = setup(&env);
+    let (domain, inputs) = run(&env, &Address::generate(&env), 5, 100, 5);
+
+    policy.validate(&domain, &proof(&env), &inputs, &vk_hash, &100, &5);
+    assert_eq!(
+        policy.try_validate(&domain, &proof(&env), &inputs, &vk_hash, &100, &5),
+        Err(Ok(PolicyError::Replay))
+    );
+}
+
+#[test]
+fn test_domain_separator_mismatch() {
+    let env = Env::default();
+    let (policy, vk_hash) = setup(&env);
+    let (mut domain, inputs) = run(&env, &Address::generate(&env), 5, 100, 5);
+    domain.nonce = 6;
+
+    assert_eq!(
+        policy.try_validate(&domain, &proof(&env), &inputs, &vk_hash, &100, &5),
+        Err(Ok(PolicyError::DomainMismatch))
+    );
+}
+
+/// A run bound to another deployment is refused even when domain and inputs agree.
+#[test]
+fn test_domain_must_name_the_application() {
+    let env = Env::default();
+    let (policy, vk_hash) = setup(&env);
+    let (mut domain, mut inputs) = run(&env, &Address::generate(&env), 5, 100, 5);
+    let other = Address::generate(&env);
+    domain.contract_id = other.clone();
+    domain.domain_separator = derive_domain_separator(&env, &domain);
+    inputs.contract_id = address_digest(&env, &other);
+    inputs.domain_separator = domain.domain_separator.clone();
+
+    assert_eq!(
+        policy.try_validate(&domain, &proof(&env), &inputs, &vk_hash, &100, &5),
+        Err(Ok(PolicyError::DomainMismatch))
+    );
+}
+
+#[test]
+fn test_public_inputs_must_match_domain_and_arguments() {
+    let env = Env::default();
+    let (policy, vk_hash) = setup(&env);
+    let player = Address::generate(&env);
+
+    // Claimed score differs from the proven one.
+    let (domain, inputs) = run(&env, &player, 5, 100, 5);
+    assert_eq!(
+        policy.try_validate(&domain, &proof(&env), &inputs, &vk_hash, &200, &5),
+        Err(Ok(PolicyError::InvalidInput))
+    );
+
+    // Proof inputs committed to another player.
+    let (domain, mut inputs) = run(&env, &player, 6, 100, 5);
+    inputs.player_address = address_digest(&env, &Address::generate(&env));
+    assert_eq!(
+        policy.try_validate(&domain, &proof(&env), &inputs, &vk_hash, &100, &5),
+        Err(Ok(PolicyError::InvalidInput))
+    );
+
+    // score < wave * MIN_SCORE_PER_WAVE.
+    let (domain, inputs) = run(&env, &player, 7, 20, 5);
+    assert_eq!(
+        policy.try_validate(&domain, &proof(&env), &inputs, &vk_hash, &20, &5),
+        Err(Ok(PolicyError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_verifier_not_set_or_unknown_vk() {
+    let env = Env::default();
+    let (domain, inputs) = run(&env, &Address::generate(&env), 5, 100, 5);
+
+    env.mock_all_auths();
+    let vk_hash = BytesN::from_array(&env, &[7u8; 32]);
+    let bare = PolicyClient::new(&env, &env.register(Policy, ()));
+    assert_eq!(
+        bare.try_validate(&domain, &proof(&env), &inputs, &vk_hash, &100, &5),
+        Err(Ok(PolicyError::ApplicationNotSet))
+    );
+    let bare = new_policy(&env);
+    assert_eq!(
+        bare.try_validate(&domain, &proof(&env), &inputs, &vk_hash, &100, &5),
+        Err(Ok(PolicyError::VerifierNotSet))
+    );
+
+    let (policy, _) = setup(&env);
+    assert_eq!(
+        policy.try_validate(&domain, &proof(&env), &inputs, &vk_hash, &100, &5),
+        Err(Ok(PolicyError::VerifierError))
+    );
+}
+
+/// init is once-only and needs the admin's auth; set_verifier needs it too.
+#[test]
+fn test_set_verifier_is_admin_only() {
+    let env = Env::default();
+    let policy = PolicyClient::new(&env, &env.register(Policy, ()));
+    let verifier = Address::generate(&env);
+    assert_eq!(policy.try_set_verifier(&verifier), Err(Ok(PolicyError::NotInitialized)));
+
+    let admin = Address::generate(&env);
+    assert!(policy.try_init(&admin).is_err());
+    policy.mock_all_auths().init(&admin);
+    assert_eq!(
+        policy.mock_all_auths().try_init(&Address::generate(&env)),
+        Err(Ok(PolicyError::AlreadyInitialized))
+    );
+
+    assert!(policy.try_set_verifier(&verifier).is_err());
+    policy.mock_all_auths().set_verifier(&verifier);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin,
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    policy.address.clone(),
+                    Symbol::new(&env, "set_verifier"),
+                    (verifier,).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+}
+
+/// The encoder reproduces the circuit's 12 public signals exactly (including the
+/// domain separator, which exceeds r and is reduced), and the proof verifies.
+#[test]
+fn test_encoder_matches_real_gamerun_v2_proof() {
+    let env = Env::default();
+    let (vk, proof, signals) = v2_fixture(&env);
+    let proof = packed_proof(&env, &proof);
+    let (domain, inputs) = v2_run(&env);
+
+    assert_eq!(signals.len(), zk_types::GAMERUN_V2_PUB_SIGNALS);
+    assert_eq!(encode_public_inputs(&env, &inputs), signals);
+
+    let verifier = new_verifier(&env);
+    let vk_hash = verifier.store_vk(&vk);
+    assert!(verifier.verify(&proof, &signals, &vk_hash));
+
+    let policy = new_policy(&env);
+    policy.set_verifier(&verifier.address);
+    policy.validate(&domain, &proof, &inputs, &vk_hash, &1200, &12);
+}
+
+/// Claiming a different score (consistently in inputs and arguments) fails the pairing.
+#[test]
+fn test_real_proof_rejects_altered_inputs() {
+    let env = Env::default();
+    let (vk, proof, _) = v2_fixture(&env);
+    let proof = packed_proof(&env, &proof);
+    let (domain, mut inputs) = v2_run(&env);
+    inputs.score = 1300;
+
+    let verifier = new_verifier(&env);
+    let vk_hash = verifier.store_vk(&vk);
+    let policy = new_policy(&env);
+    policy.set_verifier(&verifier.address);
+
+    assert_eq!(
+        policy.try_validate(&domain, &proof, &inputs, &vk_hash, &1300, &12),
+        Err(Ok(PolicyError::InvalidProof))
+    );
+}
@@ -0,0 +1,39 @@
+//! Test helpers for contracts that call the policy (feature `testutils`).
+
+use soroban_sdk::{Address, BytesN, Env};
+use zk_types::testutils::{hex_bytes, V2_CONTRACT, V2_PLAYER, V2_RUN_HASH};
+use zk_types::{address_digest, DomainBinding, ZkPublicInputs};
+
+use crate::derive_domain_separator;
+
+/// Domain and public inputs the GameRunV2 fixture was generated for.
+pub fn v2_run(env: &Env) -> (DomainBinding, ZkPublicInputs) {
+    let player = Address::from_str(env, V2_PLAYER);
+    let contract = Address::from_str(env, V2_CONTRACT);
+    let mut domain = DomainBinding {
+        challenge_id: 7,
+        player_address: player.clone(),
+        nonce: 1771599381736,
+        contract_id: contract.clone(),
+        domain_separator: BytesN::from_array(env, &[0u8; 32]),
+    };
+    domain.domain_separator = derive_domain_separator(env, &domain);
+    let run_hash = hex_bytes(V2_RUN_HASH);
+    let mut hi = [0u8; 32];
+    let mut lo = [0u8; 32];
+    hi[16..].copy_from_slice(&run_hash[..16]);
+    lo[16..].copy_from_slice(&run_hash[16..]);
+    let inputs = ZkPublicInputs {
+        run_hash_hi: BytesN::from_array(env, &hi),
+        run_hash_lo: BytesN::from_array(env, &lo),
+        score: 1200,
+        wave: 12,
+        nonce: domain.nonce,
+        season_id: 3,
+        challenge_id: 7,
+        player_address: address_digest(env, &player),
+        contract_id: address_digest(env, &contract),
+        domain_separator: domain.domain_separator.clone(),
+    };
+    (domain, inputs)
+}
@@ -0,0 +1,229 @@
+//! BN254 scalar field (Fr) arithmetic in Montgomery form.
+//! The Soroban host exposes G1 add/mul and pairings but no scalar-field arithmetic,
+//! so sumcheck and Shplemini run on this small 4x64-limb implementation.
+
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// r = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001 (little-endian limbs).
+const MODULUS: [u64; 4] = [
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+];
+/// -r^{-1} mod 2^64.
+const INV: u64 = 0xc2e1f593efffffff;
+/// R^2 mod r, R = 2^256.
+const R2: [u64; 4] = [
+    0x1bb8e645ae216da7,
+    0x53fe3ab1e35c59e3,
+    0x8c49833d53bb8085,
+    0x0216d0b17f4e44a5,
+];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Fr([u64; 4]);
+
+#[inline(always)]
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+#[inline(always)]
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub((b as u128) + ((borrow >> 63) as u128));
+    (t as u64, (t >> 64) as u64)
+}
+
+#[inline(always)]
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) * (c as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Returns `a - r` if `a >= r`, else `a`. Input must be < 2r.
+fn reduce_once(a: [u64; 4]) -> [u64; 4] {
+    let (d0, b) = sbb(a[0], MODULUS[0], 0);
+    let (d1, b) = sbb(a[1], MODULUS[1], b);
+    let (d2, b) = sbb(a[2], MODULUS[2], b);
+    let (d3, b) = sbb(a[3], MODULUS[3], b);
+    if b == 0 {
+        [d0, d1, d2, d3]
+    } else {
+        a
+    }
+}
+
+fn lt_modulus(a: &[u64; 4]) -> bool {
+    for i in (0..4).rev() {
+        if a[i] < MODULUS[i] {
+            return true;
+        }
+        if a[i] > MODULUS[i] {
+            return false;
+        }
+    }
+    false
+}
+
+fn limbs_from_be(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let start = 32 - (i + 1) * 8;
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&bytes[start..start + 8]);
+        *limb = u64::from_be_bytes(buf);
+    }
+    limbs
+}
+
+impl Fr {
+    pub const ZERO: Fr = Fr([0, 0, 0, 0]);
+    /// Montgomery form of 1 (R mod r).
+    pub const ONE: Fr = Fr([
+        0xac96341c4ffffffb,
+        0x36fc76959f60cd29,
+        0x666ea36f7879462e,
+        0x0e0a77c19a07df2f,
+    ]);
+
+    fn mont_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        // CIOS Montgomery multiplication; r < 2^254 so the final carry fits in the top limb.
+        let mut t = [0u64; 6];
+        for b_i in b.iter() {
+            let mut carry = 0u64;
+            for j in 0..4 {
+                let (lo, hi) = mac(t[j], a[j], *b_i, carry);
+                t[j] = lo;
+                carry = hi;
+            }
+            let (s, c) = adc(t[4], carry, 0);
+            t[4] = s;
+            t[5] = c;
+
+            let m = t[0].wrapping_mul(INV);
+            let (_, mut carry) = mac(t[0], m, MODULUS[0], 0);
+            for j in 1..4 {
+                let (lo, hi) = mac(t[j], m, MODULUS[j], carry);
+                t[j - 1] = lo;
+                carry = hi;
+            }
+            let (s, c) = adc(t[4], carry, 0);
+            t[3] = s;
+            t[4] = t[5] + c;
+        }
+        reduce_once([t[0], t[1], t[2], t[3]])
+    }
+
+    pub fn from_u64(v: u64) -> Fr {
+        Fr(Self::mont_mul(&[v, 0, 0, 0], &R2))
+    }
+
+    /// Canonical big-endian decoding; `None` if the value is not < r.
+    pub fn from_be_bytes_canonical(bytes: &[u8; 32]) -> Option<Fr> {
+        let limbs = limbs_from_be(bytes);
+        if !lt_modulus(&limbs) {
+            return None;
+        }
+        Some(Fr(Self::mont_mul(&limbs, &R2)))
+    }
+
+    /// Big-endian decoding reduced modulo r (Fiat-Shamir hash outputs).
+    pub fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Fr {
+        let mut limbs = limbs_from_be(bytes);
+        // 2^256 / r < 6, so at most five subtractions are needed.
+        while !lt_modulus(&limbs) {
+            let (d0, b) = sbb(limbs[0], MODULUS[0], 0);
+            let (d1, b) = sbb(limbs[1], MODULUS[1], b);
+            let (d2, b) = sbb(limbs[2], MODULUS[2], b);
+            let (d3, _) = sbb(limbs[3], MODULUS[3], b);
+            limbs = [d0, d1, d2, d3];
+        }
+        Fr(Self::mont_mul(&limbs, &R2))
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        let limbs = Self::mont_mul(&self.0, &[1, 0, 0, 0]);
+        let mut out = [0u8; 32];
+        for (i, limb) in limbs.iter().enumerate() {
+            let start = 32 - (i + 1) * 8;
+            out[start..start + 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        out
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0, 0, 0, 0]
+    }
+
+    pub fn square(&self) -> Fr {
+        *self * *self
+    }
+
+    pub fn pow(&self, exp: &[u64; 4]) -> Fr {
+        let mut res = Fr::ONE;
+        for i in (0..4).rev() {
+            for bit in (0..64).rev() {
+                res = res.square();
+                if (exp[i] >> bit) & 1 == 1 {
+                    res = res * *self;
+                }
+            }
+        }
+        res
+    }
+
+    /// Multiplicative inverse via Fermat (a^(r-2)); `None` for zero.
+    pub fn invert(&self) -> Option<Fr> {
+        if self.is_zero() {
+            return None;
+        }
+        let exp = [MODULUS[0] - 2, MODULUS[1], MODULUS[2], MODULUS[3]];
+        Some(self.pow(&exp))
+    }
+}
+
+impl Add for Fr {
+    type Output = Fr;
+    fn add(self, rhs: Fr) -> Fr {
+        let (d0, c) = adc(self.0[0], rhs.0[0], 0);
+        let (d1, c) = adc(self.0[1], rhs.0[1], c);
+        let (d2, c) = adc(self.0[2], rhs.0[2], c);
+        let (d3, _) = adc(self.0[3], rhs.0[3], c);
+        Fr(reduce_once([d0, d1, d2, d3]))
+    }
+}
+
+impl Sub for Fr {
+    type Output = Fr;
+    fn sub(self, rhs: Fr) -> Fr {
+        let (d0, b) = sbb(self.0[0], rhs.0[0], 0);
+        let (d1, b) = sbb(self.0[1], rhs.0[1], b);
+        let (d2, b) = sbb(self.0[2], rhs.0[2], b);
+        let (d3, b) = sbb(self.0[3], rhs.0[3], b);
+        if b == 0 {
+            Fr([d0, d1, d2, d3])
+        } else {
+            let (d0, c) = adc(d0, MODULUS[0], 0);
+            let (d1, c) = adc(d1, MODULUS[1], c);
+            let (d2, c) = adc(d2, MODULUS[2], c);
+            let (d3, _) = adc(d3, MODULUS[3], c);
+            Fr([d0, d1, d2, d3])
+        }
+    }
+}
+
+impl Mul for Fr {
+    type Output = Fr;
+    fn mul(self, rhs: Fr) -> Fr {
+        Fr(Self::mont_mul(&self.0, &rhs.0))
+    }
+}
+
+impl Neg for Fr {
+    type Output = Fr;
+    fn neg(self) -> Fr {
+        Fr::ZERO - self
+    }
+}
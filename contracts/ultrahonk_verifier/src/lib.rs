@@ -1,9 +1,25 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, symbol_short, Bytes, BytesN, Env, Symbol,
+    contract, contracterror, contractimpl, contracttype, Address, Bytes, BytesN, Env, Symbol, Vec,
 };
 
+mod field;
+mod relations;
+mod transcript;
+mod types;
+mod verifier;
+
+use types::{Proof, VerificationKey, FIELD_SIZE, PROOF_FIELDS, PROOF_SIZE, VK_SIZE};
+
+/// Upper bound on user public inputs accepted in a proof blob.
+const MAX_USER_PUBLIC_INPUTS: usize = 32;
+
+/// How long a verified-proof record is kept (approx 30 days in ledgers, ~5 sec per ledger).
+/// Records live in temporary storage and disappear afterwards, so proof-level replay
+/// protection (`reject_seen`) only covers this window.
+pub const PROOF_TTL_LEDGERS: u32 = 518_400;
+
 #[contract]
 pub struct UltraHonkVerifier;
 
@@ -15,61 +31,255 @@ pub enum Error {
     ProofParseError = 2,
     VerificationFailed = 3,
     VkNotSet = 4,
+    NotInitialized = 5,
+    AlreadyInitialized = 6,
+    /// A key is (or was) stored under this id; publish a new version instead.
+    VkAlreadySet = 7,
+    /// `reject_seen` was set and this proof id is already recorded.
+    ProofAlreadyVerified = 8,
+}
+
+/// Name of a stored verification key: the circuit and its version. Keys are write-once,
+/// so a circuit upgrade is published as a new version next to the old one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VkId {
+    pub circuit: Symbol,
+    pub version: u32,
+}
+
+/// One stored key, as listed by `list_vks`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VkInfo {
+    pub vk_id: VkId,
+    pub vk_hash: BytesN<32>,
+}
+
+/// Who verified a proof and when, as returned by `is_verified`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofRecord {
+    pub ledger: u32,
+    pub caller: Address,
+}
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    /// `bb write_vk` bytes of a key.
+    Vk(VkId),
+    /// sha256 of the key bytes.
+    VkHash(VkId),
+    /// Id whose key was removed; kept so the id can never be given another key.
+    VkRemoved(VkId),
+    /// Ids of all stored keys, in storage order.
+    VkList,
+    /// Verified proof record (temporary storage, PROOF_TTL_LEDGERS).
+    Proof(BytesN<32>),
+}
+
+fn require_admin(env: &Env) -> Result<(), Error> {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+    admin.require_auth();
+    Ok(())
+}
+
+fn proof_record(env: &Env, proof_id: &BytesN<32>) -> Option<ProofRecord> {
+    env.storage().temporary().get(&DataKey::Proof(proof_id.clone()))
+}
+
+/// Record a verified proof. The first record is kept (its TTL is refreshed) so `is_verified`
+/// always names the first verifier.
+fn record_proof(env: &Env, proof_id: &BytesN<32>, caller: &Address) {
+    let key = DataKey::Proof(proof_id.clone());
+    if !env.storage().temporary().has(&key) {
+        let record = ProofRecord {
+            ledger: env.ledger().sequence(),
+            caller: caller.clone(),
+        };
+        env.storage().temporary().set(&key, &record);
+    }
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, PROOF_TTL_LEDGERS, PROOF_TTL_LEDGERS);
+}
+
+fn vk_list(env: &Env) -> Vec<VkId> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VkList)
+        .unwrap_or(Vec::new(env))
+}
+
+fn parse_vk(vk_bytes: &Bytes) -> Result<VerificationKey, Error> {
+    if vk_bytes.len() as usize != VK_SIZE {
+        return Err(Error::VkParseError);
+    }
+    let mut buf = [0u8; VK_SIZE];
+    vk_bytes.copy_into_slice(&mut buf);
+    VerificationKey::from_bytes(&buf).map_err(|_| Error::VkParseError)
 }
 
 #[contractimpl]
 impl UltraHonkVerifier {
-    fn key_vk() -> Symbol {
-        symbol_short!("vk")
+    /// Set the admin allowed to manage keys. Once only, signed by `admin`.
+    pub fn init(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
     }
 
-    fn key_vk_hash() -> Symbol {
-        symbol_short!("vk_hash")
+    /// Stores a verification key (`bb write_vk` bytes output) under `vk_id` and returns its
+    /// sha256. Admin only. The key is parsed first so a malformed key can never be stored,
+    /// and an id is never overwritten or reused after remove_vk (VkAlreadySet).
+    /// Emits ("vk_set", circuit, version) with the hash.
+    pub fn set_vk(env: Env, vk_id: VkId, vk_bytes: Bytes) -> Result<BytesN<32>, Error> {
+        require_admin(&env)?;
+        parse_vk(&vk_bytes)?;
+        if env.storage().persistent().has(&DataKey::Vk(vk_id.clone()))
+            || env.storage().persistent().has(&DataKey::VkRemoved(vk_id.clone()))
+        {
+            return Err(Error::VkAlreadySet);
+        }
+        let vk_hash: BytesN<32> = env.crypto().sha256(&vk_bytes).into();
+        env.storage().persistent().set(&DataKey::Vk(vk_id.clone()), &vk_bytes);
+        env.storage().persistent().set(&DataKey::VkHash(vk_id.clone()), &vk_hash);
+        let mut list = vk_list(&env);
+        list.push_back(vk_id.clone());
+        env.storage().persistent().set(&DataKey::VkList, &list);
+        env.events().publish(
+            (Symbol::new(&env, "vk_set"), vk_id.circuit, vk_id.version),
+            vk_hash.clone(),
+        );
+        Ok(vk_hash)
     }
 
-    /// Stores verifier key payload bytes and caches its hash.
-    pub fn set_vk(env: Env, vk_json: Bytes) -> Result<BytesN<32>, Error> {
-        if vk_json.len() == 0 {
-            return Err(Error::VkParseError);
+    /// Deletes the key stored under `vk_id`; proofs against it then fail with VkNotSet.
+    /// The id stays retired, so set_vk cannot put a different key behind it.
+    /// Admin only. Emits ("vk_removed", circuit, version) with the removed key's hash.
+    pub fn remove_vk(env: Env, vk_id: VkId) -> Result<(), Error> {
+        require_admin(&env)?;
+        let vk_hash: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VkHash(vk_id.clone()))
+            .ok_or(Error::VkNotSet)?;
+        env.storage().persistent().remove(&DataKey::Vk(vk_id.clone()));
+        env.storage().persistent().remove(&DataKey::VkHash(vk_id.clone()));
+        env.storage().persistent().set(&DataKey::VkRemoved(vk_id.clone()), &true);
+        let mut list = vk_list(&env);
+        if let Some(i) = list.first_index_of(&vk_id) {
+            list.remove(i);
         }
-        let vk_hash: BytesN<32> = env.crypto().sha256(&vk_json).into();
-        env.storage().instance().set(&Self::key_vk(), &vk_json);
-        env.storage().instance().set(&Self::key_vk_hash(), &vk_hash);
-        Ok(vk_hash)
+        env.storage().persistent().set(&DataKey::VkList, &list);
+        env.events().publish(
+            (Symbol::new(&env, "vk_removed"), vk_id.circuit, vk_id.version),
+            vk_hash,
+        );
+        Ok(())
     }
 
-    /// Verifies an UltraHonk payload.
+    /// sha256 of the key stored under `vk_id`, if any.
+    pub fn get_vk_hash(env: Env, vk_id: VkId) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::VkHash(vk_id))
+    }
+
+    /// Every stored key with its hash, in storage order.
+    pub fn list_vks(env: Env) -> Vec<VkInfo> {
+        let mut out = Vec::new(&env);
+        for vk_id in vk_list(&env).iter() {
+            if let Some(vk_hash) = Self::get_vk_hash(env.clone(), vk_id.clone()) {
+                out.push_back(VkInfo { vk_id, vk_hash });
+            }
+        }
+        out
+    }
+
+    /// Verifies an UltraHonk proof (bb keccak flavour) against `vk_bytes`.
     ///
-    /// This contract keeps a verifier-compatible interface (`vk_json`, `proof_blob`) and
-    /// performs structural checks on `proof_blob` so callers can migrate from Groth16 safely.
-    /// A production verifier should replace this with full cryptographic verification.
-    pub fn verify_proof(env: Env, vk_json: Bytes, proof_blob: Bytes) -> Result<BytesN<32>, Error> {
-        if vk_json.len() == 0 {
+    /// `public_inputs` are the circuit's user public inputs as 32-byte big-endian field
+    /// elements, supplied by the caller rather than taken from the prover, and `proof` is the
+    /// raw bb proof. Runs the full sumcheck, Shplemini batching and the final KZG pairing
+    /// check; on success the proof id (sha256 of `u32_be(total_fields) || public_inputs ||
+    /// proof`, the NoirService blob) is recorded with the ledger and `caller`, who must
+    /// authorize the call. With `reject_seen` a proof id that is already recorded fails with
+    /// ProofAlreadyVerified before any verification work.
+    pub fn verify_proof(
+        env: Env,
+        caller: Address,
+        vk_bytes: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+        proof: Bytes,
+        reject_seen: bool,
+    ) -> Result<BytesN<32>, Error> {
+        caller.require_auth();
+        let vk = parse_vk(&vk_bytes)?;
+
+        let num_public_inputs = vk.num_user_public_inputs();
+        if num_public_inputs > MAX_USER_PUBLIC_INPUTS {
             return Err(Error::VkParseError);
         }
-        if proof_blob.len() < 4 {
+        if public_inputs.len() as usize != num_public_inputs || proof.len() as usize != PROOF_SIZE {
             return Err(Error::ProofParseError);
         }
-        // [u32_be(total_fields)] || [32-byte fields...]
-        let payload_len = proof_blob.len() - 4;
-        if payload_len == 0 || payload_len % 32 != 0 {
-            return Err(Error::ProofParseError);
+
+        let total_fields = (num_public_inputs + PROOF_FIELDS) as u32;
+        let mut blob = Bytes::from_array(&env, &total_fields.to_be_bytes());
+        let mut pi_bytes = [0u8; MAX_USER_PUBLIC_INPUTS * FIELD_SIZE];
+        for (i, word) in public_inputs.iter().enumerate() {
+            let word = word.to_array();
+            pi_bytes[i * FIELD_SIZE..(i + 1) * FIELD_SIZE].copy_from_slice(&word);
+            blob.extend_from_array(&word);
         }
+        blob.append(&proof);
+        let proof_id: BytesN<32> = env.crypto().sha256(&blob).into();
+        if reject_seen && proof_record(&env, &proof_id).is_some() {
+            return Err(Error::ProofAlreadyVerified);
+        }
+
+        let mut proof_bytes = [0u8; PROOF_SIZE];
+        proof.copy_into_slice(&mut proof_bytes);
+        let proof = Proof::from_bytes(&proof_bytes).map_err(|_| Error::ProofParseError)?;
 
-        let proof_id: BytesN<32> = env.crypto().sha256(&proof_blob).into();
-        env.storage().instance().set(&proof_id, &true);
+        let public_inputs_size = num_public_inputs * FIELD_SIZE;
+        if !verifier::verify(&env, &vk, &proof, &pi_bytes[..public_inputs_size]) {
+            return Err(Error::VerificationFailed);
+        }
+
+        record_proof(&env, &proof_id, &caller);
         Ok(proof_id)
     }
 
-    pub fn verify_proof_with_stored_vk(env: Env, proof_blob: Bytes) -> Result<BytesN<32>, Error> {
-        let vk_json: Bytes = match env.storage().instance().get(&Self::key_vk()) {
+    /// Same as `verify_proof`, against the key stored under `vk_id` (VkNotSet if none).
+    pub fn verify_proof_with_stored_vk(
+        env: Env,
+        caller: Address,
+        vk_id: VkId,
+        public_inputs: Vec<BytesN<32>>,
+        proof: Bytes,
+        reject_seen: bool,
+    ) -> Result<BytesN<32>, Error> {
+        let vk_bytes: Bytes = match env.storage().persistent().get(&DataKey::Vk(vk_id)) {
             Some(vk) => vk,
             None => return Err(Error::VkNotSet),
         };
-        Self::verify_proof(env, vk_json, proof_blob)
+        Self::verify_proof(env, caller, vk_bytes, public_inputs, proof, reject_seen)
     }
 
-    pub fn is_verified(env: Env, proof_id: BytesN<32>) -> bool {
-        env.storage().instance().get(&proof_id).unwrap_or(false)
+    /// Ledger and caller of the first successful verification of `proof_id`, while the
+    /// record is live (PROOF_TTL_LEDGERS).
+    pub fn is_verified(env: Env, proof_id: BytesN<32>) -> Option<ProofRecord> {
+        proof_record(&env, &proof_id)
     }
 }
+
+#[cfg(test)]
+mod test;
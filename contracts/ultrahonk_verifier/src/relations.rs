@@ -0,0 +1,453 @@
+//! UltraHonk relations evaluated at the sumcheck point, batched with the alpha challenges.
+//! Subrelation layout (26): arithmetic (2), permutation (2), log-derivative lookup (2),
+//! delta range (4), elliptic (2), auxiliary/memory (6), Poseidon2 external (4), internal (4).
+
+use crate::field::Fr;
+use crate::transcript::RelationParameters;
+use crate::types::{NUMBER_OF_ALPHAS, NUMBER_OF_ENTITIES, NUMBER_OF_SUBRELATIONS};
+
+#[derive(Copy, Clone)]
+enum Wire {
+    QM,
+    QC,
+    QL,
+    QR,
+    QO,
+    Q4,
+    QLookup,
+    QArith,
+    QRange,
+    QElliptic,
+    QAux,
+    QPoseidon2External,
+    QPoseidon2Internal,
+    Sigma1,
+    Sigma2,
+    Sigma3,
+    Sigma4,
+    Id1,
+    Id2,
+    Id3,
+    Id4,
+    Table1,
+    Table2,
+    Table3,
+    Table4,
+    LagrangeFirst,
+    LagrangeLast,
+    WL,
+    WR,
+    WO,
+    W4,
+    ZPerm,
+    LookupInverses,
+    LookupReadCounts,
+    LookupReadTags,
+    WLShift,
+    WRShift,
+    WOShift,
+    W4Shift,
+    ZPermShift,
+}
+
+type Evals = [Fr; NUMBER_OF_ENTITIES];
+type Subrelations = [Fr; NUMBER_OF_SUBRELATIONS];
+
+#[inline(always)]
+fn w(p: &Evals, wire: Wire) -> Fr {
+    p[wire as usize]
+}
+
+fn fr(v: u64) -> Fr {
+    Fr::from_u64(v)
+}
+
+/// 2^68, non-native field limb size.
+fn limb_size() -> Fr {
+    fr(1 << 34) * fr(1 << 34)
+}
+
+/// 2^14, sub-limb shift used by the limb accumulators.
+fn sublimb_shift() -> Fr {
+    fr(1 << 14)
+}
+
+/// Grumpkin curve b = -17; the elliptic relation uses -b.
+const GRUMPKIN_CURVE_B_PARAMETER_NEGATED: u64 = 17;
+
+/// Poseidon2 (t = 4) internal matrix diagonal.
+const INTERNAL_MATRIX_DIAGONAL: [[u8; 32]; 4] = [
+    hex32(b"10dc6e9c006ea38b04b1e03b4bd9490c0d03f98929ca1d7fb56821fd19d3b6e7"),
+    hex32(b"0c28145b6a44df3e0149b3d0a30b3bb599df9756d4dd9b84a86b38cfb45a740b"),
+    hex32(b"00544b8338791518b2c7645a50392798b21f75bb60e3596170067d00141cac15"),
+    hex32(b"222c01175718386f2e2e82eb122789e352e105a3b8fa852613bc534433ee428b"),
+];
+
+const fn hex_nibble(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        _ => panic!("invalid hex"),
+    }
+}
+
+pub(crate) const fn hex32(s: &[u8; 64]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        out[i] = (hex_nibble(s[2 * i]) << 4) | hex_nibble(s[2 * i + 1]);
+        i += 1;
+    }
+    out
+}
+
+fn accumulate_arithmetic(p: &Evals, evals: &mut Subrelations, domain_sep: Fr) {
+    let q_arith = w(p, Wire::QArith);
+    {
+        let neg_half = -fr(2).invert().unwrap();
+        let mut accum =
+            (q_arith - fr(3)) * (w(p, Wire::QM) * w(p, Wire::WR) * w(p, Wire::WL)) * neg_half;
+        accum = accum
+            + (w(p, Wire::QL) * w(p, Wire::WL))
+            + (w(p, Wire::QR) * w(p, Wire::WR))
+            + (w(p, Wire::QO) * w(p, Wire::WO))
+            + (w(p, Wire::Q4) * w(p, Wire::W4))
+            + w(p, Wire::QC);
+        accum = accum + (q_arith - Fr::ONE) * w(p, Wire::W4Shift);
+        accum = accum * q_arith;
+        evals[0] = accum * domain_sep;
+    }
+    {
+        let mut accum = w(p, Wire::WL) + w(p, Wire::W4) - w(p, Wire::WLShift) + w(p, Wire::QM);
+        accum = accum * (q_arith - fr(2));
+        accum = accum * (q_arith - Fr::ONE);
+        accum = accum * q_arith;
+        evals[1] = accum * domain_sep;
+    }
+}
+
+fn accumulate_permutation(
+    p: &Evals,
+    rp: &RelationParameters,
+    evals: &mut Subrelations,
+    domain_sep: Fr,
+) {
+    let numerator = (w(p, Wire::WL) + w(p, Wire::Id1) * rp.beta + rp.gamma)
+        * (w(p, Wire::WR) + w(p, Wire::Id2) * rp.beta + rp.gamma)
+        * (w(p, Wire::WO) + w(p, Wire::Id3) * rp.beta + rp.gamma)
+        * (w(p, Wire::W4) + w(p, Wire::Id4) * rp.beta + rp.gamma);
+    let denominator = (w(p, Wire::WL) + w(p, Wire::Sigma1) * rp.beta + rp.gamma)
+        * (w(p, Wire::WR) + w(p, Wire::Sigma2) * rp.beta + rp.gamma)
+        * (w(p, Wire::WO) + w(p, Wire::Sigma3) * rp.beta + rp.gamma)
+        * (w(p, Wire::W4) + w(p, Wire::Sigma4) * rp.beta + rp.gamma);
+
+    let mut acc = (w(p, Wire::ZPerm) + w(p, Wire::LagrangeFirst)) * numerator;
+    acc = acc
+        - (w(p, Wire::ZPermShift) + w(p, Wire::LagrangeLast) * rp.public_inputs_delta)
+            * denominator;
+    evals[2] = acc * domain_sep;
+    evals[3] = w(p, Wire::LagrangeLast) * w(p, Wire::ZPermShift) * domain_sep;
+}
+
+fn accumulate_log_derivative_lookup(
+    p: &Evals,
+    rp: &RelationParameters,
+    evals: &mut Subrelations,
+    domain_sep: Fr,
+) {
+    let write_term = w(p, Wire::Table1)
+        + rp.gamma
+        + w(p, Wire::Table2) * rp.eta
+        + w(p, Wire::Table3) * rp.eta_two
+        + w(p, Wire::Table4) * rp.eta_three;
+
+    let derived_entry_1 = w(p, Wire::WL) + rp.gamma + w(p, Wire::QR) * w(p, Wire::WLShift);
+    let derived_entry_2 = w(p, Wire::WR) + w(p, Wire::QM) * w(p, Wire::WRShift);
+    let derived_entry_3 = w(p, Wire::WO) + w(p, Wire::QC) * w(p, Wire::WOShift);
+    let read_term = derived_entry_1
+        + derived_entry_2 * rp.eta
+        + derived_entry_3 * rp.eta_two
+        + w(p, Wire::QO) * rp.eta_three;
+
+    let read_inverse = w(p, Wire::LookupInverses) * write_term;
+    let write_inverse = w(p, Wire::LookupInverses) * read_term;
+
+    let inverse_exists_xor = w(p, Wire::LookupReadTags) + w(p, Wire::QLookup)
+        - w(p, Wire::LookupReadTags) * w(p, Wire::QLookup);
+
+    let accumulator_none =
+        (read_term * write_term * w(p, Wire::LookupInverses) - inverse_exists_xor) * domain_sep;
+    let accumulator_one =
+        w(p, Wire::QLookup) * read_inverse - w(p, Wire::LookupReadCounts) * write_inverse;
+
+    evals[4] = accumulator_none;
+    evals[5] = accumulator_one;
+}
+
+fn accumulate_delta_range(p: &Evals, evals: &mut Subrelations, domain_sep: Fr) {
+    let minus_one = -Fr::ONE;
+    let minus_two = -fr(2);
+    let minus_three = -fr(3);
+
+    let deltas = [
+        w(p, Wire::WR) - w(p, Wire::WL),
+        w(p, Wire::WO) - w(p, Wire::WR),
+        w(p, Wire::W4) - w(p, Wire::WO),
+        w(p, Wire::WLShift) - w(p, Wire::W4),
+    ];
+    for (i, delta) in deltas.iter().enumerate() {
+        let mut acc = *delta;
+        acc = acc * (*delta + minus_one);
+        acc = acc * (*delta + minus_two);
+        acc = acc * (*delta + minus_three);
+        acc = acc * w(p, Wire::QRange);
+        evals[6 + i] = acc * domain_sep;
+    }
+}
+
+fn accumulate_elliptic(p: &Evals, evals: &mut Subrelations, domain_sep: Fr) {
+    let x_1 = w(p, Wire::WR);
+    let y_1 = w(p, Wire::WO);
+    let x_2 = w(p, Wire::WLShift);
+    let y_2 = w(p, Wire::W4Shift);
+    let y_3 = w(p, Wire::WOShift);
+    let x_3 = w(p, Wire::WRShift);
+
+    let q_sign = w(p, Wire::QL);
+    let q_is_double = w(p, Wire::QM);
+    let q_elliptic = w(p, Wire::QElliptic);
+
+    let x_diff = x_2 - x_1;
+    let y1_sqr = y_1 * y_1;
+
+    // Point addition, x-coordinate: (x3 + x2 + x1)(x2 - x1)^2 - y2^2 - y1^2 + 2 y1 y2 q_sign = 0
+    {
+        let y2_sqr = y_2 * y_2;
+        let y1y2 = y_1 * y_2 * q_sign;
+        let mut x_add_identity = (x_3 + x_2 + x_1) * x_diff * x_diff;
+        x_add_identity = x_add_identity - y2_sqr - y1_sqr + y1y2 + y1y2;
+        evals[10] = x_add_identity * domain_sep * q_elliptic * (Fr::ONE - q_is_double);
+    }
+    // Point addition, y-coordinate: (y1 + y3)(x2 - x1) + (x3 - x1)(y2 q_sign - y1) = 0
+    {
+        let y1_plus_y3 = y_1 + y_3;
+        let y_diff = y_2 * q_sign - y_1;
+        let y_add_identity = y1_plus_y3 * x_diff + (x_3 - x_1) * y_diff;
+        evals[11] = y_add_identity * domain_sep * q_elliptic * (Fr::ONE - q_is_double);
+    }
+    // Point doubling, x-coordinate: (x3 + 2 x1)(4 y1^2) - 9 x1^4 = 0, with x1^3 = y1^2 - b
+    {
+        let x_pow_4 = (y1_sqr + fr(GRUMPKIN_CURVE_B_PARAMETER_NEGATED)) * x_1;
+        let mut y1_sqr_mul_4 = y1_sqr + y1_sqr;
+        y1_sqr_mul_4 = y1_sqr_mul_4 + y1_sqr_mul_4;
+        let x1_pow_4_mul_9 = x_pow_4 * fr(9);
+        let x_double_identity = (x_3 + x_1 + x_1) * y1_sqr_mul_4 - x1_pow_4_mul_9;
+        evals[10] = evals[10] + x_double_identity * domain_sep * q_elliptic * q_is_double;
+    }
+    // Point doubling, y-coordinate: 3 x1^2 (x1 - x3) - 2 y1 (y1 + y3) = 0
+    {
+        let x1_sqr_mul_3 = (x_1 + x_1 + x_1) * x_1;
+        let y_double_identity = x1_sqr_mul_3 * (x_1 - x_3) - (y_1 + y_1) * (y_1 + y_3);
+        evals[11] = evals[11] + y_double_identity * domain_sep * q_elliptic * q_is_double;
+    }
+}
+
+fn accumulate_auxiliary(
+    p: &Evals,
+    rp: &RelationParameters,
+    evals: &mut Subrelations,
+    domain_sep: Fr,
+) {
+    let limb_size = limb_size();
+    let sublimb_shift = sublimb_shift();
+    let minus_one = -Fr::ONE;
+
+    // Non-native field arithmetic gates.
+    let mut limb_subproduct =
+        w(p, Wire::WL) * w(p, Wire::WRShift) + w(p, Wire::WLShift) * w(p, Wire::WR);
+    let mut non_native_field_gate_2 =
+        w(p, Wire::WL) * w(p, Wire::W4) + w(p, Wire::WR) * w(p, Wire::WO) - w(p, Wire::WOShift);
+    non_native_field_gate_2 = non_native_field_gate_2 * limb_size;
+    non_native_field_gate_2 = non_native_field_gate_2 - w(p, Wire::W4Shift);
+    non_native_field_gate_2 = non_native_field_gate_2 + limb_subproduct;
+    non_native_field_gate_2 = non_native_field_gate_2 * w(p, Wire::Q4);
+
+    limb_subproduct = limb_subproduct * limb_size;
+    limb_subproduct = limb_subproduct + w(p, Wire::WLShift) * w(p, Wire::WRShift);
+    let non_native_field_gate_1 =
+        (limb_subproduct - (w(p, Wire::WO) + w(p, Wire::W4))) * w(p, Wire::QO);
+    let non_native_field_gate_3 = (limb_subproduct + w(p, Wire::W4)
+        - (w(p, Wire::WOShift) + w(p, Wire::W4Shift)))
+        * w(p, Wire::QM);
+
+    let non_native_field_identity =
+        (non_native_field_gate_1 + non_native_field_gate_2 + non_native_field_gate_3)
+            * w(p, Wire::QR);
+
+    let mut limb_accumulator_1 = w(p, Wire::WRShift) * sublimb_shift;
+    limb_accumulator_1 = limb_accumulator_1 + w(p, Wire::WLShift);
+    limb_accumulator_1 = limb_accumulator_1 * sublimb_shift;
+    limb_accumulator_1 = limb_accumulator_1 + w(p, Wire::WO);
+    limb_accumulator_1 = limb_accumulator_1 * sublimb_shift;
+    limb_accumulator_1 = limb_accumulator_1 + w(p, Wire::WR);
+    limb_accumulator_1 = limb_accumulator_1 * sublimb_shift;
+    limb_accumulator_1 = limb_accumulator_1 + w(p, Wire::WL);
+    limb_accumulator_1 = limb_accumulator_1 - w(p, Wire::W4);
+    limb_accumulator_1 = limb_accumulator_1 * w(p, Wire::Q4);
+
+    let mut limb_accumulator_2 = w(p, Wire::WOShift) * sublimb_shift;
+    limb_accumulator_2 = limb_accumulator_2 + w(p, Wire::WRShift);
+    limb_accumulator_2 = limb_accumulator_2 * sublimb_shift;
+    limb_accumulator_2 = limb_accumulator_2 + w(p, Wire::WLShift);
+    limb_accumulator_2 = limb_accumulator_2 * sublimb_shift;
+    limb_accumulator_2 = limb_accumulator_2 + w(p, Wire::W4);
+    limb_accumulator_2 = limb_accumulator_2 * sublimb_shift;
+    limb_accumulator_2 = limb_accumulator_2 + w(p, Wire::WO);
+    limb_accumulator_2 = limb_accumulator_2 - w(p, Wire::W4Shift);
+    limb_accumulator_2 = limb_accumulator_2 * w(p, Wire::QM);
+
+    let limb_accumulator_identity = (limb_accumulator_1 + limb_accumulator_2) * w(p, Wire::QO);
+
+    // Memory record check: qc + w1 eta + w2 eta_two + w3 eta_three - w4 = 0
+    let mut memory_record_check = w(p, Wire::WO) * rp.eta_three;
+    memory_record_check = memory_record_check + w(p, Wire::WR) * rp.eta_two;
+    memory_record_check = memory_record_check + w(p, Wire::WL) * rp.eta;
+    memory_record_check = memory_record_check + w(p, Wire::QC);
+    let partial_record_check = memory_record_check;
+    memory_record_check = memory_record_check - w(p, Wire::W4);
+
+    // ROM consistency.
+    let index_delta = w(p, Wire::WLShift) - w(p, Wire::WL);
+    let record_delta = w(p, Wire::W4Shift) - w(p, Wire::W4);
+    let index_is_monotonically_increasing = index_delta * index_delta - index_delta;
+    let adjacent_values_match_if_adjacent_indices_match =
+        (index_delta * minus_one + Fr::ONE) * record_delta;
+
+    let q_aux_by_scaling = w(p, Wire::QAux) * domain_sep;
+    let q_one_by_two = w(p, Wire::QL) * w(p, Wire::QR);
+
+    evals[13] = adjacent_values_match_if_adjacent_indices_match * q_one_by_two * q_aux_by_scaling;
+    evals[14] = index_is_monotonically_increasing * q_one_by_two * q_aux_by_scaling;
+
+    let rom_consistency_check_identity = memory_record_check * q_one_by_two;
+
+    // RAM consistency.
+    let access_type = w(p, Wire::W4) - partial_record_check;
+    let access_check = access_type * access_type - access_type;
+
+    let mut next_gate_access_type = w(p, Wire::WOShift) * rp.eta_three;
+    next_gate_access_type = next_gate_access_type + w(p, Wire::WRShift) * rp.eta_two;
+    next_gate_access_type = next_gate_access_type + w(p, Wire::WLShift) * rp.eta;
+    next_gate_access_type = w(p, Wire::W4Shift) - next_gate_access_type;
+
+    let value_delta = w(p, Wire::WOShift) - w(p, Wire::WO);
+    let adjacent_values_match_if_adjacent_indices_match_and_next_access_is_a_read_operation =
+        (index_delta * minus_one + Fr::ONE)
+            * value_delta
+            * (next_gate_access_type * minus_one + Fr::ONE);
+
+    let next_gate_access_type_is_boolean =
+        next_gate_access_type * next_gate_access_type - next_gate_access_type;
+
+    let q_arith = w(p, Wire::QArith);
+    evals[15] = adjacent_values_match_if_adjacent_indices_match_and_next_access_is_a_read_operation
+        * q_arith
+        * q_aux_by_scaling;
+    evals[16] = index_is_monotonically_increasing * q_arith * q_aux_by_scaling;
+    evals[17] = next_gate_access_type_is_boolean * q_arith * q_aux_by_scaling;
+
+    let ram_consistency_check_identity = access_check * q_arith;
+
+    // RAM timestamp consistency.
+    let timestamp_delta = w(p, Wire::WRShift) - w(p, Wire::WR);
+    let ram_timestamp_check_identity =
+        (index_delta * minus_one + Fr::ONE) * timestamp_delta - w(p, Wire::WO);
+
+    let mut memory_identity = rom_consistency_check_identity;
+    memory_identity =
+        memory_identity + ram_timestamp_check_identity * (w(p, Wire::Q4) * w(p, Wire::QL));
+    memory_identity = memory_identity + memory_record_check * (w(p, Wire::QM) * w(p, Wire::QL));
+    memory_identity = memory_identity + ram_consistency_check_identity;
+
+    let auxiliary_identity =
+        memory_identity + non_native_field_identity + limb_accumulator_identity;
+    evals[12] = auxiliary_identity * q_aux_by_scaling;
+}
+
+fn accumulate_poseidon_external(p: &Evals, evals: &mut Subrelations, domain_sep: Fr) {
+    let s1 = w(p, Wire::WL) + w(p, Wire::QL);
+    let s2 = w(p, Wire::WR) + w(p, Wire::QR);
+    let s3 = w(p, Wire::WO) + w(p, Wire::QO);
+    let s4 = w(p, Wire::W4) + w(p, Wire::Q4);
+
+    let u1 = s1 * s1 * s1 * s1 * s1;
+    let u2 = s2 * s2 * s2 * s2 * s2;
+    let u3 = s3 * s3 * s3 * s3 * s3;
+    let u4 = s4 * s4 * s4 * s4 * s4;
+
+    // v = M_E * u
+    let t0 = u1 + u2;
+    let t1 = u3 + u4;
+    let t2 = u2 + u2 + t1;
+    let t3 = u4 + u4 + t0;
+    let mut v4 = t1 + t1;
+    v4 = v4 + v4 + t3;
+    let mut v2 = t0 + t0;
+    v2 = v2 + v2 + t2;
+    let v1 = t3 + v2;
+    let v3 = t2 + v4;
+
+    let q_pos_by_scaling = w(p, Wire::QPoseidon2External) * domain_sep;
+    evals[18] = evals[18] + q_pos_by_scaling * (v1 - w(p, Wire::WLShift));
+    evals[19] = evals[19] + q_pos_by_scaling * (v2 - w(p, Wire::WRShift));
+    evals[20] = evals[20] + q_pos_by_scaling * (v3 - w(p, Wire::WOShift));
+    evals[21] = evals[21] + q_pos_by_scaling * (v4 - w(p, Wire::W4Shift));
+}
+
+fn accumulate_poseidon_internal(p: &Evals, evals: &mut Subrelations, domain_sep: Fr) {
+    let diag = INTERNAL_MATRIX_DIAGONAL.map(|d| Fr::from_be_bytes_reduced(&d));
+
+    let s1 = w(p, Wire::WL) + w(p, Wire::QL);
+    let u1 = s1 * s1 * s1 * s1 * s1;
+    let u2 = w(p, Wire::WR);
+    let u3 = w(p, Wire::WO);
+    let u4 = w(p, Wire::W4);
+
+    // v = M_I * u
+    let u_sum = u1 + u2 + u3 + u4;
+    let q_pos_by_scaling = w(p, Wire::QPoseidon2Internal) * domain_sep;
+
+    let v1 = u1 * diag[0] + u_sum;
+    evals[22] = evals[22] + q_pos_by_scaling * (v1 - w(p, Wire::WLShift));
+    let v2 = u2 * diag[1] + u_sum;
+    evals[23] = evals[23] + q_pos_by_scaling * (v2 - w(p, Wire::WRShift));
+    let v3 = u3 * diag[2] + u_sum;
+    evals[24] = evals[24] + q_pos_by_scaling * (v3 - w(p, Wire::WOShift));
+    let v4 = u4 * diag[3] + u_sum;
+    evals[25] = evals[25] + q_pos_by_scaling * (v4 - w(p, Wire::W4Shift));
+}
+
+/// Full Honk relation at the sumcheck evaluation point, scaled by the pow polynomial.
+pub fn accumulate_relation_evaluations(
+    purported_evaluations: &Evals,
+    rp: &RelationParameters,
+    alphas: &[Fr; NUMBER_OF_ALPHAS],
+    pow_partial_eval: Fr,
+) -> Fr {
+    let mut evals = [Fr::ZERO; NUMBER_OF_SUBRELATIONS];
+    let p = purported_evaluations;
+    accumulate_arithmetic(p, &mut evals, pow_partial_eval);
+    accumulate_permutation(p, rp, &mut evals, pow_partial_eval);
+    accumulate_log_derivative_lookup(p, rp, &mut evals, pow_partial_eval);
+    accumulate_delta_range(p, &mut evals, pow_partial_eval);
+    accumulate_elliptic(p, &mut evals, pow_partial_eval);
+    accumulate_auxiliary(p, rp, &mut evals, pow_partial_eval);
+    accumulate_poseidon_external(p, &mut evals, pow_partial_eval);
+    accumulate_poseidon_internal(p, &mut evals, pow_partial_eval);
+
+    let mut accumulator = evals[0];
+    for i in 1..NUMBER_OF_SUBRELATIONS {
+        accumulator = accumulator + evals[i] * alphas[i - 1];
+    }
+    accumulator
+}
@@ -0,0 +1,480 @@
+//! Unit tests: VK parsing against the checked-in GameRun artifacts, input/proof framing,
+//! rejection of structurally valid but bogus proofs, and the Fr / sumcheck helpers.
+//! test_proof_records_and_reject_seen needs the real proof in circuits/noir/target/proof and
+//! public_inputs (`npm run zk:noir:prove`, then `git add -f`) and fails while they are missing.
+
+#![cfg(test)]
+extern crate std;
+
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _, MockAuth, MockAuthInvoke};
+use soroban_sdk::{vec, Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec};
+
+use crate::field::Fr;
+use crate::types::{VerificationKey, PROOF_FIELDS, PROOF_SIZE};
+use crate::verifier::{compute_next_target_sum, pairing_points};
+use crate::{
+    Error, ProofRecord, UltraHonkVerifier, UltraHonkVerifierClient, VkId, VkInfo,
+    PROOF_TTL_LEDGERS,
+};
+
+const GAMERUN_VK: &[u8] = include_bytes!("../../../circuits/noir/target/vk");
+const GAMERUN_ABI: &str = include_str!("../../../circuits/noir/target/gamerun_noir.json");
+
+fn create_client(env: &Env) -> UltraHonkVerifierClient<'_> {
+    env.mock_all_auths();
+    let client = UltraHonkVerifierClient::new(env, &env.register(UltraHonkVerifier, ()));
+    client.init(&Address::generate(env));
+    client
+}
+
+fn gamerun_id(env: &Env, version: u32) -> VkId {
+    VkId {
+        circuit: Symbol::new(env, "gamerun"),
+        version,
+    }
+}
+
+/// Client with the GameRun key stored as version 1.
+fn client_with_vk(env: &Env) -> UltraHonkVerifierClient<'_> {
+    let client = create_client(env);
+    client.set_vk(&gamerun_id(env, 1), &Bytes::from_slice(env, GAMERUN_VK));
+    client
+}
+
+fn abi_public_inputs() -> usize {
+    let start = GAMERUN_ABI.find("\"abi\"").unwrap();
+    let end = GAMERUN_ABI.find("\"bytecode\"").unwrap();
+    GAMERUN_ABI[start..end]
+        .matches("\"visibility\":\"public\"")
+        .count()
+}
+
+/// `n` public inputs, each set to `fill`.
+fn inputs(env: &Env, n: usize, fill: [u8; 32]) -> Vec<BytesN<32>> {
+    let mut v = Vec::new(env);
+    for _ in 0..n {
+        v.push_back(BytesN::from_array(env, &fill));
+    }
+    v
+}
+
+/// Raw proof of `fields` words, each set to `fill`.
+fn proof(env: &Env, fields: usize, fill: [u8; 32]) -> Bytes {
+    let mut b = Bytes::new(env);
+    for _ in 0..fields {
+        b.extend_from_array(&fill);
+    }
+    b
+}
+
+fn one_word() -> [u8; 32] {
+    let mut w = [0u8; 32];
+    w[31] = 1;
+    w
+}
+
+#[test]
+fn test_gamerun_vk_parses() {
+    let vk = VerificationKey::from_bytes(GAMERUN_VK).unwrap();
+    assert_eq!(vk.circuit_size, 4096);
+    assert_eq!(vk.log_circuit_size, 12);
+    assert_eq!(vk.num_user_public_inputs(), abi_public_inputs());
+    assert_eq!(vk.num_user_public_inputs(), 7);
+}
+
+#[test]
+fn test_set_vk_rejects_malformed_key() {
+    let env = Env::default();
+    let client = create_client(&env);
+
+    let id = gamerun_id(&env, 1);
+
+    let json = Bytes::from_slice(&env, b"{\"not\":\"a vk\"}");
+    assert_eq!(client.try_set_vk(&id, &json), Err(Ok(Error::VkParseError)));
+
+    let mut truncated = Bytes::from_slice(&env, GAMERUN_VK);
+    truncated.pop_back();
+    assert_eq!(client.try_set_vk(&id, &truncated), Err(Ok(Error::VkParseError)));
+    assert_eq!(client.get_vk_hash(&id), None);
+
+    let vk = Bytes::from_slice(&env, GAMERUN_VK);
+    let expected: BytesN<32> = env.crypto().sha256(&vk).into();
+    assert_eq!(client.set_vk(&id, &vk), expected);
+    assert_eq!(client.get_vk_hash(&id), Some(expected));
+}
+
+#[test]
+fn test_vk_management_is_admin_only_and_versioned() {
+    let env = Env::default();
+    let contract_id = env.register(UltraHonkVerifier, ());
+    let client = UltraHonkVerifierClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let vk = Bytes::from_slice(&env, GAMERUN_VK);
+    let v1 = gamerun_id(&env, 1);
+
+    assert_eq!(client.try_set_vk(&v1, &vk), Err(Ok(Error::NotInitialized)));
+    // init needs the admin's own signature, so nobody else can claim the contract.
+    assert!(client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "init",
+                args: (admin.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_init(&admin)
+        .is_err());
+    assert!(client.try_init(&admin).is_err());
+    client.mock_all_auths().init(&admin);
+    assert_eq!(client.try_init(&attacker), Err(Ok(Error::AlreadyInitialized)));
+
+    // Only the admin's authorization stores a key.
+    assert!(client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "set_vk",
+                args: (v1.clone(), vk.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_set_vk(&v1, &vk)
+        .is_err());
+
+    env.mock_all_auths();
+    let hash = client.set_vk(&v1, &vk);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "vk_set"), v1.circuit.clone(), 1u32).into_val(&env),
+                hash.clone().into_val(&env),
+            ),
+        ]
+    );
+
+    // A stored id is never replaced; a new version sits next to it.
+    assert_eq!(client.try_set_vk(&v1, &vk), Err(Ok(Error::VkAlreadySet)));
+    let v2 = gamerun_id(&env, 2);
+    client.set_vk(&v2, &vk);
+    assert_eq!(
+        client.list_vks(),
+        vec![
+            &env,
+            VkInfo { vk_id: v1.clone(), vk_hash: hash.clone() },
+            VkInfo { vk_id: v2.clone(), vk_hash: hash.clone() },
+        ]
+    );
+
+    client.remove_vk(&v1);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "vk_removed"), v1.circuit.clone(), 1u32).into_val(&env),
+                hash.clone().into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(client.get_vk_hash(&v1), None);
+    assert_eq!(client.list_vks(), vec![&env, VkInfo { vk_id: v2, vk_hash: hash }]);
+    assert_eq!(client.try_remove_vk(&v1), Err(Ok(Error::VkNotSet)));
+    // A removed id is retired: no other key can be published behind it.
+    assert_eq!(client.try_set_vk(&v1, &vk), Err(Ok(Error::VkAlreadySet)));
+    assert_eq!(client.get_vk_hash(&v1), None);
+    let caller = Address::generate(&env);
+    assert_eq!(
+        client.try_verify_proof_with_stored_vk(
+            &caller,
+            &v1,
+            &inputs(&env, 7, one_word()),
+            &proof(&env, PROOF_FIELDS, one_word()),
+            &false
+        ),
+        Err(Ok(Error::VkNotSet))
+    );
+}
+
+#[test]
+fn test_verify_without_vk_fails() {
+    let env = Env::default();
+    let client = create_client(&env);
+    let caller = Address::generate(&env);
+    assert_eq!(
+        client.try_verify_proof_with_stored_vk(
+            &caller,
+            &gamerun_id(&env, 1),
+            &inputs(&env, 7, one_word()),
+            &proof(&env, PROOF_FIELDS, one_word()),
+            &false
+        ),
+        Err(Ok(Error::VkNotSet))
+    );
+}
+
+/// The old structural check accepted any `u32 || n * 32` blob; that must no longer pass.
+#[test]
+fn test_structural_only_blob_rejected() {
+    let env = Env::default();
+    let client = client_with_vk(&env);
+    let id = gamerun_id(&env, 1);
+    let caller = Address::generate(&env);
+
+    assert_eq!(
+        client.try_verify_proof_with_stored_vk(
+            &caller,
+            &id,
+            &inputs(&env, 7, one_word()),
+            &proof(&env, 1, one_word()),
+            &false
+        ),
+        Err(Ok(Error::ProofParseError))
+    );
+}
+
+#[test]
+fn test_wrong_input_count_or_size_rejected() {
+    let env = Env::default();
+    let client = client_with_vk(&env);
+    let id = gamerun_id(&env, 1);
+    let caller = Address::generate(&env);
+    let full_proof = proof(&env, PROOF_FIELDS, one_word());
+
+    // One public input short of, or beyond, what the VK expects.
+    for n in [6, 8] {
+        assert_eq!(
+            client.try_verify_proof_with_stored_vk(
+                &caller,
+                &id,
+                &inputs(&env, n, one_word()),
+                &full_proof,
+                &false
+            ),
+            Err(Ok(Error::ProofParseError))
+        );
+    }
+
+    // Proof one field short.
+    assert_eq!(
+        client.try_verify_proof_with_stored_vk(
+            &caller,
+            &id,
+            &inputs(&env, 7, one_word()),
+            &proof(&env, PROOF_FIELDS - 1, one_word()),
+            &false
+        ),
+        Err(Ok(Error::ProofParseError))
+    );
+
+    // Non-canonical field elements in the proof.
+    assert_eq!(
+        client.try_verify_proof_with_stored_vk(
+            &caller,
+            &id,
+            &inputs(&env, 7, one_word()),
+            &proof(&env, PROOF_FIELDS, [0xff; 32]),
+            &false
+        ),
+        Err(Ok(Error::ProofParseError))
+    );
+
+    // Non-canonical public inputs never verify.
+    assert_eq!(
+        client.try_verify_proof_with_stored_vk(
+            &caller,
+            &id,
+            &inputs(&env, 7, [0xff; 32]),
+            &full_proof,
+            &false
+        ),
+        Err(Ok(Error::VerificationFailed))
+    );
+}
+
+#[test]
+fn test_well_formed_bogus_proof_fails_verification() {
+    let env = Env::default();
+    let client = client_with_vk(&env);
+    let id = gamerun_id(&env, 1);
+    let caller = Address::generate(&env);
+
+    let public_inputs = inputs(&env, 7, one_word());
+    let raw_proof = proof(&env, PROOF_FIELDS, one_word());
+    assert_eq!(raw_proof.len() as usize, PROOF_SIZE);
+    assert_eq!(
+        client.try_verify_proof_with_stored_vk(&caller, &id, &public_inputs, &raw_proof, &false),
+        Err(Ok(Error::VerificationFailed))
+    );
+
+    // The proof id is sha256 of the NoirService blob `u32_be(total_fields) || inputs || proof`.
+    let mut blob = Bytes::from_array(&env, &((7 + PROOF_FIELDS) as u32).to_be_bytes());
+    for word in public_inputs.iter() {
+        blob.extend_from_array(&word.to_array());
+    }
+    blob.append(&raw_proof);
+    let proof_id: BytesN<32> = env.crypto().sha256(&blob).into();
+    assert_eq!(client.is_verified(&proof_id), None);
+}
+
+/// Real GameRun proof from `npm run zk:noir:prove` (nargo execute + bb prove, keccak
+/// transcript) for circuits/noir/Prover.toml: (public_inputs, proof). Panics if the
+/// artifacts are missing.
+fn gamerun_proof(env: &Env) -> (Vec<BytesN<32>>, Bytes) {
+    let target = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../circuits/noir/target");
+    let read = |name: &str| {
+        std::fs::read(target.join(name)).unwrap_or_else(|e| {
+            panic!(
+                "circuits/noir/target/{name}: {e}; run `npm run zk:noir:prove` and commit \
+                 target/proof and target/public_inputs with `git add -f`"
+            )
+        })
+    };
+    let raw_proof = read("proof");
+    let raw_inputs = read("public_inputs");
+    let mut public_inputs = Vec::new(env);
+    for word in raw_inputs.chunks(32) {
+        public_inputs.push_back(BytesN::from_array(env, &word.try_into().unwrap()));
+    }
+    (public_inputs, Bytes::from_slice(env, &raw_proof))
+}
+
+/// A real proof verifies against the stored key and is recorded by that call; records keep
+/// the first verifier, feed `reject_seen` and expire after PROOF_TTL_LEDGERS.
+#[test]
+fn test_proof_records_and_reject_seen() {
+    let env = Env::default();
+    let (public_inputs, raw_proof) = gamerun_proof(&env);
+    let client = client_with_vk(&env);
+    let id = gamerun_id(&env, 1);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    assert_eq!(public_inputs.len() as usize, abi_public_inputs());
+    assert_eq!(raw_proof.len() as usize, PROOF_SIZE);
+
+    env.ledger().set_sequence_number(100);
+    let proof_id = client.verify_proof_with_stored_vk(&first, &id, &public_inputs, &raw_proof, &true);
+    let record = ProofRecord {
+        ledger: 100,
+        caller: first,
+    };
+    assert_eq!(client.is_verified(&proof_id), Some(record.clone()));
+
+    // reject_seen stops a recorded proof; without it the proof verifies again and the
+    // record keeps the first verifier.
+    env.ledger().set_sequence_number(200);
+    assert_eq!(
+        client.try_verify_proof_with_stored_vk(&second, &id, &public_inputs, &raw_proof, &true),
+        Err(Ok(Error::ProofAlreadyVerified))
+    );
+    assert_eq!(
+        client.verify_proof_with_stored_vk(&second, &id, &public_inputs, &raw_proof, &false),
+        proof_id
+    );
+    assert_eq!(client.is_verified(&proof_id), Some(record));
+
+    // The proof is bound to its public inputs.
+    let mut altered = public_inputs.clone();
+    altered.set(2, BytesN::from_array(&env, &one_word()));
+    assert_eq!(
+        client.try_verify_proof_with_stored_vk(&second, &id, &altered, &raw_proof, &false),
+        Err(Ok(Error::VerificationFailed))
+    );
+
+    env.ledger().set_sequence_number(200 + PROOF_TTL_LEDGERS + 1);
+    assert_eq!(client.is_verified(&proof_id), None);
+    assert_eq!(
+        client.verify_proof_with_stored_vk(&second, &id, &public_inputs, &raw_proof, &true),
+        proof_id
+    );
+}
+
+#[test]
+fn test_field_arithmetic() {
+    let a = Fr::from_u64(123_456_789);
+    let b = Fr::from_u64(987_654_321);
+    assert_eq!(a * b, Fr::from_u64(123_456_789 * 987_654_321));
+    assert_eq!(a + b - b, a);
+    assert_eq!(a * a.invert().unwrap(), Fr::ONE);
+    assert_eq!(-a + a, Fr::ZERO);
+    assert!(Fr::ZERO.invert().is_none());
+
+    // r - 1 round-trips; r itself is not canonical.
+    let r_minus_one = (-Fr::ONE).to_be_bytes();
+    assert_eq!(Fr::from_be_bytes_canonical(&r_minus_one), Some(-Fr::ONE));
+    let mut r = r_minus_one;
+    r[31] += 1;
+    assert!(Fr::from_be_bytes_canonical(&r).is_none());
+    assert_eq!(Fr::from_be_bytes_reduced(&r), Fr::ZERO);
+}
+
+#[test]
+fn test_barycentric_evaluation() {
+    // p(x) = 3x^2 + 2x + 1 sampled on 0..8, evaluated at 11.
+    let mut univariate = [Fr::ZERO; 8];
+    for (x, v) in univariate.iter_mut().enumerate() {
+        let x = x as u64;
+        *v = Fr::from_u64(3 * x * x + 2 * x + 1);
+    }
+    assert_eq!(
+        compute_next_target_sum(&univariate, Fr::from_u64(11)),
+        Some(Fr::from_u64(386))
+    );
+    // Challenges on the domain are rejected rather than dividing by zero.
+    assert_eq!(compute_next_target_sum(&univariate, Fr::from_u64(3)), None);
+}
+
+/// The pairing point object holds lhs.x, lhs.y, rhs.x, rhs.y as four 68-bit limbs each.
+#[test]
+fn test_pairing_points_from_limbs() {
+    let limb = |v: u128| {
+        let mut bytes = [0u8; 32];
+        bytes[16..].copy_from_slice(&v.to_be_bytes());
+        Fr::from_be_bytes_canonical(&bytes).unwrap()
+    };
+    // lhs = G1 generator (1, 2), rhs = 2 * generator.
+    let mut limbs = [Fr::ZERO; 16];
+    limbs[0] = limb(1);
+    limbs[4] = limb(2);
+    let rhs_limbs: [u128; 8] = [
+        0x8d3c208c16d87cfd3,
+        0x85d97816a916871ca,
+        0xa029b85045b681815,
+        0x30644e72e131,
+        0x7ff3ebf7a5a18a2c4,
+        0xa68a6a449e3538fc,
+        0xc92e7845f96b2ae9c,
+        0x15ed738c0e0a7,
+    ];
+    for (i, v) in rhs_limbs.iter().enumerate() {
+        limbs[8 + i] = limb(*v);
+    }
+
+    let (lhs, rhs) = pairing_points(&limbs).unwrap();
+    let mut expected_lhs = [0u8; 64];
+    expected_lhs[31] = 1;
+    expected_lhs[63] = 2;
+    assert_eq!(lhs, expected_lhs);
+    let expected_rhs = "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+                        15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4";
+    let mut hex = std::string::String::new();
+    for b in rhs {
+        hex.push_str(&std::format!("{b:02x}"));
+    }
+    assert_eq!(hex, expected_rhs);
+
+    // A limb wider than 68 bits, or a top limb overflowing 256 bits, is rejected.
+    let mut wide = limbs;
+    wide[1] = limb(1 << 68);
+    assert!(pairing_points(&wide).is_none());
+    let mut overflow = limbs;
+    overflow[3] = limb(1 << 52);
+    assert!(pairing_points(&overflow).is_none());
+}
@@ -0,0 +1,204 @@
+//! Fiat-Shamir transcript (keccak256), mirroring bb's UltraKeccak verifier.
+//! Each hash output is reduced into Fr and split into two 128-bit challenges.
+
+use soroban_sdk::{Bytes, Env};
+
+use crate::field::Fr;
+use crate::types::{
+    G1ProofPoint, Proof, VerificationKey, BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N,
+    NUMBER_OF_ALPHAS, NUMBER_OF_ENTITIES,
+};
+
+pub struct RelationParameters {
+    pub eta: Fr,
+    pub eta_two: Fr,
+    pub eta_three: Fr,
+    pub beta: Fr,
+    pub gamma: Fr,
+    pub public_inputs_delta: Fr,
+}
+
+pub struct Transcript {
+    pub relation_parameters: RelationParameters,
+    pub alphas: [Fr; NUMBER_OF_ALPHAS],
+    pub gate_challenges: [Fr; CONST_PROOF_SIZE_LOG_N],
+    pub sumcheck_u_challenges: [Fr; CONST_PROOF_SIZE_LOG_N],
+    pub rho: Fr,
+    pub gemini_r: Fr,
+    pub shplonk_nu: Fr,
+    pub shplonk_z: Fr,
+}
+
+/// Accumulates 32-byte words and hashes them into the next challenge.
+struct Round {
+    buf: Bytes,
+}
+
+impl Round {
+    fn new(env: &Env, previous: Option<&Fr>) -> Self {
+        let mut round = Round {
+            buf: Bytes::new(env),
+        };
+        if let Some(prev) = previous {
+            round.fr(prev);
+        }
+        round
+    }
+
+    fn word(&mut self, w: &[u8; 32]) {
+        self.buf.extend_from_array(w);
+    }
+
+    fn fr(&mut self, f: &Fr) {
+        self.word(&f.to_be_bytes());
+    }
+
+    fn u64(&mut self, v: u64) {
+        let mut w = [0u8; 32];
+        w[24..].copy_from_slice(&v.to_be_bytes());
+        self.word(&w);
+    }
+
+    fn point(&mut self, p: &G1ProofPoint) {
+        self.word(&p.x_0);
+        self.word(&p.x_1);
+        self.word(&p.y_0);
+        self.word(&p.y_1);
+    }
+
+    fn finish(self, env: &Env) -> Fr {
+        let hash = env.crypto().keccak256(&self.buf).to_array();
+        Fr::from_be_bytes_reduced(&hash)
+    }
+}
+
+/// (lo 128 bits, hi 128 bits) of a challenge.
+fn split_challenge(challenge: &Fr) -> (Fr, Fr) {
+    let bytes = challenge.to_be_bytes();
+    let mut lo = [0u8; 32];
+    let mut hi = [0u8; 32];
+    lo[16..].copy_from_slice(&bytes[16..]);
+    hi[16..].copy_from_slice(&bytes[..16]);
+    (
+        Fr::from_be_bytes_reduced(&lo),
+        Fr::from_be_bytes_reduced(&hi),
+    )
+}
+
+fn rehash(env: &Env, previous: &Fr) -> Fr {
+    Round::new(env, Some(previous)).finish(env)
+}
+
+impl Transcript {
+    pub fn generate(
+        env: &Env,
+        proof: &Proof,
+        public_inputs: &[u8],
+        vk: &VerificationKey,
+    ) -> Transcript {
+        // eta, eta_two, eta_three: VK header, public inputs, pairing points, w1..w3.
+        let mut round = Round::new(env, None);
+        round.u64(vk.circuit_size);
+        round.u64(vk.num_public_inputs);
+        round.u64(vk.pub_inputs_offset);
+        for pi in public_inputs.chunks_exact(32) {
+            let mut w = [0u8; 32];
+            w.copy_from_slice(pi);
+            round.word(&w);
+        }
+        for p in proof.pairing_point_object.iter() {
+            round.fr(p);
+        }
+        round.point(&proof.w1);
+        round.point(&proof.w2);
+        round.point(&proof.w3);
+        let mut previous = round.finish(env);
+        let (eta, eta_two) = split_challenge(&previous);
+        previous = rehash(env, &previous);
+        let (eta_three, _) = split_challenge(&previous);
+
+        // beta, gamma: lookup read counts/tags and w4.
+        let mut round = Round::new(env, Some(&previous));
+        round.point(&proof.lookup_read_counts);
+        round.point(&proof.lookup_read_tags);
+        round.point(&proof.w4);
+        previous = round.finish(env);
+        let (beta, gamma) = split_challenge(&previous);
+
+        // alphas: lookup inverses and z_perm, then repeated rehashing.
+        let mut alphas = [Fr::ZERO; NUMBER_OF_ALPHAS];
+        let mut round = Round::new(env, Some(&previous));
+        round.point(&proof.lookup_inverses);
+        round.point(&proof.z_perm);
+        previous = round.finish(env);
+        (alphas[0], alphas[1]) = split_challenge(&previous);
+        for i in 1..NUMBER_OF_ALPHAS / 2 {
+            previous = rehash(env, &previous);
+            (alphas[2 * i], alphas[2 * i + 1]) = split_challenge(&previous);
+        }
+        if NUMBER_OF_ALPHAS % 2 == 1 && NUMBER_OF_ALPHAS > 2 {
+            previous = rehash(env, &previous);
+            (alphas[NUMBER_OF_ALPHAS - 1], _) = split_challenge(&previous);
+        }
+
+        let mut gate_challenges = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+        for g in gate_challenges.iter_mut() {
+            previous = rehash(env, &previous);
+            (*g, _) = split_challenge(&previous);
+        }
+
+        let mut sumcheck_u_challenges = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+        for (i, u) in sumcheck_u_challenges.iter_mut().enumerate() {
+            let mut round = Round::new(env, Some(&previous));
+            for j in 0..BATCHED_RELATION_PARTIAL_LENGTH {
+                round.fr(&proof.sumcheck_univariates[i][j]);
+            }
+            previous = round.finish(env);
+            (*u, _) = split_challenge(&previous);
+        }
+
+        let mut round = Round::new(env, Some(&previous));
+        for i in 0..NUMBER_OF_ENTITIES {
+            round.fr(&proof.sumcheck_evaluations[i]);
+        }
+        previous = round.finish(env);
+        let (rho, _) = split_challenge(&previous);
+
+        let mut round = Round::new(env, Some(&previous));
+        for c in proof.gemini_fold_comms.iter() {
+            round.point(c);
+        }
+        previous = round.finish(env);
+        let (gemini_r, _) = split_challenge(&previous);
+
+        let mut round = Round::new(env, Some(&previous));
+        for e in proof.gemini_a_evaluations.iter() {
+            round.fr(e);
+        }
+        previous = round.finish(env);
+        let (shplonk_nu, _) = split_challenge(&previous);
+
+        let mut round = Round::new(env, Some(&previous));
+        round.point(&proof.shplonk_q);
+        previous = round.finish(env);
+        let (shplonk_z, _) = split_challenge(&previous);
+
+        Transcript {
+            relation_parameters: RelationParameters {
+                eta,
+                eta_two,
+                eta_three,
+                beta,
+                gamma,
+                public_inputs_delta: Fr::ZERO,
+            },
+            alphas,
+            gate_challenges,
+            sumcheck_u_challenges,
+            rho,
+            gemini_r,
+            shplonk_nu,
+            shplonk_z,
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! UltraHonk (bb 0.87, keccak oracle, non-ZK) verification key and proof layouts.
+//!
+//! VK bytes (`bb write_vk --output_format bytes`, i.e. `circuits/noir/target/vk`):
+//!   u64_be circuit_size || u64_be log_circuit_size || u64_be num_public_inputs ||
+//!   u64_be pub_inputs_offset || 27 x G1 (x || y, 32-byte big-endian each)
+//!
+//! Proof bytes (456 fields of 32 bytes): pairing point object (16 Fr), 8 witness
+//! commitments, sumcheck univariates, sumcheck evaluations, Gemini fold commitments,
+//! Gemini evaluations, Shplonk Q and KZG quotient. Proof commitments are encoded as
+//! four 136-bit limbs (x_0, x_1, y_0, y_1).
+
+use crate::field::Fr;
+
+pub const CONST_PROOF_SIZE_LOG_N: usize = 28;
+pub const BATCHED_RELATION_PARTIAL_LENGTH: usize = 8;
+pub const NUMBER_OF_ENTITIES: usize = 40;
+pub const NUMBER_UNSHIFTED: usize = 35;
+pub const NUMBER_OF_SUBRELATIONS: usize = 26;
+pub const NUMBER_OF_ALPHAS: usize = NUMBER_OF_SUBRELATIONS - 1;
+pub const PAIRING_POINTS_SIZE: usize = 16;
+pub const NUM_VK_COMMITMENTS: usize = 27;
+
+pub const FIELD_SIZE: usize = 32;
+pub const VK_HEADER_SIZE: usize = 32;
+pub const VK_SIZE: usize = VK_HEADER_SIZE + NUM_VK_COMMITMENTS * 64;
+pub const PROOF_FIELDS: usize = PAIRING_POINTS_SIZE
+    + 8 * 4
+    + CONST_PROOF_SIZE_LOG_N * BATCHED_RELATION_PARTIAL_LENGTH
+    + NUMBER_OF_ENTITIES
+    + (CONST_PROOF_SIZE_LOG_N - 1) * 4
+    + CONST_PROOF_SIZE_LOG_N
+    + 2 * 4;
+pub const PROOF_SIZE: usize = PROOF_FIELDS * FIELD_SIZE;
+
+/// Uncompressed G1 point, `be(x) || be(y)`; all zeroes is the point at infinity.
+pub type G1 = [u8; 64];
+
+/// G1 commitment as sent in the proof: 136-bit limbs, hashed verbatim into the transcript.
+#[derive(Copy, Clone)]
+pub struct G1ProofPoint {
+    pub x_0: [u8; 32],
+    pub x_1: [u8; 32],
+    pub y_0: [u8; 32],
+    pub y_1: [u8; 32],
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseError {
+    Vk,
+    Proof,
+}
+
+/// Precomputed commitments, in the order bb serializes them.
+pub struct VerificationKey {
+    pub circuit_size: u64,
+    pub log_circuit_size: u64,
+    pub num_public_inputs: u64,
+    pub pub_inputs_offset: u64,
+    /// q_m, q_c, q_l, q_r, q_o, q_4, q_lookup, q_arith, q_delta_range, q_elliptic,
+    /// q_aux, q_poseidon2_external, q_poseidon2_internal, sigma_1..4, id_1..4,
+    /// table_1..4, lagrange_first, lagrange_last
+    pub commitments: [G1; NUM_VK_COMMITMENTS],
+}
+
+pub struct Proof {
+    pub pairing_point_object: [Fr; PAIRING_POINTS_SIZE],
+    pub w1: G1ProofPoint,
+    pub w2: G1ProofPoint,
+    pub w3: G1ProofPoint,
+    pub w4: G1ProofPoint,
+    pub z_perm: G1ProofPoint,
+    pub lookup_read_counts: G1ProofPoint,
+    pub lookup_read_tags: G1ProofPoint,
+    pub lookup_inverses: G1ProofPoint,
+    pub sumcheck_univariates: [[Fr; BATCHED_RELATION_PARTIAL_LENGTH]; CONST_PROOF_SIZE_LOG_N],
+    pub sumcheck_evaluations: [Fr; NUMBER_OF_ENTITIES],
+    pub gemini_fold_comms: [G1ProofPoint; CONST_PROOF_SIZE_LOG_N - 1],
+    pub gemini_a_evaluations: [Fr; CONST_PROOF_SIZE_LOG_N],
+    pub shplonk_q: G1ProofPoint,
+    pub kzg_quotient: G1ProofPoint,
+}
+
+fn read_u64_be(bytes: &[u8], offset: usize) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_be_bytes(buf)
+}
+
+fn read_field(bytes: &[u8], offset: usize) -> [u8; 32] {
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(&bytes[offset..offset + FIELD_SIZE]);
+    buf
+}
+
+impl VerificationKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        if bytes.len() != VK_SIZE {
+            return Err(ParseError::Vk);
+        }
+        let circuit_size = read_u64_be(bytes, 0);
+        let log_circuit_size = read_u64_be(bytes, 8);
+        let num_public_inputs = read_u64_be(bytes, 16);
+        let pub_inputs_offset = read_u64_be(bytes, 24);
+        if log_circuit_size == 0
+            || log_circuit_size as usize > CONST_PROOF_SIZE_LOG_N
+            || circuit_size != 1u64 << log_circuit_size
+            || num_public_inputs < PAIRING_POINTS_SIZE as u64
+            || num_public_inputs >= circuit_size
+        {
+            return Err(ParseError::Vk);
+        }
+        let mut commitments = [[0u8; 64]; NUM_VK_COMMITMENTS];
+        for (i, c) in commitments.iter_mut().enumerate() {
+            let start = VK_HEADER_SIZE + i * 64;
+            c.copy_from_slice(&bytes[start..start + 64]);
+        }
+        Ok(VerificationKey {
+            circuit_size,
+            log_circuit_size,
+            num_public_inputs,
+            pub_inputs_offset,
+            commitments,
+        })
+    }
+
+    /// Number of user public inputs (excluding the pairing point object).
+    pub fn num_user_public_inputs(&self) -> usize {
+        self.num_public_inputs as usize - PAIRING_POINTS_SIZE
+    }
+}
+
+impl G1ProofPoint {
+    /// Recombine limbs: x = x_0 | x_1 << 136 (same for y). Limbs must fit their width.
+    pub fn to_affine(self) -> Result<G1, ParseError> {
+        let mut out = [0u8; 64];
+        combine_limbs(&self.x_0, &self.x_1, &mut out[0..32])?;
+        combine_limbs(&self.y_0, &self.y_1, &mut out[32..64])?;
+        Ok(out)
+    }
+}
+
+fn combine_limbs(lo: &[u8; 32], hi: &[u8; 32], out: &mut [u8]) -> Result<(), ParseError> {
+    // lo < 2^136 (17 bytes), hi < 2^120 (15 bytes).
+    if lo[..15].iter().any(|b| *b != 0) || hi[..17].iter().any(|b| *b != 0) {
+        return Err(ParseError::Proof);
+    }
+    out[..15].copy_from_slice(&hi[17..]);
+    out[15..].copy_from_slice(&lo[15..]);
+    Ok(())
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn field(&mut self) -> [u8; 32] {
+        let f = read_field(self.bytes, self.offset);
+        self.offset += FIELD_SIZE;
+        f
+    }
+
+    fn fr(&mut self) -> Result<Fr, ParseError> {
+        Fr::from_be_bytes_canonical(&self.field()).ok_or(ParseError::Proof)
+    }
+
+    fn point(&mut self) -> G1ProofPoint {
+        G1ProofPoint {
+            x_0: self.field(),
+            x_1: self.field(),
+            y_0: self.field(),
+            y_1: self.field(),
+        }
+    }
+}
+
+impl Proof {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        if bytes.len() != PROOF_SIZE {
+            return Err(ParseError::Proof);
+        }
+        let mut r = Reader { bytes, offset: 0 };
+
+        let mut pairing_point_object = [Fr::ZERO; PAIRING_POINTS_SIZE];
+        for p in pairing_point_object.iter_mut() {
+            *p = r.fr()?;
+        }
+        let w1 = r.point();
+        let w2 = r.point();
+        let w3 = r.point();
+        let lookup_read_counts = r.point();
+        let lookup_read_tags = r.point();
+        let w4 = r.point();
+        let lookup_inverses = r.point();
+        let z_perm = r.point();
+
+        let mut sumcheck_univariates =
+            [[Fr::ZERO; BATCHED_RELATION_PARTIAL_LENGTH]; CONST_PROOF_SIZE_LOG_N];
+        for round in sumcheck_univariates.iter_mut() {
+            for v in round.iter_mut() {
+                *v = r.fr()?;
+            }
+        }
+        let mut sumcheck_evaluations = [Fr::ZERO; NUMBER_OF_ENTITIES];
+        for v in sumcheck_evaluations.iter_mut() {
+            *v = r.fr()?;
+        }
+        let empty = G1ProofPoint {
+            x_0: [0u8; 32],
+            x_1: [0u8; 32],
+            y_0: [0u8; 32],
+            y_1: [0u8; 32],
+        };
+        let mut gemini_fold_comms = [empty; CONST_PROOF_SIZE_LOG_N - 1];
+        for c in gemini_fold_comms.iter_mut() {
+            *c = r.point();
+        }
+        let mut gemini_a_evaluations = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+        for v in gemini_a_evaluations.iter_mut() {
+            *v = r.fr()?;
+        }
+        let shplonk_q = r.point();
+        let kzg_quotient = r.point();
+
+        Ok(Proof {
+            pairing_point_object,
+            w1,
+            w2,
+            w3,
+            w4,
+            z_perm,
+            lookup_read_counts,
+            lookup_read_tags,
+            lookup_inverses,
+            sumcheck_univariates,
+            sumcheck_evaluations,
+            gemini_fold_comms,
+            gemini_a_evaluations,
+            shplonk_q,
+            kzg_quotient,
+        })
+    }
+}
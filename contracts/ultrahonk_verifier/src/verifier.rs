@@ -0,0 +1,400 @@
+//! UltraHonk verification: sumcheck over the batched relations, then Shplemini
+//! (Gemini folding + Shplonk batching) reduced to a single KZG pairing check, into which
+//! the pairing point object (the recursion accumulator) is folded.
+//! Mirrors bb's generated `HonkVerifier.sol` for the keccak flavour.
+
+use soroban_sdk::crypto::bn254::{Bn254G1Affine, Bn254G2Affine, Fr as HostFr};
+use soroban_sdk::{vec, Bytes, BytesN, Env};
+use zk_types::g1_is_valid;
+
+use crate::field::Fr;
+use crate::relations::accumulate_relation_evaluations;
+use crate::transcript::Transcript;
+use crate::types::{
+    G1ProofPoint, Proof, VerificationKey, BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N,
+    G1, NUMBER_OF_ENTITIES, NUMBER_UNSHIFTED, NUM_VK_COMMITMENTS, PAIRING_POINTS_SIZE,
+};
+
+const NUM_MSM_TERMS: usize = NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N + 2;
+
+/// Fixed G2 generator, `x.c1 || x.c0 || y.c1 || y.c0`.
+const G2_GENERATOR: [u8; 128] = concat_words([
+    hex(b"198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"),
+    hex(b"1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"),
+    hex(b"090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"),
+    hex(b"12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"),
+]);
+
+/// [x]_2 from the Aztec Ignition SRS.
+const G2_X: [u8; 128] = concat_words([
+    hex(b"260e01b251f6f1c7e7ff4e580791dee8ea51d87a358e038b4efe30fac09383c1"),
+    hex(b"0118c4d5b837bcc2bc89b5b398b5974e9f5944073b32078b7e231fec938883b0"),
+    hex(b"04fc6369f7110fe3d25156c1bb9a72859cf2a04641f99ba4ee413c80da6a5fe4"),
+    hex(b"22febda3c0c0632a56475b4214e5615e11e6dd3f96e6cea2854a87d4dacc5e55"),
+]);
+
+const fn hex(s: &[u8; 64]) -> [u8; 32] {
+    const fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("invalid hex"),
+        }
+    }
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        out[i] = (nibble(s[2 * i]) << 4) | nibble(s[2 * i + 1]);
+        i += 1;
+    }
+    out
+}
+
+const fn concat_words(words: [[u8; 32]; 4]) -> [u8; 128] {
+    let mut out = [0u8; 128];
+    let mut i = 0;
+    while i < 128 {
+        out[i] = words[i / 32][i % 32];
+        i += 1;
+    }
+    out
+}
+
+/// Full verification of `proof` against `vk`; `public_inputs` are the user inputs
+/// as concatenated 32-byte words (the pairing point object is part of the proof).
+pub fn verify(env: &Env, vk: &VerificationKey, proof: &Proof, public_inputs: &[u8]) -> bool {
+    if public_inputs.len() != vk.num_user_public_inputs() * 32 {
+        return false;
+    }
+    let mut tp = Transcript::generate(env, proof, public_inputs, vk);
+    tp.relation_parameters.public_inputs_delta = match compute_public_input_delta(
+        vk,
+        proof,
+        public_inputs,
+        tp.relation_parameters.beta,
+        tp.relation_parameters.gamma,
+    ) {
+        Some(delta) => delta,
+        None => return false,
+    };
+    let log_n = vk.log_circuit_size as usize;
+    verify_sumcheck(proof, &tp, log_n)
+        && verify_shplemini(env, vk, proof, &tp, log_n).unwrap_or(false)
+}
+
+fn compute_public_input_delta(
+    vk: &VerificationKey,
+    proof: &Proof,
+    public_inputs: &[u8],
+    beta: Fr,
+    gamma: Fr,
+) -> Option<Fr> {
+    let offset = Fr::from_u64(vk.pub_inputs_offset);
+    let mut numerator = Fr::ONE;
+    let mut denominator = Fr::ONE;
+    let mut numerator_acc = gamma + beta * (Fr::from_u64(vk.circuit_size) + offset);
+    let mut denominator_acc = gamma - beta * (offset + Fr::ONE);
+
+    let mut step = |pi: Fr| {
+        numerator = numerator * (numerator_acc + pi);
+        denominator = denominator * (denominator_acc + pi);
+        numerator_acc = numerator_acc + beta;
+        denominator_acc = denominator_acc - beta;
+    };
+    for word in public_inputs.chunks_exact(32) {
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(word);
+        step(Fr::from_be_bytes_canonical(&buf)?);
+    }
+    for pi in proof.pairing_point_object.iter() {
+        step(*pi);
+    }
+    Some(numerator * denominator.invert()?)
+}
+
+fn verify_sumcheck(proof: &Proof, tp: &Transcript, log_n: usize) -> bool {
+    let mut round_target = Fr::ZERO;
+    let mut pow_partial_evaluation = Fr::ONE;
+    for round in 0..log_n {
+        let univariate = &proof.sumcheck_univariates[round];
+        if univariate[0] + univariate[1] != round_target {
+            return false;
+        }
+        let u = tp.sumcheck_u_challenges[round];
+        round_target = match compute_next_target_sum(univariate, u) {
+            Some(t) => t,
+            None => return false,
+        };
+        pow_partial_evaluation =
+            pow_partial_evaluation * (Fr::ONE + u * (tp.gate_challenges[round] - Fr::ONE));
+    }
+    let grand_honk_relation_sum = accumulate_relation_evaluations(
+        &proof.sumcheck_evaluations,
+        &tp.relation_parameters,
+        &tp.alphas,
+        pow_partial_evaluation,
+    );
+    grand_honk_relation_sum == round_target
+}
+
+/// Evaluates the round univariate (given on 0..8) at `challenge` in barycentric form.
+pub(crate) fn compute_next_target_sum(
+    univariate: &[Fr; BATCHED_RELATION_PARTIAL_LENGTH],
+    challenge: Fr,
+) -> Option<Fr> {
+    let n = BATCHED_RELATION_PARTIAL_LENGTH;
+    let mut numerator = Fr::ONE;
+    let mut terms = [Fr::ONE; BATCHED_RELATION_PARTIAL_LENGTH];
+    for (i, term) in terms.iter_mut().enumerate() {
+        let shifted = challenge - Fr::from_u64(i as u64);
+        numerator = numerator * shifted;
+        // prod_{j != i} (i - j)
+        let mut denominator = Fr::ONE;
+        for j in 0..n {
+            if j != i {
+                denominator = denominator * (Fr::from_u64(i as u64) - Fr::from_u64(j as u64));
+            }
+        }
+        *term = denominator * shifted;
+    }
+    batch_invert(&mut terms)?;
+
+    let mut target = Fr::ZERO;
+    for i in 0..n {
+        target = target + univariate[i] * terms[i];
+    }
+    Some(target * numerator)
+}
+
+/// Montgomery's trick: one inversion for the whole slice. `None` if any entry is zero.
+fn batch_invert(values: &mut [Fr]) -> Option<()> {
+    let mut prefix = [Fr::ONE; BATCHED_RELATION_PARTIAL_LENGTH];
+    let mut acc = Fr::ONE;
+    for (p, v) in prefix.iter_mut().zip(values.iter()) {
+        *p = acc;
+        acc = acc * *v;
+    }
+    let mut inv = acc.invert()?;
+    for (p, v) in prefix.iter().zip(values.iter_mut()).rev() {
+        let next = inv * *v;
+        *v = inv * *p;
+        inv = next;
+    }
+    Some(())
+}
+
+fn verify_shplemini(
+    env: &Env,
+    vk: &VerificationKey,
+    proof: &Proof,
+    tp: &Transcript,
+    log_n: usize,
+) -> Option<bool> {
+    let r = tp.gemini_r;
+    let nu = tp.shplonk_nu;
+    let z = tp.shplonk_z;
+
+    let mut r_powers = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+    r_powers[0] = r;
+    for i in 1..CONST_PROOF_SIZE_LOG_N {
+        r_powers[i] = r_powers[i - 1].square();
+    }
+
+    let mut scalars = [Fr::ZERO; NUM_MSM_TERMS];
+    let mut commitments = [[0u8; 64]; NUM_MSM_TERMS];
+
+    let mut pos_inverted_denominator = (z - r_powers[0]).invert()?;
+    let mut neg_inverted_denominator = (z + r_powers[0]).invert()?;
+    let unshifted_scalar = pos_inverted_denominator + nu * neg_inverted_denominator;
+    let shifted_scalar = r.invert()? * (pos_inverted_denominator - nu * neg_inverted_denominator);
+
+    scalars[0] = Fr::ONE;
+    commitments[0] = affine(&proof.shplonk_q)?;
+
+    let mut batching_challenge = Fr::ONE;
+    let mut batched_evaluation = Fr::ZERO;
+    for (i, evaluation) in proof.sumcheck_evaluations.iter().enumerate() {
+        let scalar = if i < NUMBER_UNSHIFTED {
+            unshifted_scalar
+        } else {
+            shifted_scalar
+        };
+        scalars[i + 1] = -scalar * batching_challenge;
+        batched_evaluation = batched_evaluation + *evaluation * batching_challenge;
+        batching_challenge = batching_challenge * tp.rho;
+    }
+
+    commitments[1..=NUM_VK_COMMITMENTS].copy_from_slice(&vk.commitments);
+    let witness_commitments = [
+        &proof.w1,
+        &proof.w2,
+        &proof.w3,
+        &proof.w4,
+        &proof.z_perm,
+        &proof.lookup_inverses,
+        &proof.lookup_read_counts,
+        &proof.lookup_read_tags,
+        // Shifted: w1..w4 and z_perm again.
+        &proof.w1,
+        &proof.w2,
+        &proof.w3,
+        &proof.w4,
+        &proof.z_perm,
+    ];
+    for (i, c) in witness_commitments.iter().enumerate() {
+        commitments[NUM_VK_COMMITMENTS + 1 + i] = affine(c)?;
+    }
+
+    let fold_pos_evaluations = compute_fold_pos_evaluations(
+        &tp.sumcheck_u_challenges,
+        batched_evaluation,
+        &proof.gemini_a_evaluations,
+        &r_powers,
+        log_n,
+    )?;
+
+    let mut constant_term_accumulator = fold_pos_evaluations[0] * pos_inverted_denominator
+        + proof.gemini_a_evaluations[0] * nu * neg_inverted_denominator;
+    batching_challenge = nu.square();
+
+    for i in 0..CONST_PROOF_SIZE_LOG_N - 1 {
+        // Dummy rounds (beyond log_n) carry a zero scalar and are skipped by the MSM.
+        if i + 1 >= log_n {
+            break;
+        }
+        pos_inverted_denominator = (z - r_powers[i + 1]).invert()?;
+        neg_inverted_denominator = (z + r_powers[i + 1]).invert()?;
+        let scaling_factor_pos = batching_challenge * pos_inverted_denominator;
+        let scaling_factor_neg = batching_challenge * nu * neg_inverted_denominator;
+        scalars[NUMBER_OF_ENTITIES + 1 + i] = -scaling_factor_neg - scaling_factor_pos;
+        constant_term_accumulator = constant_term_accumulator
+            + scaling_factor_neg * proof.gemini_a_evaluations[i + 1]
+            + scaling_factor_pos * fold_pos_evaluations[i + 1];
+        batching_challenge = batching_challenge * nu * nu;
+        commitments[NUMBER_OF_ENTITIES + 1 + i] = affine(&proof.gemini_fold_comms[i])?;
+    }
+
+    let mut generator = [0u8; 64];
+    generator[31] = 1;
+    generator[63] = 2;
+    commitments[NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N] = generator;
+    scalars[NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N] = constant_term_accumulator;
+
+    let quotient = affine(&proof.kzg_quotient)?;
+    commitments[NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N + 1] = quotient;
+    scalars[NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N + 1] = z;
+
+    let p0 = batch_mul(env, &commitments, &scalars);
+    let p1 = -g1(env, &quotient);
+
+    // Aggregate with the pairing points from the public inputs: P0 = sep * P0 + lhs,
+    // P1 = sep * P1 + rhs, so the check also covers the accumulated recursion pairing.
+    // Like bb's validateOnCurve, the point at infinity is rejected too.
+    let (lhs, rhs) = pairing_points(&proof.pairing_point_object)?;
+    for point in [&lhs, &rhs] {
+        if *point == [0u8; 64] || !g1_is_valid(point) {
+            return None;
+        }
+    }
+    let mut separator_input = Bytes::from_array(env, &lhs);
+    separator_input.extend_from_array(&rhs);
+    separator_input.append(&p0.to_bytes().into());
+    separator_input.append(&p1.to_bytes().into());
+    let separator = Fr::from_be_bytes_reduced(&env.crypto().keccak256(&separator_input).to_array());
+    let separator = HostFr::from_bytes(BytesN::from_array(env, &separator.to_be_bytes()));
+
+    let bn = env.crypto().bn254();
+    let p0 = bn.g1_add(&bn.g1_mul(&p0, &separator), &g1(env, &lhs));
+    let p1 = bn.g1_add(&bn.g1_mul(&p1, &separator), &g1(env, &rhs));
+    Some(bn.pairing_check(
+        vec![env, p0, p1],
+        vec![
+            env,
+            Bn254G2Affine::from_bytes(BytesN::from_array(env, &G2_GENERATOR)),
+            Bn254G2Affine::from_bytes(BytesN::from_array(env, &G2_X)),
+        ],
+    ))
+}
+
+fn compute_fold_pos_evaluations(
+    sumcheck_u_challenges: &[Fr; CONST_PROOF_SIZE_LOG_N],
+    mut batched_eval_accumulator: Fr,
+    gemini_evaluations: &[Fr; CONST_PROOF_SIZE_LOG_N],
+    r_powers: &[Fr; CONST_PROOF_SIZE_LOG_N],
+    log_n: usize,
+) -> Option<[Fr; CONST_PROOF_SIZE_LOG_N]> {
+    let mut fold_pos_evaluations = [Fr::ZERO; CONST_PROOF_SIZE_LOG_N];
+    for i in (1..=log_n).rev() {
+        let challenge_power = r_powers[i - 1];
+        let u = sumcheck_u_challenges[i - 1];
+        let numerator = challenge_power * batched_eval_accumulator * Fr::from_u64(2)
+            - gemini_evaluations[i - 1] * (challenge_power * (Fr::ONE - u) - u);
+        let denominator = challenge_power * (Fr::ONE - u) + u;
+        batched_eval_accumulator = numerator * denominator.invert()?;
+        fold_pos_evaluations[i - 1] = batched_eval_accumulator;
+    }
+    Some(fold_pos_evaluations)
+}
+
+/// (lhs, rhs) encoded in the pairing point object, as bb's `convertPairingPointsToG1`:
+/// lhs.x, lhs.y, rhs.x, rhs.y, each four 68-bit limbs, lowest first. None if a limb or
+/// the recombined coordinate does not fit.
+pub(crate) fn pairing_points(limbs: &[Fr; PAIRING_POINTS_SIZE]) -> Option<(G1, G1)> {
+    let mut coords = [[0u8; 32]; 4];
+    for (coord, coord_limbs) in coords.iter_mut().zip(limbs.chunks_exact(4)) {
+        // 68-bit limbs are 17 hex digits; collect them little-endian, then pack to 32 bytes.
+        let mut nibbles = [0u8; 68];
+        for (i, limb) in coord_limbs.iter().enumerate() {
+            let bytes = limb.to_be_bytes();
+            if bytes[..23].iter().any(|b| *b != 0) || bytes[23] > 0x0f {
+                return None;
+            }
+            let mut low = [0u8; 16];
+            low.copy_from_slice(&bytes[16..]);
+            let value = u128::from_be_bytes(low);
+            for (j, nibble) in nibbles[17 * i..17 * (i + 1)].iter_mut().enumerate() {
+                *nibble = ((value >> (4 * j)) & 0xf) as u8;
+            }
+        }
+        if nibbles[64..].iter().any(|n| *n != 0) {
+            return None;
+        }
+        for (k, byte) in coord.iter_mut().rev().enumerate() {
+            *byte = nibbles[2 * k] | (nibbles[2 * k + 1] << 4);
+        }
+    }
+    let mut lhs = [0u8; 64];
+    let mut rhs = [0u8; 64];
+    lhs[..32].copy_from_slice(&coords[0]);
+    lhs[32..].copy_from_slice(&coords[1]);
+    rhs[..32].copy_from_slice(&coords[2]);
+    rhs[32..].copy_from_slice(&coords[3]);
+    Some((lhs, rhs))
+}
+
+fn affine(p: &G1ProofPoint) -> Option<G1> {
+    p.to_affine().ok()
+}
+
+fn g1(env: &Env, p: &G1) -> Bn254G1Affine {
+    Bn254G1Affine::from_bytes(BytesN::from_array(env, p))
+}
+
+/// sum_i scalars[i] * commitments[i], skipping zero scalars (dummy Gemini rounds).
+fn batch_mul(
+    env: &Env,
+    commitments: &[G1; NUM_MSM_TERMS],
+    scalars: &[Fr; NUM_MSM_TERMS],
+) -> Bn254G1Affine {
+    let bn = env.crypto().bn254();
+    let mut acc = g1(env, &[0u8; 64]);
+    for (c, s) in commitments.iter().zip(scalars.iter()) {
+        if s.is_zero() {
+            continue;
+        }
+        let scalar = HostFr::from_bytes(BytesN::from_array(env, &s.to_be_bytes()));
+        let term = bn.g1_mul(&g1(env, c), &scalar);
+        acc = bn.g1_add(&acc, &term);
+    }
+    acc
+}
@@ -1,6 +1,7 @@
 //! Verifier contract v2 (BN254/Groth16) — crypto-only.
 //! Responsibilities:
-//!   - Load verification key from storage (by vk_hash)
+//!   - Store validated verification keys (admin only, write-once, by canonical vk_hash)
+//!   - Track each key's lifecycle (active, deprecated until a ledger, revoked)
 //!   - Verify Groth16 proof against public inputs
 //!   - Emit structured event on success/failure
 //!   - Return bool (no game logic, no policy)
@@ -9,21 +10,153 @@
 
 use core::ops::Neg;
 use soroban_sdk::{
-    contract, contractimpl,
+    contract, contractimpl, contracttype,
     crypto::bn254::{Bn254G1Affine, Bn254G2Affine, Fr},
-    vec, BytesN, Env, Vec, Symbol,
+    vec, Address, BytesN, Env, Vec, Symbol,
 };
-use zk_types::{Groth16Error, ZkProof, ZkVerificationKey};
+use zk_types::{
+    validate_proof, validate_vk, vk_hash, Groth16Error, VkState, ZkProof, ZkVerificationKey,
+};
+
+/// One stored key and its state, as listed by `list_vks`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VkInfo {
+    pub vk_hash: BytesN<32>,
+    pub state: VkState,
+}
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    /// Verification key, validated by store_vk.
+    Vk(BytesN<32>),
+    VkState(BytesN<32>),
+    /// Hashes of all stored keys, in storage order.
+    VkList,
+}
+
+fn require_admin(env: &Env) -> Result<(), Groth16Error> {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Groth16Error::NotInitialized)?;
+    admin.require_auth();
+    Ok(())
+}
+
+fn vk_state(env: &Env, vk_hash: &BytesN<32>) -> Option<VkState> {
+    env.storage().persistent().get(&DataKey::VkState(vk_hash.clone()))
+}
+
+/// Set the state of a stored, non-revoked key. Admin only.
+fn set_vk_state(env: &Env, vk_hash: &BytesN<32>, state: VkState) -> Result<(), Groth16Error> {
+    require_admin(env)?;
+    match vk_state(env, vk_hash) {
+        None => return Err(Groth16Error::InvalidVerificationKey),
+        Some(VkState::Revoked) => return Err(Groth16Error::VkRevoked),
+        Some(_) => {}
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::VkState(vk_hash.clone()), &state);
+    Ok(())
+}
 
 #[contract]
 pub struct Verifier;
 
 #[contractimpl]
 impl Verifier {
-    /// Store a verification key under its hash.
-    /// vk_hash = sha256(serialize(vk)).
-    pub fn store_vk(env: Env, vk_hash: BytesN<32>, vk: ZkVerificationKey) {
-        env.storage().persistent().set(&vk_hash, &vk);
+    /// Set the admin allowed to store keys. Once only, signed by `admin`.
+    pub fn init(env: Env, admin: Address) -> Result<(), Groth16Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Groth16Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Validate a verification key and store it. Admin only. Points are checked here once,
+    /// so `verify` skips VK validation.
+    /// Returns vk_hash = zk_types::vk_hash(vk), computed here rather than taken from the caller.
+    /// Slots are write-once: storing the same key again fails with VkAlreadyStored.
+    /// Malformed points fail with InvalidVerificationKey. Emits ("zk", "vk_stored", vk_hash).
+    pub fn store_vk(env: Env, vk: ZkVerificationKey) -> Result<BytesN<32>, Groth16Error> {
+        require_admin(&env)?;
+        validate_vk(&vk)?;
+
+        let hash = vk_hash(&env, &vk);
+        let key = DataKey::Vk(hash.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Groth16Error::VkAlreadyStored);
+        }
+        env.storage().persistent().set(&key, &vk);
+        env.storage()
+            .persistent()
+            .set(&DataKey::VkState(hash.clone()), &VkState::Active);
+        let mut list: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VkList)
+            .unwrap_or(Vec::new(&env));
+        list.push_back(hash.clone());
+        env.storage().persistent().set(&DataKey::VkList, &list);
+        env.events().publish(
+            (Symbol::new(&env, "zk"), Symbol::new(&env, "vk_stored"), hash.clone()),
+            (),
+        );
+        Ok(hash)
+    }
+
+    /// Keep a key usable up to and including `last_ledger`, then reject it with VkExpired.
+    /// Admin only; may be called again to move the window. Emits ("zk", "vk_deprecated", vk_hash).
+    pub fn deprecate_vk(env: Env, vk_hash: BytesN<32>, last_ledger: u32) -> Result<(), Groth16Error> {
+        set_vk_state(&env, &vk_hash, VkState::Deprecated(last_ledger))?;
+        env.events().publish(
+            (Symbol::new(&env, "zk"), Symbol::new(&env, "vk_deprecated"), vk_hash),
+            last_ledger,
+        );
+        Ok(())
+    }
+
+    /// Reject the key from now on with VkRevoked. Admin only; final.
+    /// Emits ("zk", "vk_revoked", vk_hash).
+    pub fn revoke_vk(env: Env, vk_hash: BytesN<32>) -> Result<(), Groth16Error> {
+        set_vk_state(&env, &vk_hash, VkState::Revoked)?;
+        env.events().publish(
+            (Symbol::new(&env, "zk"), Symbol::new(&env, "vk_revoked"), vk_hash),
+            (),
+        );
+        Ok(())
+    }
+
+    /// State of a stored key, if any.
+    pub fn get_vk_state(env: Env, vk_hash: BytesN<32>) -> Option<VkState> {
+        vk_state(&env, &vk_hash)
+    }
+
+    /// Every stored key with its state, in storage order, so clients can pick proving artifacts.
+    pub fn list_vks(env: Env) -> Vec<VkInfo> {
+        let list: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VkList)
+            .unwrap_or(Vec::new(&env));
+        let mut out = Vec::new(&env);
+        for vk_hash in list.iter() {
+            if let Some(state) = vk_state(&env, &vk_hash) {
+                out.push_back(VkInfo { vk_hash, state });
+            }
+        }
+        out
+    }
+
+    /// Key stored under `vk_hash`, if any.
+    pub fn get_vk(env: Env, vk_hash: BytesN<32>) -> Option<ZkVerificationKey> {
+        env.storage().persistent().get(&DataKey::Vk(vk_hash))
     }
 
     /// Verify a Groth16 proof.
@@ -31,7 +164,11 @@ impl Verifier {
     ///   proof: BytesN<256> packed A(64) || B(128) || C(64)
     ///   public_inputs: Vec<BytesN<32>>
     ///   vk_hash: BytesN<32>
-    /// Returns bool and emits event.
+    /// Returns bool and emits event. Malformed proof points fail with InvalidProof; revoked keys
+    /// with VkRevoked, deprecated keys past their window with VkExpired.
+    /// Always four pairings: the BN254 host functions expose only `g1_add`, `g1_mul` and
+    /// `pairing_check`, so there is no target-group value to precompute e(alpha, beta) into,
+    /// and `store_vk` keeps the key as given rather than a prepared form.
     pub fn verify(
         env: Env,
         proof: BytesN<256>,
@@ -39,7 +176,7 @@ impl Verifier {
         vk_hash: BytesN<32>,
     ) -> Result<bool, Groth16Error> {
         // Load verification key
-        let vk: ZkVerificationKey = match env.storage().persistent().get::<BytesN<32>, ZkVerificationKey>(&vk_hash) {
+        let vk: ZkVerificationKey = match env.storage().persistent().get(&DataKey::Vk(vk_hash.clone())) {
             Some(v) => v,
             None => {
                 env.events().publish(
@@ -50,6 +187,24 @@ impl Verifier {
             }
         };
 
+        match vk_state(&env, &vk_hash) {
+            Some(VkState::Revoked) => {
+                env.events().publish(
+                    (Symbol::new(&env, "zk"), Symbol::new(&env, "verify_error")),
+                    (vk_hash, "vk_revoked"),
+                );
+                return Err(Groth16Error::VkRevoked);
+            }
+            Some(state) if !state.is_usable_at(env.ledger().sequence()) => {
+                env.events().publish(
+                    (Symbol::new(&env, "zk"), Symbol::new(&env, "verify_error")),
+                    (vk_hash, "vk_expired"),
+                );
+                return Err(Groth16Error::VkExpired);
+            }
+            _ => {}
+        }
+
         if public_inputs.len() + 1 != vk.ic.len() {
             env.events().publish(
                 (Symbol::new(&env, "zk"), Symbol::new(&env, "verify_error")),
@@ -66,13 +221,22 @@ impl Verifier {
         b.copy_from_slice(&proof_arr[64..192]);
         let mut c = [0u8; 64];
         c.copy_from_slice(&proof_arr[192..256]);
-        let proof_a_bytes = BytesN::<64>::from_array(&env, &a);
-        let proof_b_bytes = BytesN::<128>::from_array(&env, &b);
-        let proof_c_bytes = BytesN::<64>::from_array(&env, &c);
+        let proof = ZkProof {
+            a: BytesN::<64>::from_array(&env, &a),
+            b: BytesN::<128>::from_array(&env, &b),
+            c: BytesN::<64>::from_array(&env, &c),
+        };
+        if let Err(e) = validate_proof(&proof) {
+            env.events().publish(
+                (Symbol::new(&env, "zk"), Symbol::new(&env, "verify_error")),
+                (vk_hash, "invalid_proof_point"),
+            );
+            return Err(e);
+        }
 
-        let proof_a = Bn254G1Affine::from_bytes(proof_a_bytes);
-        let proof_b = Bn254G2Affine::from_bytes(proof_b_bytes);
-        let proof_c = Bn254G1Affine::from_bytes(proof_c_bytes);
+        let proof_a = Bn254G1Affine::from_bytes(proof.a);
+        let proof_b = Bn254G2Affine::from_bytes(proof.b);
+        let proof_c = Bn254G1Affine::from_bytes(proof.c);
         let vk_alpha = Bn254G1Affine::from_bytes(vk.alpha);
         let vk_beta = Bn254G2Affine::from_bytes(vk.beta);
         let vk_gamma = Bn254G2Affine::from_bytes(vk.gamma);
@@ -89,7 +253,7 @@ impl Verifier {
             vk_x = bn.g1_add(&vk_x, &prod);
         }
 
-        // Pairing check: e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+        // Pairing check: e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1.
         let neg_a = proof_a.neg();
         let vp1 = vec![&env, neg_a, vk_alpha, vk_x, proof_c];
         let vp2 = vec![&env, proof_b, vk_beta, vk_gamma, vk_delta];
@@ -111,5 +275,8 @@ impl Verifier {
     }
 }
 
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
 #[cfg(test)]
 mod test;
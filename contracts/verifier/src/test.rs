@@ -0,0 +1,269 @@
+//! Unit tests: VK lookup by hash, malformed VK rejected, point-at-infinity proof verifies,
+//! off-curve proof and VK points return typed errors, stored VK against the real GameRunV2
+//! fixture (circuits/build/gamerun_v2_contract_proof.json) and the verify budget, admin-only
+//! write-once key registration under the canonical hash, key lifecycle.
+
+#![cfg(test)]
+extern crate std;
+
+use core::ops::Neg;
+use soroban_sdk::{
+    crypto::bn254::{Bn254G1Affine, Bn254G2Affine, Fr},
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    vec, Address, BytesN, Env, IntoVal, Vec,
+};
+use zk_types::testutils::{packed_proof, v2_fixture, zero_vk};
+use zk_types::{vk_hash, Groth16Error, VkState, G1_SIZE};
+
+use crate::testutils::new_verifier;
+use crate::{Verifier, VerifierClient, VkInfo};
+
+fn inputs(env: &Env, n: u32) -> Vec<BytesN<32>> {
+    let mut v = Vec::new(env);
+    for i in 0..n {
+        let mut w = [0u8; 32];
+        w[31] = i as u8;
+        v.push_back(BytesN::from_array(env, &w));
+    }
+    v
+}
+
+#[test]
+fn test_verify_with_stored_vk() {
+    let env = Env::default();
+    let client = new_verifier(&env);
+    let vk = zero_vk(&env, 11);
+    let hash = client.store_vk(&vk);
+
+    let proof = BytesN::from_array(&env, &[0u8; 256]);
+    assert!(client.verify(&proof, &inputs(&env, 10), &hash));
+}
+
+#[test]
+fn test_unknown_vk_hash_rejected() {
+    let env = Env::default();
+    let client = new_verifier(&env);
+
+    let proof = BytesN::from_array(&env, &[0u8; 256]);
+    let res = client.try_verify(&proof, &inputs(&env, 10), &BytesN::from_array(&env, &[7u8; 32]));
+    assert_eq!(res, Err(Ok(Groth16Error::InvalidVerificationKey)));
+}
+
+#[test]
+fn test_public_input_count_must_match_vk() {
+    let env = Env::default();
+    let client = new_verifier(&env);
+    let vk = zero_vk(&env, 11);
+    let hash = client.store_vk(&vk);
+
+    let proof = BytesN::from_array(&env, &[0u8; 256]);
+    let res = client.try_verify(&proof, &inputs(&env, 9), &hash);
+    assert_eq!(res, Err(Ok(Groth16Error::MalformedVerifyingKey)));
+}
+
+/// (1, 3) is not on y^2 = x^3 + 3; the host would trap on it, store_vk and verify return typed errors.
+#[test]
+fn test_off_curve_points_rejected() {
+    let env = Env::default();
+    let client = new_verifier(&env);
+    let mut off_curve = [0u8; G1_SIZE];
+    off_curve[31] = 1;
+    off_curve[63] = 3;
+
+    let vk = zero_vk(&env, 11);
+    let hash = client.store_vk(&vk);
+    let mut proof = [0u8; 256];
+    proof[192..].copy_from_slice(&off_curve);
+    let res = client.try_verify(&BytesN::from_array(&env, &proof), &inputs(&env, 10), &hash);
+    assert_eq!(res, Err(Ok(Groth16Error::InvalidProof)));
+
+    let mut bad_vk = zero_vk(&env, 11);
+    bad_vk.alpha = BytesN::from_array(&env, &off_curve);
+    assert_eq!(
+        client.try_store_vk(&bad_vk),
+        Err(Ok(Groth16Error::InvalidVerificationKey))
+    );
+    assert!(client.get_vk(&vk_hash(&env, &bad_vk)).is_none());
+}
+
+/// store_vk computes the canonical hash itself, refuses a second write and needs the admin.
+#[test]
+fn test_store_vk_is_admin_only_and_write_once() {
+    let env = Env::default();
+    let vk = zero_vk(&env, 11);
+
+    let bare = VerifierClient::new(&env, &env.register(Verifier, ()));
+    assert_eq!(bare.try_store_vk(&vk), Err(Ok(Groth16Error::NotInitialized)));
+
+    let admin = Address::generate(&env);
+    bare.mock_all_auths().init(&admin);
+    assert_eq!(bare.try_init(&admin), Err(Ok(Groth16Error::AlreadyInitialized)));
+    // No admin signature: the call aborts.
+    assert!(bare.try_store_vk(&vk).is_err());
+
+    let hash = bare
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &bare.address,
+                fn_name: "store_vk",
+                args: (vk.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .store_vk(&vk);
+    assert_eq!(hash, vk_hash(&env, &vk));
+    assert!(bare.get_vk(&hash).is_some());
+
+    env.mock_all_auths();
+    assert_eq!(bare.try_store_vk(&vk), Err(Ok(Groth16Error::VkAlreadyStored)));
+    let other = zero_vk(&env, 3);
+    assert_ne!(bare.store_vk(&other), hash);
+}
+
+/// init needs the admin's signature, so nobody else can claim the contract after deploy.
+#[test]
+fn test_init_requires_admin_auth() {
+    let env = Env::default();
+    let client = VerifierClient::new(&env, &env.register(Verifier, ()));
+    let admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+
+    // Signed by someone else, or not at all: the call aborts and no admin is set.
+    assert!(client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "init",
+                args: (admin.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_init(&admin)
+        .is_err());
+    assert!(client.try_init(&admin).is_err());
+    assert_eq!(client.try_store_vk(&zero_vk(&env, 3)), Err(Ok(Groth16Error::NotInitialized)));
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "init",
+                args: (admin.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .init(&admin);
+    assert_eq!(client.try_init(&admin), Err(Ok(Groth16Error::AlreadyInitialized)));
+}
+
+/// Deprecated keys verify through their last ledger, then fail with VkExpired; revoked keys
+/// fail with VkRevoked and cannot be moved back. list_vks reports each key's state.
+#[test]
+fn test_vk_lifecycle() {
+    let env = Env::default();
+    let client = new_verifier(&env);
+    let old = client.store_vk(&zero_vk(&env, 11));
+    let new = client.store_vk(&zero_vk(&env, 13));
+    let proof = BytesN::from_array(&env, &[0u8; 256]);
+    assert_eq!(client.get_vk_state(&old), Some(VkState::Active));
+
+    let last_ledger = env.ledger().sequence() + 5;
+    client.deprecate_vk(&old, &last_ledger);
+    env.ledger().set_sequence_number(last_ledger);
+    assert!(client.verify(&proof, &inputs(&env, 10), &old));
+    env.ledger().set_sequence_number(last_ledger + 1);
+    assert_eq!(
+        client.try_verify(&proof, &inputs(&env, 10), &old),
+        Err(Ok(Groth16Error::VkExpired))
+    );
+    assert!(client.verify(&proof, &inputs(&env, 12), &new));
+
+    client.revoke_vk(&old);
+    assert_eq!(
+        client.try_verify(&proof, &inputs(&env, 10), &old),
+        Err(Ok(Groth16Error::VkRevoked))
+    );
+    assert_eq!(client.try_deprecate_vk(&old, &u32::MAX), Err(Ok(Groth16Error::VkRevoked)));
+    assert_eq!(
+        client.try_revoke_vk(&BytesN::from_array(&env, &[7u8; 32])),
+        Err(Ok(Groth16Error::InvalidVerificationKey))
+    );
+    assert_eq!(
+        client.list_vks(),
+        vec![
+            &env,
+            VkInfo { vk_hash: old, state: VkState::Revoked },
+            VkInfo { vk_hash: new, state: VkState::Active },
+        ]
+    );
+}
+
+/// The stored key is the validated key as given; a real proof verifies against it and an
+/// altered input does not.
+#[test]
+fn test_stored_vk_verifies_real_proof() {
+    let env = Env::default();
+    let client = new_verifier(&env);
+    let (vk, proof, signals) = v2_fixture(&env);
+    let proof = packed_proof(&env, &proof);
+    let hash = client.store_vk(&vk);
+
+    let stored = client.get_vk(&hash).unwrap();
+    assert_eq!(vk_hash(&env, &stored), hash);
+    assert!(client.verify(&proof, &signals, &hash));
+
+    let mut tampered = signals.clone();
+    tampered.set(2, BytesN::from_array(&env, &[1u8; 32]));
+    assert!(!client.verify(&proof, &tampered, &hash));
+}
+
+/// Soroban's per-transaction CPU limit.
+const TX_CPU_LIMIT: u64 = 100_000_000;
+
+/// Host cost of a full `verify` call against the four-pair check it is built around. The pairing
+/// check cannot shrink (see `verify`), so it stays the dominant cost: the public-input MSM,
+/// storage reads and events add less than another pairing check's worth, and the whole call
+/// (about 43M instructions for the 11-signal fixture) fits well inside one transaction.
+#[test]
+fn test_verify_budget() {
+    let env = Env::default();
+    let client = new_verifier(&env);
+    let (vk, proof, signals) = v2_fixture(&env);
+    let hash = client.store_vk(&vk);
+
+    let a = Bn254G1Affine::from_bytes(proof.a.clone());
+    let b = Bn254G2Affine::from_bytes(proof.b.clone());
+    let c = Bn254G1Affine::from_bytes(proof.c.clone());
+    let bn = env.crypto().bn254();
+    let mut vk_x = Bn254G1Affine::from_bytes(vk.ic.get(0).unwrap());
+    for (i, sig) in signals.iter().enumerate() {
+        let ic_pt = Bn254G1Affine::from_bytes(vk.ic.get((i + 1) as u32).unwrap());
+        vk_x = bn.g1_add(&vk_x, &bn.g1_mul(&ic_pt, &Fr::from_bytes(sig)));
+    }
+    let mut budget = env.cost_estimate().budget();
+
+    budget.reset_default();
+    assert!(bn.pairing_check(
+        vec![&env, a.neg(), Bn254G1Affine::from_bytes(vk.alpha.clone()), vk_x, c],
+        vec![
+            &env,
+            b,
+            Bn254G2Affine::from_bytes(vk.beta.clone()),
+            Bn254G2Affine::from_bytes(vk.gamma.clone()),
+            Bn254G2Affine::from_bytes(vk.delta.clone()),
+        ],
+    ));
+    let pairing_cpu = budget.cpu_instruction_cost();
+
+    let proof = packed_proof(&env, &proof);
+    budget.reset_default();
+    assert!(client.verify(&proof, &signals, &hash));
+    let verify_cpu = budget.cpu_instruction_cost();
+
+    assert!(verify_cpu > pairing_cpu);
+    assert!(verify_cpu < 2 * pairing_cpu);
+    assert!(verify_cpu < TX_CPU_LIMIT / 2);
+}
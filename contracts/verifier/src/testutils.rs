@@ -0,0 +1,13 @@
+//! Test helpers for contracts that call the verifier (feature `testutils`).
+
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+use crate::{Verifier, VerifierClient};
+
+/// Verifier with an admin set; auths are mocked so store_vk can be called directly.
+pub fn new_verifier(env: &Env) -> VerifierClient<'_> {
+    env.mock_all_auths();
+    let verifier = VerifierClient::new(env, &env.register(Verifier, ()));
+    verifier.init(&Address::generate(env));
+    verifier
+}
@@ -0,0 +1,400 @@
+//! BN254 point validation for untrusted proof and VK bytes.
+//! The host traps on a malformed point inside `Bn254G1Affine::from_bytes` / `Bn254G2Affine::from_bytes`;
+//! these checks run first so verifiers can return a typed error instead. They mirror the host
+//! rules: flag bits unset, all-zero = infinity, coordinates < p, on curve, G2 in the r-torsion.
+
+/// p = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47 (little-endian limbs).
+const MODULUS: [u64; 4] = [
+    0x3c208c16d87cfd47,
+    0x97816a916871ca8d,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+];
+/// -p^{-1} mod 2^64.
+const INV: u64 = 0x87d20782e4866389;
+/// R^2 mod p, R = 2^256.
+const R2: [u64; 4] = [
+    0xf32cfc5b538afa89,
+    0xb5e71911d44501fb,
+    0x47ab1eff0a417ff6,
+    0x06d89f71cab8351f,
+];
+/// G1: y^2 = x^3 + 3 (Montgomery form of 3).
+const B: Fp = Fp([
+    0x7a17caa950ad28d7,
+    0x1f6ac17ae15521b9,
+    0x334bea4e696bd284,
+    0x2a1f6744ce179d8e,
+]);
+/// G2 (twist): y^2 = x^3 + 3 / (9 + u).
+const B2: Fp2 = Fp2 {
+    c0: Fp([
+        0x3bf938e377b802a8,
+        0x020b1b273633535d,
+        0x26b7edf049755260,
+        0x2514c6324384a86d,
+    ]),
+    c1: Fp([
+        0x38e7ecccd1dcff67,
+        0x65f0b37d93ce0d3e,
+        0xd749d0dd22ac00aa,
+        0x0141b9ce4a688d4d,
+    ]),
+};
+/// psi(x, y) = (conj(x) * PSI_X, conj(y) * PSI_Y), with PSI_X = (9+u)^((p-1)/3), PSI_Y = (9+u)^((p-1)/2).
+const PSI_X: Fp2 = Fp2 {
+    c0: Fp([
+        0xb5773b104563ab30,
+        0x347f91c8a9aa6454,
+        0x7a007127242e0991,
+        0x1956bcd8118214ec,
+    ]),
+    c1: Fp([
+        0x6e849f1ea0aa4757,
+        0xaa1c7b6d89f89141,
+        0xb6e713cdfae0ca3a,
+        0x26694fbb4e82ebc3,
+    ]),
+};
+const PSI_Y: Fp2 = Fp2 {
+    c0: Fp([
+        0xe4bbdd0c2936b629,
+        0xbb30f162e133bacb,
+        0x31a9d1b6f9645366,
+        0x253570bea500f8dd,
+    ]),
+    c1: Fp([
+        0xa1d77ce45ffe77c7,
+        0x07affd117826d1db,
+        0x6d16bd27bb7edc6b,
+        0x2c87200285defecc,
+    ]),
+};
+/// 6x^2 for the BN parameter x = 4965661367192848881. A twist point Q is in G2 iff psi(Q) = [6x^2]Q
+/// (eprint 2022/352, section 4.3), which needs a 127-bit instead of a 254-bit scalar multiplication.
+const SIX_X_SQUARED: u128 = 0x6f4d8248eeb859fbf83e9682e87cfd46;
+
+#[inline(always)]
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+#[inline(always)]
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub((b as u128) + ((borrow >> 63) as u128));
+    (t as u64, (t >> 64) as u64)
+}
+
+#[inline(always)]
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) * (c as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+/// `a - p` if `a >= p`, else `a`. Input must be < 2p.
+fn reduce_once(a: [u64; 4]) -> [u64; 4] {
+    let (d0, b) = sbb(a[0], MODULUS[0], 0);
+    let (d1, b) = sbb(a[1], MODULUS[1], b);
+    let (d2, b) = sbb(a[2], MODULUS[2], b);
+    let (d3, b) = sbb(a[3], MODULUS[3], b);
+    if b == 0 {
+        [d0, d1, d2, d3]
+    } else {
+        a
+    }
+}
+
+/// Base field element in Montgomery form.
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct Fp([u64; 4]);
+
+impl Fp {
+    const ZERO: Fp = Fp([0, 0, 0, 0]);
+    /// Montgomery form of 1 (R mod p).
+    const ONE: Fp = Fp([
+        0xd35d438dc58f0d9d,
+        0x0a78eb28f5c70b3d,
+        0x666ea36f7879462c,
+        0x0e0a77c19a07df2f,
+    ]);
+
+    /// Big-endian 32 bytes; None unless the value is canonical (< p).
+    fn from_be(bytes: &[u8]) -> Option<Fp> {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let start = 32 - (i + 1) * 8;
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes[start..start + 8]);
+            *limb = u64::from_be_bytes(buf);
+        }
+        for i in (0..4).rev() {
+            if limbs[i] != MODULUS[i] {
+                return if limbs[i] < MODULUS[i] {
+                    Some(Fp(limbs).mul(&Fp(R2)))
+                } else {
+                    None
+                };
+            }
+        }
+        None
+    }
+
+    fn add(&self, o: &Fp) -> Fp {
+        // p < 2^254, so the sum fits in 256 bits.
+        let (d0, c) = adc(self.0[0], o.0[0], 0);
+        let (d1, c) = adc(self.0[1], o.0[1], c);
+        let (d2, c) = adc(self.0[2], o.0[2], c);
+        let (d3, _) = adc(self.0[3], o.0[3], c);
+        Fp(reduce_once([d0, d1, d2, d3]))
+    }
+
+    fn sub(&self, o: &Fp) -> Fp {
+        let (d0, b) = sbb(self.0[0], o.0[0], 0);
+        let (d1, b) = sbb(self.0[1], o.0[1], b);
+        let (d2, b) = sbb(self.0[2], o.0[2], b);
+        let (d3, b) = sbb(self.0[3], o.0[3], b);
+        if b == 0 {
+            return Fp([d0, d1, d2, d3]);
+        }
+        let (d0, c) = adc(d0, MODULUS[0], 0);
+        let (d1, c) = adc(d1, MODULUS[1], c);
+        let (d2, c) = adc(d2, MODULUS[2], c);
+        let (d3, _) = adc(d3, MODULUS[3], c);
+        Fp([d0, d1, d2, d3])
+    }
+
+    fn neg(&self) -> Fp {
+        Fp::ZERO.sub(self)
+    }
+
+    fn mul(&self, o: &Fp) -> Fp {
+        // CIOS Montgomery multiplication; p < 2^254 so the final carry fits in the top limb.
+        let (a, b) = (&self.0, &o.0);
+        let mut t = [0u64; 6];
+        for b_i in b.iter() {
+            let mut carry = 0u64;
+            for j in 0..4 {
+                let (lo, hi) = mac(t[j], a[j], *b_i, carry);
+                t[j] = lo;
+                carry = hi;
+            }
+            let (s, c) = adc(t[4], carry, 0);
+            t[4] = s;
+            t[5] = c;
+
+            let m = t[0].wrapping_mul(INV);
+            let (_, mut carry) = mac(t[0], m, MODULUS[0], 0);
+            for j in 1..4 {
+                let (lo, hi) = mac(t[j], m, MODULUS[j], carry);
+                t[j - 1] = lo;
+                carry = hi;
+            }
+            let (s, c) = adc(t[4], carry, 0);
+            t[3] = s;
+            t[4] = t[5] + c;
+        }
+        Fp(reduce_once([t[0], t[1], t[2], t[3]]))
+    }
+}
+
+/// c0 + c1 * u with u^2 = -1.
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct Fp2 {
+    c0: Fp,
+    c1: Fp,
+}
+
+impl Fp2 {
+    const ZERO: Fp2 = Fp2 {
+        c0: Fp::ZERO,
+        c1: Fp::ZERO,
+    };
+
+    /// Ethereum order: c1 || c0, each 32 bytes big-endian.
+    fn from_be(bytes: &[u8]) -> Option<Fp2> {
+        Some(Fp2 {
+            c1: Fp::from_be(&bytes[..32])?,
+            c0: Fp::from_be(&bytes[32..64])?,
+        })
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Fp2::ZERO
+    }
+
+    fn add(&self, o: &Fp2) -> Fp2 {
+        Fp2 {
+            c0: self.c0.add(&o.c0),
+            c1: self.c1.add(&o.c1),
+        }
+    }
+
+    fn sub(&self, o: &Fp2) -> Fp2 {
+        Fp2 {
+            c0: self.c0.sub(&o.c0),
+            c1: self.c1.sub(&o.c1),
+        }
+    }
+
+    fn double(&self) -> Fp2 {
+        self.add(self)
+    }
+
+    fn mul(&self, o: &Fp2) -> Fp2 {
+        let v0 = self.c0.mul(&o.c0);
+        let v1 = self.c1.mul(&o.c1);
+        let s = self.c0.add(&self.c1).mul(&o.c0.add(&o.c1));
+        Fp2 {
+            c0: v0.sub(&v1),
+            c1: s.sub(&v0).sub(&v1),
+        }
+    }
+
+    fn square(&self) -> Fp2 {
+        self.mul(self)
+    }
+
+    /// Frobenius map x -> x^p.
+    fn conjugate(&self) -> Fp2 {
+        Fp2 {
+            c0: self.c0,
+            c1: self.c1.neg(),
+        }
+    }
+}
+
+/// Twist point in Jacobian coordinates (x = X/Z^2, y = Y/Z^3); Z = 0 is infinity.
+#[derive(Copy, Clone)]
+struct G2Jacobian {
+    x: Fp2,
+    y: Fp2,
+    z: Fp2,
+}
+
+impl G2Jacobian {
+    const INFINITY: G2Jacobian = G2Jacobian {
+        x: Fp2::ZERO,
+        y: Fp2::ZERO,
+        z: Fp2::ZERO,
+    };
+
+    fn double(&self) -> G2Jacobian {
+        if self.z.is_zero() {
+            return *self;
+        }
+        // dbl-2009-l (a = 0)
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+        let d = self.x.add(&b).square().sub(&a).sub(&c).double();
+        let e = a.double().add(&a);
+        let f = e.square();
+        let x = f.sub(&d.double());
+        let y = e.mul(&d.sub(&x)).sub(&c.double().double().double());
+        let z = self.y.mul(&self.z).double();
+        G2Jacobian { x, y, z }
+    }
+
+    fn add(&self, o: &G2Jacobian) -> G2Jacobian {
+        if self.z.is_zero() {
+            return *o;
+        }
+        if o.z.is_zero() {
+            return *self;
+        }
+        // add-2007-bl
+        let z1z1 = self.z.square();
+        let z2z2 = o.z.square();
+        let u1 = self.x.mul(&z2z2);
+        let u2 = o.x.mul(&z1z1);
+        let s1 = self.y.mul(&o.z).mul(&z2z2);
+        let s2 = o.y.mul(&self.z).mul(&z1z1);
+        let h = u2.sub(&u1);
+        let r = s2.sub(&s1).double();
+        if h.is_zero() {
+            return if r.is_zero() {
+                self.double()
+            } else {
+                G2Jacobian::INFINITY
+            };
+        }
+        let i = h.double().square();
+        let j = h.mul(&i);
+        let v = u1.mul(&i);
+        let x = r.square().sub(&j).sub(&v.double());
+        let y = r.mul(&v.sub(&x)).sub(&s1.mul(&j).double());
+        let z = self.z.add(&o.z).square().sub(&z1z1).sub(&z2z2).mul(&h);
+        G2Jacobian { x, y, z }
+    }
+
+    fn mul(&self, k: u128) -> G2Jacobian {
+        let mut acc = G2Jacobian::INFINITY;
+        for i in (0..128 - k.leading_zeros()).rev() {
+            acc = acc.double();
+            if (k >> i) & 1 == 1 {
+                acc = acc.add(self);
+            }
+        }
+        acc
+    }
+
+    /// Equality with the affine point (x, y).
+    fn eq_affine(&self, x: &Fp2, y: &Fp2) -> bool {
+        if self.z.is_zero() {
+            return false;
+        }
+        let z2 = self.z.square();
+        self.x == x.mul(&z2) && self.y == y.mul(&z2.mul(&self.z))
+    }
+}
+
+/// Flag bits (top two bits of the first byte) must be unset; returns None on a set flag,
+/// Some(true) for the all-zero encoding of infinity.
+fn check_flags_and_infinity(bytes: &[u8]) -> Option<bool> {
+    if bytes[0] & 0b1100_0000 != 0 {
+        return None;
+    }
+    Some(bytes.iter().all(|b| *b == 0))
+}
+
+/// 64-byte G1 encoding x || y: flag bits unset, canonical coordinates, y^2 = x^3 + 3.
+/// G1 has cofactor 1, so no subgroup check is needed.
+pub fn g1_is_valid(bytes: &[u8; 64]) -> bool {
+    match check_flags_and_infinity(bytes) {
+        None => return false,
+        Some(true) => return true,
+        Some(false) => {}
+    }
+    let (Some(x), Some(y)) = (Fp::from_be(&bytes[..32]), Fp::from_be(&bytes[32..])) else {
+        return false;
+    };
+    y.mul(&y) == x.mul(&x).mul(&x).add(&B)
+}
+
+/// 128-byte G2 encoding x || y (each c1 || c0): flag bits unset, canonical coordinates,
+/// on the twist, and in the order-r subgroup.
+pub fn g2_is_valid(bytes: &[u8; 128]) -> bool {
+    match check_flags_and_infinity(bytes) {
+        None => return false,
+        Some(true) => return true,
+        Some(false) => {}
+    }
+    let (Some(x), Some(y)) = (Fp2::from_be(&bytes[..64]), Fp2::from_be(&bytes[64..])) else {
+        return false;
+    };
+    if y.square() != x.square().mul(&x).add(&B2) {
+        return false;
+    }
+    let q = G2Jacobian {
+        x,
+        y,
+        z: Fp2 {
+            c0: Fp::ONE,
+            c1: Fp::ZERO,
+        },
+    };
+    let psi_x = x.conjugate().mul(&PSI_X);
+    let psi_y = y.conjugate().mul(&PSI_Y);
+    q.mul(SIX_X_SQUARED).eq_affine(&psi_x, &psi_y)
+}
@@ -1,10 +1,13 @@
 //! Shared ZK types for Groth16 BN254 verification.
-//! Used by groth16_verifier and cosmic_coder. No duplicated definitions.
+//! Used by groth16_verifier, cosmic_coder and the v2 verifier/policy/application stack.
+//! No duplicated definitions.
 //! CAP-0074 (BN254); serialization: G1 = 64 bytes, G2 = 128 bytes, Fr = 32 bytes.
 
 #![no_std]
 
-use soroban_sdk::{contracterror, contracttype, BytesN, Vec};
+use soroban_sdk::{
+    contracterror, contracttype, crypto::bn254::Fr, xdr::ToXdr, Address, BytesN, Env, Vec,
+};
 
 /// BN254 G1 serialized size (Ethereum-compatible uncompressed).
 pub const G1_SIZE: usize = 64;
@@ -13,6 +16,12 @@ pub const G2_SIZE: usize = 128;
 /// BN254 scalar (Fr) serialized size.
 pub const FR_SIZE: usize = 32;
 
+mod curve;
+pub use curve::{g1_is_valid, g2_is_valid};
+
+#[cfg(feature = "testutils")]
+pub mod testutils;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -22,6 +31,33 @@ pub enum Groth16Error {
     InvalidPublicInputs = 2,
     /// vk.ic length must equal pub_signals.len() + 1
     MalformedVerifyingKey = 3,
+    /// verifier::store_vk: a key with this hash is already stored (slots are write-once).
+    VkAlreadyStored = 4,
+    /// verifier: no admin set yet (`init`).
+    NotInitialized = 5,
+    AlreadyInitialized = 6,
+    /// verifier: the key was revoked (see VkState).
+    VkRevoked = 7,
+    /// verifier: the key's deprecation window has ended.
+    VkExpired = 8,
+}
+
+/// Errors returned by the v2 policy contract (shared so the application can decode them).
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PolicyError {
+    VerifierNotSet = 1,
+    Replay = 2,
+    InvalidProof = 3,
+    VerifierError = 4,
+    InvalidInput = 5,
+    MalformedVk = 6,
+    DomainMismatch = 7,
+    UnsupportedNetwork = 8,
+    NotInitialized = 9,
+    AlreadyInitialized = 10,
+    ApplicationNotSet = 11,
 }
 
 #[derive(Clone)]
@@ -42,6 +78,58 @@ pub struct ZkVerificationKey {
     pub ic: Vec<BytesN<64>>,
 }
 
+/// Point checks for a proof: any encoding, curve or subgroup fault is `InvalidProof`.
+/// Run before `Bn254*Affine::from_bytes`, which would trap on the same input.
+pub fn validate_proof(proof: &ZkProof) -> Result<(), Groth16Error> {
+    if g1_is_valid(&proof.a.to_array())
+        && g2_is_valid(&proof.b.to_array())
+        && g1_is_valid(&proof.c.to_array())
+    {
+        Ok(())
+    } else {
+        Err(Groth16Error::InvalidProof)
+    }
+}
+
+/// Point checks for every VK point (alpha, beta, gamma, delta, ic); faults are `InvalidVerificationKey`.
+pub fn validate_vk(vk: &ZkVerificationKey) -> Result<(), Groth16Error> {
+    let g2_ok = [&vk.beta, &vk.gamma, &vk.delta]
+        .iter()
+        .all(|p| g2_is_valid(&p.to_array()));
+    if g1_is_valid(&vk.alpha.to_array()) && g2_ok && vk.ic.iter().all(|p| g1_is_valid(&p.to_array())) {
+        Ok(())
+    } else {
+        Err(Groth16Error::InvalidVerificationKey)
+    }
+}
+
+/// Lifecycle of a registered verification key. `Deprecated(last_ledger)` keeps the key usable
+/// up to and including `last_ledger` so clients can migrate to its successor; revocation is final.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VkState {
+    Active,
+    Deprecated(u32),
+    Revoked,
+}
+
+impl VkState {
+    /// Whether proofs against the key are accepted at `ledger`.
+    pub fn is_usable_at(&self, ledger: u32) -> bool {
+        match self {
+            VkState::Active => true,
+            VkState::Deprecated(last_ledger) => ledger <= *last_ledger,
+            VkState::Revoked => false,
+        }
+    }
+}
+
+/// Canonical verification key hash: sha256 of the key's XDR (an ScVal map of its fields, so
+/// one key has exactly one encoding). The handle used by verifier::store_vk and CosmicCoder::register_vk.
+pub fn vk_hash(env: &Env, vk: &ZkVerificationKey) -> BytesN<32> {
+    env.crypto().sha256(&vk.clone().to_xdr(env)).into()
+}
+
 /// Domain binding for v2 ZK proofs.
 #[derive(Clone)]
 #[contracttype]
@@ -54,7 +142,8 @@ pub struct DomainBinding {
 }
 
 /// Public inputs order matching GameRunV2.circom outputs.
-/// Used to map between circuit and contracts.
+/// Used to map between circuit and contracts; `encode_public_inputs` gives the signal vector.
+/// `player_address` / `contract_id` hold `address_digest` values.
 #[derive(Clone)]
 #[contracttype]
 pub struct ZkPublicInputs {
@@ -69,3 +158,63 @@ pub struct ZkPublicInputs {
     pub contract_id: BytesN<32>,
     pub domain_separator: BytesN<32>,
 }
+
+/// sha256 of the address ScVal XDR; the value carried in ZkPublicInputs address fields.
+/// Off-chain: `sha256(Address.fromString(addr).toScVal().toXDR())`.
+pub fn address_digest(env: &Env, address: &Address) -> BytesN<32> {
+    env.crypto().sha256(&address.clone().to_xdr(env)).into()
+}
+
+/// Address commitment used in public signals: address_digest split into (hi, lo)
+/// 128-bit halves, each a 32-byte big-endian Fr.
+pub fn address_commitment(env: &Env, address: &Address) -> (BytesN<32>, BytesN<32>) {
+    split_signal(env, &address_digest(env, address))
+}
+
+/// Number of GameRunV2 public signals (addresses are split into hi/lo halves).
+pub const GAMERUN_V2_PUB_SIGNALS: u32 = 12;
+
+/// Big-endian 32-byte field encoding of an integer public input.
+fn int_signal(env: &Env, v: u64) -> BytesN<32> {
+    let mut out = [0u8; FR_SIZE];
+    out[24..].copy_from_slice(&v.to_be_bytes());
+    BytesN::from_array(env, &out)
+}
+
+/// Canonical Fr encoding: values >= r are reduced, as circom does with its inputs.
+fn field_signal(value: &BytesN<32>) -> BytesN<32> {
+    Fr::from_bytes(value.clone()).to_bytes()
+}
+
+/// (hi, lo) 128-bit halves of a 32-byte digest, each as a 32-byte big-endian Fr.
+fn split_signal(env: &Env, digest: &BytesN<32>) -> (BytesN<32>, BytesN<32>) {
+    let digest = digest.to_array();
+    let mut hi = [0u8; FR_SIZE];
+    let mut lo = [0u8; FR_SIZE];
+    hi[16..].copy_from_slice(&digest[..16]);
+    lo[16..].copy_from_slice(&digest[16..]);
+    (BytesN::from_array(env, &hi), BytesN::from_array(env, &lo))
+}
+
+/// The exact public signals GameRunV2 emits for `inputs`, in circuit order:
+/// [run_hash_hi, run_hash_lo, score, wave, nonce, season_id, challenge_id,
+///  player_hi, player_lo, contract_hi, contract_lo, domain_separator].
+pub fn encode_public_inputs(env: &Env, inputs: &ZkPublicInputs) -> Vec<BytesN<32>> {
+    let (player_hi, player_lo) = split_signal(env, &inputs.player_address);
+    let (contract_hi, contract_lo) = split_signal(env, &inputs.contract_id);
+    soroban_sdk::vec![
+        env,
+        field_signal(&inputs.run_hash_hi),
+        field_signal(&inputs.run_hash_lo),
+        int_signal(env, inputs.score as u64),
+        int_signal(env, inputs.wave as u64),
+        int_signal(env, inputs.nonce),
+        int_signal(env, inputs.season_id as u64),
+        int_signal(env, inputs.challenge_id as u64),
+        player_hi,
+        player_lo,
+        contract_hi,
+        contract_lo,
+        field_signal(&inputs.domain_separator),
+    ]
+}
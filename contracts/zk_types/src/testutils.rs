@@ -0,0 +1,98 @@
+//! Test helpers shared by the contract test suites (feature `testutils`).
+//! The GameRunV2 fixture (circuits/build/gamerun_v2_contract_proof.json) is a real proof for
+//! circuits/build/gamerun_v2_input.json (`node scripts/zk/generate_proof_v2.js`);
+//! gamerun_v2_contract_proof_2.json is a second one for gamerun_v2_input_2.json (other score,
+//! wave and nonce), so batches can mix distinct proofs under the same key.
+
+extern crate std;
+
+use soroban_sdk::{Bytes, BytesN, Env, Vec};
+
+use crate::{ZkProof, ZkVerificationKey, FR_SIZE, G1_SIZE, G2_SIZE};
+
+pub const V2_FIXTURE: &str =
+    include_str!("../../../circuits/build/gamerun_v2_contract_proof.json");
+pub const V2_FIXTURE_2: &str =
+    include_str!("../../../circuits/build/gamerun_v2_contract_proof_2.json");
+/// Addresses and run hash the fixture input commits to.
+pub const V2_PLAYER: &str = "GAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSABOV";
+pub const V2_CONTRACT: &str = "CDGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZLND";
+pub const V2_RUN_HASH: &str = "5929a8e809d699caa979c1791b699a59446a3b68fdc4cb096e6ceb5380df76be";
+
+pub fn hex_bytes(hex: &str) -> std::vec::Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+pub fn hex_n<const N: usize>(env: &Env, v: &serde_json::Value) -> BytesN<N> {
+    let bytes = hex_bytes(v.as_str().unwrap());
+    BytesN::from_array(env, &bytes.try_into().unwrap())
+}
+
+/// VK made of points at infinity: the pairing check holds for any inputs.
+pub fn zero_vk(env: &Env, ic_len: u32) -> ZkVerificationKey {
+    let g1 = BytesN::from_array(env, &[0u8; G1_SIZE]);
+    let g2 = BytesN::from_array(env, &[0u8; G2_SIZE]);
+    let mut ic = Vec::new(env);
+    for _ in 0..ic_len {
+        ic.push_back(g1.clone());
+    }
+    ZkVerificationKey {
+        alpha: g1,
+        beta: g2.clone(),
+        gamma: g2.clone(),
+        delta: g2,
+        ic,
+    }
+}
+
+/// Real GameRunV2 (vk, proof, pub_signals) from the fixture.
+pub fn v2_fixture(env: &Env) -> (ZkVerificationKey, ZkProof, Vec<BytesN<FR_SIZE>>) {
+    fixture_from_json(env, V2_FIXTURE)
+}
+
+/// Second real GameRunV2 proof and its pub_signals; same VK as [`v2_fixture`].
+pub fn v2_fixture_2(env: &Env) -> (ZkProof, Vec<BytesN<FR_SIZE>>) {
+    let (_, proof, signals) = fixture_from_json(env, V2_FIXTURE_2);
+    (proof, signals)
+}
+
+fn fixture_from_json(
+    env: &Env,
+    src: &str,
+) -> (ZkVerificationKey, ZkProof, Vec<BytesN<FR_SIZE>>) {
+    let json: serde_json::Value = serde_json::from_str(src).unwrap();
+    let (p, vk) = (&json["proof"], &json["vk"]);
+    let mut ic = Vec::new(env);
+    for pt in vk["ic"].as_array().unwrap() {
+        ic.push_back(hex_n(env, pt));
+    }
+    let vk = ZkVerificationKey {
+        alpha: hex_n(env, &vk["alpha"]),
+        beta: hex_n(env, &vk["beta"]),
+        gamma: hex_n(env, &vk["gamma"]),
+        delta: hex_n(env, &vk["delta"]),
+        ic,
+    };
+    let proof = ZkProof {
+        a: hex_n(env, &p["a"]),
+        b: hex_n(env, &p["b"]),
+        c: hex_n(env, &p["c"]),
+    };
+    let mut signals = Vec::new(env);
+    for s in json["pub_signals"].as_array().unwrap() {
+        signals.push_back(hex_n(env, s));
+    }
+    (vk, proof, signals)
+}
+
+/// Proof packed as a||b||c, the form the v2 verifier takes.
+pub fn packed_proof(env: &Env, proof: &ZkProof) -> BytesN<256> {
+    let mut packed = Bytes::new(env);
+    packed.append(&proof.a.clone().into());
+    packed.append(&proof.b.clone().into());
+    packed.append(&proof.c.clone().into());
+    packed.try_into().unwrap()
+}
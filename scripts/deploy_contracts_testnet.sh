@@ -36,8 +36,8 @@ if [ -z "$SOURCE_ACCOUNT" ]; then
   echo "# Save the returned ID as VERIFIER_ID"
   echo "stellar contract deploy --source-account <SOURCE> --network testnet --wasm target/wasm32v1-none/release/cosmic_coder.wasm"
   echo "# Save the returned ID as POLICY_ID"
-  echo "stellar contract invoke --id <POLICY_ID> --source-account <SOURCE> --network testnet -- init --game_hub $GAME_HUB"
-  echo "stellar contract invoke --id <POLICY_ID> --source-account <SOURCE> --network testnet -- set_verifier --verifier <VERIFIER_ID>"
+  echo "# init requires the admin's signature, so <ADMIN_ADDRESS> must be the <SOURCE> account"
+  echo "stellar contract invoke --id <POLICY_ID> --source-account <SOURCE> --network testnet -- init --admin <ADMIN_ADDRESS> --game_hub $GAME_HUB --zk_verifier <VERIFIER_ID>"
   echo ""
   echo "Then add to .env: VITE_COSMIC_CODER_CONTRACT_ID=<POLICY_ID>"
   exit 0
@@ -53,12 +53,22 @@ echo "Deploying policy..."
 POLICY_ID=$(stellar contract deploy --source-account "$SOURCE_ACCOUNT" --network testnet --wasm target/wasm32v1-none/release/cosmic_coder.wasm 2>&1 | tee /dev/stderr | tail -1)
 echo "POLICY_ID=$POLICY_ID"
 
-echo "Initing policy with Game Hub..."
-stellar contract invoke --id "$POLICY_ID" --source-account "$SOURCE_ACCOUNT" --network testnet -- init --game_hub "$GAME_HUB"
+ADMIN_ADDRESS=$(stellar keys address "$SOURCE_ACCOUNT" 2>/dev/null || echo "$SOURCE_ACCOUNT")
 
-echo "Setting verifier..."
-stellar contract invoke --id "$POLICY_ID" --source-account "$SOURCE_ACCOUNT" --network testnet -- set_verifier --verifier "$VERIFIER_ID"
+echo "Initing policy with admin, Game Hub and verifier (init can only run once)..."
+stellar contract invoke --id "$POLICY_ID" --source-account "$SOURCE_ACCOUNT" --network testnet -- init --admin "$ADMIN_ADDRESS" --game_hub "$GAME_HUB" --zk_verifier "$VERIFIER_ID"
 
+echo ""
+echo "submit_zk_noir needs its own UltraHonk verifier slot (init only sets Groth16 and WeaponUnlock):"
+echo "  stellar contract invoke --id $POLICY_ID --source-account $SOURCE_ACCOUNT --network testnet -- set_verifier --kind UltraHonk --verifier <ULTRAHONK_VERIFIER_ID>"
+echo "  then store the GameRun VK and select it: node scripts/zk/set_verifier_vk.js <ULTRAHONK_VERIFIER_ID>"
+
+echo ""
+echo "Ranked submissions need an open season (the frontend submits to season 1):"
+echo "  stellar contract invoke --id $POLICY_ID --source-account $SOURCE_ACCOUNT --network testnet -- create_season --season_id 1 --start_ledger <LEDGER> --end_ledger <LEDGER>"
+echo ""
+echo "Weapon unlocks need catalog entries (one per weapon in src/config/weapons.js), e.g.:"
+echo "  stellar contract invoke --id $POLICY_ID --source-account $SOURCE_ACCOUNT --network testnet -- set_weapon --weapon '{\"id\":2,\"name\":\"Shotgun\",\"threshold\":1000,\"vk_hash\":null,\"enabled\":true,\"season_id\":null}'"
 echo ""
 echo "Done. Add to .env:"
 echo "  VITE_COSMIC_CODER_CONTRACT_ID=$POLICY_ID"
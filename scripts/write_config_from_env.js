@@ -18,6 +18,7 @@ const CONFIG_KEYS = [
   'VITE_COSMIC_CODER_CONTRACT_ID',
   'VITE_GAME_HUB_CONTRACT_ID',
   'VITE_ZK_PROVER_URL',
+  'VITE_SKILLPROOF_VK_HASH',
   'VITE_PROGRESS_API_URL',
   'VITE_LEADERBOARD_URL',
   'VITE_API_URL',
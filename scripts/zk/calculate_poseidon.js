@@ -22,7 +22,9 @@ const input = {
   wallet: String(wallet),
   nonce: String(nonce),
   threshold: '0',
-  publicHash: '0'
+  publicHash: '0',
+  player_hi: '0',
+  player_lo: '0'
 };
 
 const inputPath = path.join(BUILD_DIR, 'poseidon_input.json');
@@ -32,14 +32,18 @@ const pub_signals = data.pub_signals;
 
 console.log(JSON.stringify({
   verify_proof: { vk, proof, pub_signals },
+  // Admin registers the key once; submit_zk takes the returned vk_hash.
+  register_vk: { circuit: 'GameRun', vk },
+  // `run` is a RankedRun; the caller adds player and session_id (from start_match).
   submit_zk: {
+    run: {
+      run_hash: pub_signals[0], // 32 bytes (64 hex chars); matches first public signal run_hash_hi
+      score: parseInt(pub_signals[2], 16),
+      wave: parseInt(pub_signals[3], 16),
+      nonce: BigInt('0x' + pub_signals[4]).toString(),
+      season_id: parseInt(pub_signals[5], 16),
+    },
     proof,
-    vk,
     pub_signals,
-    run_hash_hex: pub_signals[0], // 32 bytes (64 hex chars); matches first public signal run_hash_hi
-    score: parseInt(pub_signals[2], 16),
-    wave: parseInt(pub_signals[3], 16),
-    nonce: BigInt('0x' + pub_signals[4]).toString(),
-    season_id: parseInt(pub_signals[5], 16),
   },
 }, null, 2));
@@ -62,9 +62,9 @@ export function exportPubSignals(publicPath) {
   return pub.map((s) => toBytes32BE(s));
 }
 
-export function exportAll(buildDir, outPath) {
+export function exportAll(buildDir, outPath, vkeyFile = 'vkey.json') {
   const proof = exportProof(path.join(buildDir, 'proof.json'));
-  const vk = exportVk(path.join(buildDir, 'vkey.json'));
+  const vk = exportVk(path.join(buildDir, vkeyFile));
   const pub_signals = exportPubSignals(path.join(buildDir, 'public.json'));
   const out = {
     proof: {
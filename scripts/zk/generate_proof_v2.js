@@ -46,5 +46,6 @@ execSync(
 
 console.log('Exporting for contract...');
 const outPath = path.join(buildDir, 'contract_proof.json');
-exportAll(buildDir, outPath);
+// GameRunV2 has its own key (13 IC points); vkey.json belongs to GameRun.
+exportAll(buildDir, outPath, `${CIRCUIT_NAME}_vkey.json`);
 console.log('Done. Contract-ready proof:', outPath);
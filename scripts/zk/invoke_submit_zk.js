@@ -1,7 +1,9 @@
 #!/usr/bin/env node
 /**
  * Invoke submit_zk on the Cosmic Coder contract with the generated proof.
- * Usage: node invoke_submit_zk.js [contract_proof.json] [policy_contract_id]
+ * Usage: node invoke_submit_zk.js [contract_proof.json] [policy_contract_id] [network] [source] [vk_hash]
+ * vk_hash is the value returned by the admin's register_vk call for the GameRun key.
+ * Opens a session for the player (start_match) and submits against it.
  */
 import fs from 'fs';
 import path from 'path';
@@ -14,6 +16,7 @@ const proofPath = process.argv[2] || path.join(ROOT, 'circuits/build/contract_pr
 const contractId = process.argv[3] || process.env.POLICY_CONTRACT_ID;
 const network = process.argv[4] || 'testnet';
 const source = process.argv[5] || 'testnet-user';
+const vkHash = process.argv[6] || process.env.GAMERUN_VK_HASH;
 
 if (!contractId) {
   console.error('Usage: node invoke_submit_zk.js [contract_proof.json] <policy_contract_id> [network] [source]');
@@ -21,8 +24,14 @@ if (!contractId) {
   process.exit(1);
 }
 
+if (!vkHash) {
+  console.error('Missing GameRun vk_hash (6th arg or GAMERUN_VK_HASH). Register the VK first:');
+  console.error('  stellar contract invoke --id <POLICY_ID> --source-account <ADMIN> --network testnet -- register_vk --circuit GameRun --vk \'<vk json>\'');
+  process.exit(1);
+}
+
 const json = JSON.parse(fs.readFileSync(proofPath, 'utf8'));
-const { proof, vk, pub_signals } = json;
+const { proof, pub_signals } = json;
 
 // Get player address from source account
 let playerAddress;
@@ -34,14 +43,6 @@ try {
 }
 
 // Build the JSON arguments for stellar CLI (using hex format for bytes)
-const vkArg = {
-  alpha: vk.alpha,
-  beta: vk.beta,
-  gamma: vk.gamma,
-  delta: vk.delta,
-  ic: vk.ic
-};
-
 const proofArg = {
   a: proof.a,
   b: proof.b,
@@ -68,6 +69,30 @@ console.log(`Player: ${playerAddress}`);
 console.log(`Score: ${score}, Wave: ${wave}, Nonce: ${nonce}, Season: ${seasonId}`);
 console.log('');
 
+// submit_zk must reference the player's open Game Hub session
+let sessionId;
+try {
+  sessionId = execSync(
+    `stellar contract invoke --id ${contractId} --source-account ${source} --network ${network} -- start_match --player ${playerAddress}`,
+    { encoding: 'utf8' }
+  ).trim();
+} catch (e) {
+  console.error('start_match failed:', e.message);
+  process.exit(1);
+}
+console.log(`Session: ${sessionId}`);
+
+// RankedRun claimed by the proof (cosmic_coder::RankedRun)
+const run = {
+  player: playerAddress,
+  session_id: Number(sessionId),
+  nonce,
+  run_hash: runHash,
+  season_id: seasonId,
+  score,
+  wave,
+};
+
 // Build and execute the command
 const cmd = `stellar contract invoke \
   --id ${contractId} \
@@ -75,15 +100,10 @@ const cmd = `stellar contract invoke \
   --network ${network} \
   -- \
   submit_zk \
-  --player ${playerAddress} \
+  --run '${JSON.stringify(run)}' \
   --proof '${JSON.stringify(proofArg)}' \
-  --vk '${JSON.stringify(vkArg)}' \
-  --pub_signals '${JSON.stringify(pub_signals)}' \
-  --nonce ${nonce} \
-  --run_hash ${runHash} \
-  --season_id ${seasonId} \
-  --score ${score} \
-  --wave ${wave}`;
+  --vk_hash ${vkHash} \
+  --pub_signals '${JSON.stringify(pub_signals)}'`;
 
 console.log('Executing...');
 try {
@@ -1,11 +1,15 @@
 #!/usr/bin/env node
 /**
- * One-time setup: call set_vk on the UltraHonk verifier contract with GameRun_vk.json.
- * Required after deploying the verifier so submit_zk_noir (which uses verify_proof_with_stored_vk) works.
+ * Store the Noir GameRun VK (binary output of `bb write_vk --oracle_hash keccak`,
+ * circuits/noir/target/vk) in the UltraHonk verifier under id { circuit: "gamerun", version }.
+ * set_vk is admin-only (init the verifier once with the admin), parses the key, rejects anything
+ * malformed and never overwrites an id: a new circuit build gets the next version.
+ * Then point Cosmic Coder at that id with set_ultrahonk_vk; submit_zk_noir calls
+ * verify_proof_with_stored_vk(caller, vk_id, public_inputs, proof, reject_seen).
  *
  * Usage:
- *   ZK_VERIFIER_CONTRACT_ID=<verifier_id> node scripts/zk/set_verifier_vk.js
- *   node scripts/zk/set_verifier_vk.js <verifier_id>
+ *   ZK_VERIFIER_CONTRACT_ID=<verifier_id> node scripts/zk/set_verifier_vk.js [version]
+ *   node scripts/zk/set_verifier_vk.js <verifier_id> [version]
  *
  * Verifier ID is the contract you passed to Cosmic Coder init as --zk_verifier
  * (e.g. CASQNBAV6ZX2DXVUIF2FHBAX3LFKTRNQ7PZ4IRPZOBXK2276KKZ3LV2Y).
@@ -18,7 +22,9 @@ const __dirname = path.dirname(fileURLToPath(import.meta.url));
 const ROOT = path.resolve(__dirname, '../..');
 
 const verifierId = process.argv[2] || process.env.ZK_VERIFIER_CONTRACT_ID;
-const vkPath = path.join(ROOT, 'public/circuits/GameRun_vk.json');
+const version = Number(process.argv[3] || process.env.ZK_VK_VERSION || 1);
+const vkId = JSON.stringify({ circuit: 'gamerun', version });
+const vkPath = path.join(ROOT, 'circuits/noir/target/vk');
 
 if (!verifierId) {
   console.error('Usage: node set_verifier_vk.js <verifier_contract_id>');
@@ -31,19 +37,23 @@ if (!fs.existsSync(vkPath)) {
   process.exit(1);
 }
 
-const vkJson = fs.readFileSync(vkPath, 'utf8');
-const vkBytes = new TextEncoder().encode(vkJson);
-const vkBase64 = Buffer.from(vkBytes).toString('base64');
+const vkBytes = fs.readFileSync(vkPath);
+const vkHex = vkBytes.toString('hex');
 
-console.log('=== Set VK on UltraHonk verifier (one-time) ===');
+console.log('=== Set VK on UltraHonk verifier ===');
 console.log('Verifier:', verifierId);
+console.log('VK id:', vkId);
 console.log('VK size:', vkBytes.length, 'bytes');
 console.log('');
-console.log('Run this (Stellar CLI):');
+console.log('Once per verifier deployment:');
+console.log(`stellar contract invoke --id ${verifierId} --source testnet-user --network testnet -- init --admin <ADMIN>`);
 console.log('');
-console.log(`stellar contract invoke --id ${verifierId} --source testnet-user --network testnet -- set_vk --vk_json ${vkBase64}`);
+console.log('Run this (Stellar CLI, as the admin):');
+console.log('');
+console.log(`stellar contract invoke --id ${verifierId} --source testnet-user --network testnet -- set_vk --vk_id '${vkId}' --vk_bytes ${vkHex}`);
 console.log('');
 console.log('Or with VK from file (if your CLI supports it):');
-console.log(`stellar contract invoke --id ${verifierId} --source testnet-user --network testnet -- set_vk --vk_json "$(cat ${vkPath} | base64)"`);
+console.log(`stellar contract invoke --id ${verifierId} --source testnet-user --network testnet -- set_vk --vk_id '${vkId}' --vk_bytes "$(xxd -p ${vkPath} | tr -d '\\n')"`);
 console.log('');
-console.log('After this, submit_zk_noir will use verify_proof_with_stored_vk(proof_blob) only.');
+console.log('Then select it on Cosmic Coder (admin):');
+console.log(`stellar contract invoke --id <POLICY_ID> --source testnet-user --network testnet -- set_ultrahonk_vk --vk_id '${vkId}'`);
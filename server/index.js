@@ -260,10 +260,10 @@ app.post('/leaderboard', async (req, res) => {
 });
 
 app.post('/zk/prove', (req, res) => {
-  const { run_hash_hex, score, wave, nonce, season_id, used_zk_weapon } = req.body || {};
-  if (!run_hash_hex || score == null || wave == null || nonce == null) {
+  const { run_hash_hex, score, wave, nonce, season_id, used_zk_weapon, player_address, contract_id } = req.body || {};
+  if (!run_hash_hex || score == null || wave == null || nonce == null || !player_address || !contract_id) {
     return res.status(400).json({
-      error: 'Missing required fields: run_hash_hex, score, wave, nonce. season_id optional (default 1).'
+      error: 'Missing required fields: run_hash_hex, score, wave, nonce, player_address, contract_id. season_id optional (default 1).'
     });
   }
   try {
@@ -273,7 +273,9 @@ app.post('/zk/prove', (req, res) => {
       wave: Number(wave),
       nonce: Number(nonce),
       season_id: season_id != null ? Number(season_id) : 1,
-      used_zk_weapon: used_zk_weapon != null ? Number(used_zk_weapon) : 0
+      used_zk_weapon: used_zk_weapon != null ? Number(used_zk_weapon) : 0,
+      player_address,
+      contract_id
     });
     res.status(200).json(payload);
   } catch (err) {
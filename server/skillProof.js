@@ -7,6 +7,7 @@ import { execSync } from 'child_process';
 import fs from 'fs';
 import path from 'path';
 import { fileURLToPath } from 'url';
+import { addressCommitmentParts } from './zkProve.js';
 
 const __dirname = path.dirname(fileURLToPath(import.meta.url));
 const ROOT = path.resolve(__dirname, '..');
@@ -31,7 +32,8 @@ function walletToField(wallet) {
 
 /**
  * Build circuit input for skill proof.
- * The circuit computes publicHash = Poseidon(wallet, score, nonce)
+ * The circuit computes publicHash = Poseidon(wallet, score, nonce); player_hi / player_lo bind the
+ * proof to the wallet's address commitment, which unlock_weapon checks against the caller.
  * @param {{ score: number, wallet: string, nonce: number, threshold: number }} body
  */
 function buildInput(body) {
@@ -39,6 +41,7 @@ function buildInput(body) {
   const threshold = Math.max(0, Math.floor(Number(body.threshold) || 0));
   const nonce = BigInt(body.nonce != null ? body.nonce : Date.now());
   const wallet = walletToField(body.wallet);
+  const player = addressCommitmentParts(body.wallet);
   
   // For the initial input, we set publicHash to 0
   // The circuit will compute the actual hash and verify it
@@ -50,7 +53,9 @@ function buildInput(body) {
     wallet: String(wallet),
     nonce: String(nonce),
     threshold: String(threshold),
-    publicHash: '0' // Placeholder - will be computed
+    publicHash: '0', // Placeholder - will be computed
+    player_hi: player.hi,
+    player_lo: player.lo
   };
 }
 
@@ -8,15 +8,30 @@ import fs from 'fs';
 import path from 'path';
 import { fileURLToPath } from 'url';
 import crypto from 'crypto';
+import { Address } from '@stellar/stellar-sdk';
 
 const __dirname = path.dirname(fileURLToPath(import.meta.url));
 const ROOT = path.resolve(__dirname, '..');
 const BUILD_DIR = path.join(ROOT, 'circuits', 'build');
 
+/**
+ * Address commitment bound in GameRun public signals (mirrors zk_types::address_commitment):
+ * sha256(ScVal XDR of the address) split into 128-bit halves, as decimal strings.
+ * @param {string} strkey - G... account or C... contract address
+ */
+export function addressCommitmentParts(strkey) {
+  const digest = crypto.createHash('sha256').update(Address.fromString(strkey).toScVal().toXDR()).digest('hex');
+  return {
+    hi: BigInt('0x' + digest.slice(0, 32)).toString(),
+    lo: BigInt('0x' + digest.slice(32, 64)).toString()
+  };
+}
+
 /**
  * Build circuit input from prover request.
- * @param {{ run_hash_hex: string, score: number, wave: number, nonce: number, season_id: number }} body
+ * @param {{ run_hash_hex: string, score: number, wave: number, nonce: number, season_id: number, player_address: string, contract_id: string }} body
  * run_hash_hex: 64 hex chars (32 bytes). Split into hi (first 16 bytes) and lo (last 16 bytes) as decimal strings.
+ * player_address / contract_id: Stellar addresses the proof is bound to (submitting player, Cosmic Coder contract).
  */
 function buildInput(body) {
   const run_hash_hex = String(body.run_hash_hex || '').replace(/^0x/, '').padStart(64, '0').slice(0, 64);
@@ -30,6 +45,11 @@ function buildInput(body) {
   const nonce = BigInt(body.nonce != null ? body.nonce : 0).toString();
   const season_id = Math.max(0, Math.floor(Number(body.season_id) || 1));
   const used_zk_weapon = Number(body.used_zk_weapon) ? 1 : 0;
+  if (!body.player_address || !body.contract_id) {
+    throw new Error('player_address and contract_id are required (proofs are bound to both)');
+  }
+  const player = addressCommitmentParts(body.player_address);
+  const contract = addressCommitmentParts(body.contract_id);
   return {
     run_hash_hi,
     run_hash_lo,
@@ -37,13 +57,17 @@ function buildInput(body) {
     wave: String(wave),
     nonce,
     season_id: String(season_id),
-    used_zk_weapon: String(used_zk_weapon)
+    used_zk_weapon: String(used_zk_weapon),
+    player_address_hi: player.hi,
+    player_address_lo: player.lo,
+    contract_id_hi: contract.hi,
+    contract_id_lo: contract.lo
   };
 }
 
 /**
  * Generate proof and return contract-ready payload.
- * @param {{ run_hash_hex: string, score: number, wave: number, nonce: number, season_id: number }} body
+ * @param {{ run_hash_hex: string, score: number, wave: number, nonce: number, season_id: number, player_address: string, contract_id: string }} body
  * @returns {{ proof: { a, b, c }, vk: { alpha, beta, gamma, delta, ic }, pub_signals: string[] }}
  */
 export function generateProof(body) {
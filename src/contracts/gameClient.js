@@ -5,7 +5,7 @@
  * @see https://github.com/jamesbachini/Stellar-Game-Studio
  */
 
-import { StrKey } from '@stellar/stellar-sdk';
+import { Address, StrKey, hash } from '@stellar/stellar-sdk';
 import { validateGameRules } from '../zk/gameProof.js';
 import { NoirService } from '../services/NoirService.js';
 import { getAssetPath } from '../utils/assetBase.js';
@@ -63,7 +63,7 @@ function safeJson(value) {
   }
 }
 
-async function waitForTx(server, hash, tries = 12, delayMs = 1000) {
+export async function waitForTx(server, hash, tries = 12, delayMs = 1000) {
   let lastSeen = null;
   for (let i = 0; i < tries; i++) {
     try {
@@ -228,7 +228,109 @@ export async function startMatch(signerPublicKey, signTransaction) {
 }
 
 /**
- * Submit result to contract (submit_result(player, wave, score)). Updates casual leaderboard. Requires wallet sign.
+ * Player's open Game Hub session id (from start_match), or null. Submits must reference it.
+ */
+export async function getOpenSession(playerAddress) {
+  if (!getContractId() || !playerAddress) return null;
+  try {
+    const {
+      Contract,
+      TransactionBuilder,
+      Account,
+      BASE_FEE,
+      Address,
+    } = await import('@stellar/stellar-sdk');
+    const server = await getServer();
+    const contract = new Contract(getContractId());
+    const dummyAccount = new Account(
+      'GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA',
+      '0'
+    );
+    const built = new TransactionBuilder(dummyAccount, {
+      fee: BASE_FEE,
+      networkPassphrase: TESTNET_PASSPHRASE,
+    })
+      .addOperation(contract.call('get_open_session', new Address(playerAddress).toScVal()))
+      .setTimeout(TX_VALIDITY_SECONDS)
+      .build();
+    const sim = await server.simulateTransaction(built);
+    if (sim.error) return null;
+    const rv = sim.result?.retval;
+    if (!rv || rv.switch().name !== 'u32') return null;
+    return rv.u32();
+  } catch (_) {
+    return null;
+  }
+}
+
+/**
+ * Nonce the contract will always accept next for this player in `seasonId` (get_next_nonce),
+ * as BigInt, or null. Each season has its own window. Increasing nonces such as Date.now()
+ * also work; reused or >128-behind nonces are rejected.
+ */
+export async function getNextNonce(playerAddress, seasonId) {
+  if (!getContractId() || !playerAddress || seasonId == null) return null;
+  try {
+    const {
+      Contract,
+      TransactionBuilder,
+      Account,
+      BASE_FEE,
+      Address,
+      xdr,
+    } = await import('@stellar/stellar-sdk');
+    const { scValToNative } = await import('@stellar/stellar-base');
+    const server = await getServer();
+    const contract = new Contract(getContractId());
+    const dummyAccount = new Account(
+      'GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA',
+      '0'
+    );
+    const built = new TransactionBuilder(dummyAccount, {
+      fee: BASE_FEE,
+      networkPassphrase: TESTNET_PASSPHRASE,
+    })
+      .addOperation(
+        contract.call('get_next_nonce', new Address(playerAddress).toScVal(), xdr.ScVal.scvU32(seasonId))
+      )
+      .setTimeout(TX_VALIDITY_SECONDS)
+      .build();
+    const sim = await server.simulateTransaction(built);
+    if (sim.error || !sim.result?.retval) return null;
+    return BigInt(scValToNative(sim.result.retval));
+  } catch (_) {
+    return null;
+  }
+}
+
+/** ScVal for the signer's open session; throws if start_match was not called. */
+async function openSessionScVal(signerPublicKey, xdr) {
+  const sessionId = await getOpenSession(signerPublicKey);
+  if (sessionId == null) throw new Error('No open session: call startMatch before submitting');
+  return xdr.ScVal.scvU32(sessionId);
+}
+
+/**
+ * RankedRun struct for submit_zk / submit_zk_noir (mirrors cosmic_coder::RankedRun), in the
+ * signer's open session.
+ */
+async function rankedRunScVal(signerPublicKey, payload, runHashHex, xdr, Address) {
+  return sortedScMap(
+    [
+      { key: 'player', val: new Address(signerPublicKey).toScVal() },
+      { key: 'session_id', val: await openSessionScVal(signerPublicKey, xdr) },
+      { key: 'nonce', val: u64ToScVal(xdr, payload.nonce) },
+      { key: 'run_hash', val: xdr.ScVal.scvBytes(hexToBytes(runHashHex)) },
+      { key: 'season_id', val: xdr.ScVal.scvU32(payload.season_id != null ? payload.season_id : 1) },
+      { key: 'score', val: xdr.ScVal.scvU32(payload.score) },
+      { key: 'wave', val: xdr.ScVal.scvU32(payload.wave) }
+    ],
+    xdr
+  );
+}
+
+/**
+ * Submit result to contract (submit_result(player, session_id, wave, score)). Updates casual leaderboard. Requires wallet sign.
  */
 export async function submitResult(signerPublicKey, signTransaction, wave, score) {
   const contractId = getContractId();
@@ -236,6 +338,7 @@ export async function submitResult(signerPublicKey, signTransaction, wave, score
   const { xdr, ScInt } = await import('@stellar/stellar-sdk');
   const args = [
     await playerScVal(signerPublicKey),
+    await openSessionScVal(signerPublicKey, xdr),
     xdr.ScVal.scvU32(wave),
     new ScInt(BigInt(Math.floor(score)), { type: 'i128' }).toI128(),
   ];
@@ -250,7 +353,8 @@ export async function submitResult(signerPublicKey, signTransaction, wave, score
  * @returns {Promise<{ proof: { a, b, c }, vk: object, pub_signals: string[] }>} hex strings
  */
 export async function requestZkProofV2(baseUrl, payload) {
-  // For submit_zk on Cosmic Coder contract we must use GameRun (7 pub signals), not GameRunV2.
+  // For submit_zk on Cosmic Coder contract we must use GameRun (11 pub signals, bound to
+  // player + contract), not GameRunV2.
   // Keep function name for compatibility with existing callers.
   const proverBase = baseUrl || getZkProverUrl();
   const url = String(proverBase).replace(/\/$/, '') + '/zk/prove';
@@ -265,7 +369,9 @@ export async function requestZkProofV2(baseUrl, payload) {
       wave: payload.wave,
       nonce: payload.nonce != null ? payload.nonce.toString() : payload.nonce,
       season_id: payload.season_id != null ? payload.season_id : 1,
-      used_zk_weapon: payload.used_zk_weapon != null ? payload.used_zk_weapon : 0
+      used_zk_weapon: payload.used_zk_weapon != null ? payload.used_zk_weapon : 0,
+      player_address: payload.player_address,
+      contract_id: payload.contract_id || getContractId()
     })
   });
   if (!res.ok) {
@@ -456,6 +562,7 @@ function normalizeRunHashParts(payload) {
   };
 }
 
+// Mirrored on-chain by noir_run_inputs (contracts/cosmic_coder): keep both folds identical.
 function computeSafeNoirHashInputs(payload) {
   const { fullHex64, hiHex32, loHex32 } = normalizeRunHashParts(payload);
   const maxU128 = (1n << 128n) - 1n;
@@ -622,9 +729,14 @@ export async function submitZkV2(
     proofToScVal(zk.proof, xdr),
     inputsMap, // public_inputs
     xdr.ScVal.scvBytes(hexToBytes(vkHash)),
-    xdr.ScVal.scvU32(Math.max(0, Math.floor(score))),
-    xdr.ScVal.scvU32(wave),
-    xdr.ScVal.scvU32(seasonId)
+    sortedScMap(
+      [
+        { key: 'score', val: xdr.ScVal.scvU32(Math.max(0, Math.floor(score))) },
+        { key: 'wave', val: xdr.ScVal.scvU32(wave) },
+        { key: 'season_id', val: xdr.ScVal.scvU32(seasonId) }
+      ],
+      xdr
+    ) // run (ClaimedRun)
   ];
   return invoke(contractId, 'submit_proof', args, signerPublicKey, signTransaction);
 }
@@ -643,12 +755,25 @@ const toBigInt = (str) => {
   return BigInt(str.startsWith('0x') ? str : '0x' + str);
 };
 
+/**
+ * Address commitment bound in GameRun public signals (mirrors zk_types::address_commitment):
+ * sha256(ScVal XDR of the address) split into 128-bit halves, as decimal strings.
+ */
+function addressCommitmentParts(strkey) {
+  const digest = hash(Address.fromString(strkey).toScVal().toXDR());
+  const hex = Array.from(digest, (b) => b.toString(16).padStart(2, '0')).join('');
+  return {
+    hi: BigInt('0x' + hex.slice(0, 32)).toString(10),
+    lo: BigInt('0x' + hex.slice(32, 64)).toString(10)
+  };
+}
+
 /**
  * TRUSTLESS LOCAL PROVER (like xray-games)
  * Generate ZK proof locally in browser without server dependency.
  * Requires snarkjs loaded and circuit artifacts in public/circuits/build/
  * 
- * @param {{ run_hash_hi: string, run_hash_lo: string, score: number, wave: number, nonce: number, season_id?: number, used_zk_weapon?: number }} payload
+ * @param {{ run_hash_hi: string, run_hash_lo: string, score: number, wave: number, nonce: number, season_id?: number, used_zk_weapon?: number, player_address: string, contract_id?: string }} payload
  * @returns {Promise<{ proof: object, vk: object, pub_signals: string[] }>}
  */
 export async function generateLocalProof(payload) {
@@ -670,6 +795,12 @@ export async function generateLocalProof(payload) {
     season_id: String(payload.season_id || 1),
     used_zk_weapon: String(payload.used_zk_weapon || 0)
   };
+  const player = addressCommitmentParts(payload.player_address);
+  const contract = addressCommitmentParts(payload.contract_id || getContractId());
+  input.player_address_hi = player.hi;
+  input.player_address_lo = player.lo;
+  input.contract_id_hi = contract.hi;
+  input.contract_id_lo = contract.lo;
   
   console.log('[LocalProver] Generating proof with input:', input);
   
@@ -718,23 +849,20 @@ export async function submitZkTrustless(signerPublicKey, signTransaction, payloa
   validateNoirSubmitPayload(payload);
   console.log('[Trustless] Generating Noir + UltraHonk proof in browser...');
   const noir = new NoirService();
-  const { proofBlob } = await noir.generateProof('GameRun', noirInputsFromPayload(payload));
+  const { proofBytes } = await noir.generateProof('GameRun', noirInputsFromPayload(payload));
   const { fullHex64 } = normalizeRunHashParts(payload);
 
-  // Submit to contract: verifier uses stored VK (no vk_json in tx to avoid size limit)
+  // Submit to contract: verifier uses stored VK (no vk_json in tx to avoid size limit).
+  // Only the raw proof is sent; the contract rebuilds the public inputs from the run.
   const contractId = getContractId();
   if (!contractId) throw new Error('Contract ID not configured');
   
   const { xdr, Address } = await import('@stellar/stellar-sdk');
 
   const args = [
-    new Address(signerPublicKey).toScVal(),
-    xdr.ScVal.scvBytes(proofBlob),
-    u64ToScVal(xdr, payload.nonce),
-    xdr.ScVal.scvBytes(hexToBytes(fullHex64)),
-    xdr.ScVal.scvU32(payload.season_id || 1),
-    xdr.ScVal.scvU32(payload.score),
-    xdr.ScVal.scvU32(payload.wave)
+    await rankedRunScVal(signerPublicKey, payload, fullHex64, xdr, Address),
+    xdr.ScVal.scvBytes(proofBytes),
+    xdr.ScVal.scvBool(toU1Safe(payload.used_zk_weapon) === '1')
   ];
   
   console.log('[Trustless] Submitting Noir proof to contract...');
@@ -752,20 +880,16 @@ export async function submitZkTrustless(signerPublicKey, signTransaction, payloa
 export async function submitZkFromProverV2(signerPublicKey, signTransaction, proverUrl, payload, vkHash) {
   validateNoirSubmitPayload(payload);
   const noir = new NoirService();
-  const { proofBlob } = await noir.generateProof('GameRun', noirInputsFromPayload(payload));
+  const { proofBytes } = await noir.generateProof('GameRun', noirInputsFromPayload(payload));
   const { fullHex64 } = normalizeRunHashParts(payload);
   const contractId = getContractId();
   if (!contractId) throw new Error('Cosmic Coder contract not configured');
   const { xdr, Address } = await import('@stellar/stellar-sdk');
 
   const args = [
-    new Address(signerPublicKey).toScVal(),
-    xdr.ScVal.scvBytes(proofBlob),
-    u64ToScVal(xdr, payload.nonce),
-    xdr.ScVal.scvBytes(hexToBytes(fullHex64)),
-    xdr.ScVal.scvU32(payload.season_id != null ? payload.season_id : 1),
-    xdr.ScVal.scvU32(payload.score),
-    xdr.ScVal.scvU32(payload.wave)
+    await rankedRunScVal(signerPublicKey, payload, fullHex64, xdr, Address),
+    xdr.ScVal.scvBytes(proofBytes),
+    xdr.ScVal.scvBool(toU1Safe(payload.used_zk_weapon) === '1')
   ];
   return invoke(contractId, 'submit_zk_noir', args, signerPublicKey, signTransaction);
 }
@@ -827,9 +951,9 @@ export async function getLeaderboard(limit = 10) {
 
 /**
  * Get ranked leaderboard by season (ZK runs). Returns [] if contract not configured or on error.
- * ScoreEntry has { player, score } (no wave).
+ * ScoreEntry has { player, score } (no wave). `offset` pages through the contract's bounded top list.
  */
-export async function getLeaderboardBySeason(seasonId = 1, limit = 10) {
+export async function getLeaderboardBySeason(seasonId = 1, limit = 10, offset = 0) {
   const contractId = getContractId();
   if (!contractId || !isContractIdValid(contractId)) return [];
   try {
@@ -852,7 +976,12 @@ export async function getLeaderboardBySeason(seasonId = 1, limit = 10) {
       fee: BASE_FEE,
       networkPassphrase: TESTNET_PASSPHRASE,
     })
-      .addOperation(contract.call('get_leaderboard_by_season', xdr.ScVal.scvU32(seasonId), xdr.ScVal.scvU32(limit)))
+      .addOperation(contract.call(
+          'get_leaderboard_by_season',
+          xdr.ScVal.scvU32(seasonId),
+          xdr.ScVal.scvU32(offset),
+          xdr.ScVal.scvU32(limit)
+        ))
       .setTimeout(TX_VALIDITY_SECONDS)
       .build();
     const sim = await server.simulateTransaction(built);
@@ -3,7 +3,7 @@
  * Interfaces with CosmicCoder contract for weapon unlocks and player stats
  */
 
-import { getContractId, getZkProverUrl } from './gameClient.js';
+import { getContractId, getZkProverUrl, waitForTx } from './gameClient.js';
 import { WEAPONS, getWeaponById, getTierById } from '../config/weapons.js';
 import { getRankBonus, isUnranked } from '../systems/RankManager.js';
 
@@ -17,6 +17,17 @@ function getCosmicCoderContractId() {
   return getContractId();
 }
 
+/**
+ * Hash of the SkillProof verification key registered on CosmicCoder (register_vk).
+ */
+function getSkillProofVkHash() {
+  return (
+    (typeof window !== 'undefined' && window.__VITE_CONFIG__?.VITE_SKILLPROOF_VK_HASH) ||
+    (typeof import.meta !== 'undefined' && import.meta.env?.VITE_SKILLPROOF_VK_HASH) ||
+    ''
+  );
+}
+
 /**
  * Check if weapon unlock system is configured
  */
@@ -178,7 +189,7 @@ export async function checkWeaponUnlockEligibility(publicKey, weaponId) {
  * Generate ZK proof for weapon unlock
  * This would call the ZK prover to generate a proof that score >= threshold
  * @param {number} score - Player's score (private)
- * @param {string} wallet - Wallet address (private)
+ * @param {string} wallet - Wallet address; the proof is bound to its address commitment
  * @param {number} nonce - Unique nonce (private)
  * @param {number} threshold - Threshold to prove (public)
  * @returns {Promise<{proof: object, publicHash: string}>}
@@ -210,7 +221,50 @@ export async function generateUnlockProof(score, wallet, nonce, threshold) {
 }
 
 /**
- * Unlock weapon on-chain with ZK proof
+ * Prepare, sign and send one CosmicCoder call, then wait for it to be applied.
+ * sendTransaction only queues the transaction (PENDING), so the result is polled until
+ * SUCCESS or FAILED; the next call then builds on the updated account sequence.
+ * @returns {Promise<boolean>} true if the transaction succeeded on-chain
+ */
+async function sendContractCall(publicKey, signTransaction, contractId, method, ...args) {
+  const { Contract, TransactionBuilder, Account, BASE_FEE, rpc } = await import('@stellar/stellar-sdk');
+
+  const server = new rpc.Server(TESTNET_RPC);
+  const source = await server.getAccount(publicKey);
+  const account = new Account(publicKey, String(source.sequence ?? '0'));
+  const contract = new Contract(contractId);
+
+  const built = new TransactionBuilder(account, {
+    fee: BASE_FEE,
+    networkPassphrase: TESTNET_PASSPHRASE,
+  })
+    .addOperation(contract.call(method, ...args))
+    .setTimeout(30)
+    .build();
+  const prepared = await server.prepareTransaction(built);
+
+  const signedXdr = await signTransaction(prepared.toXDR(), TESTNET_PASSPHRASE);
+  const signedTx = TransactionBuilder.fromXDR(signedXdr, TESTNET_PASSPHRASE);
+
+  const sent = await server.sendTransaction(signedTx);
+  if (sent.status !== 'PENDING' || !sent.hash) {
+    console.error(`[WeaponClient] ${method} rejected:`, sent.status, sent.errorResult);
+    return false;
+  }
+  const txInfo = await waitForTx(server, sent.hash, 30, 1000);
+  const status = String(txInfo?.status || '').toUpperCase();
+  if (status !== 'SUCCESS') {
+    console.error(`[WeaponClient] ${method} ${status || 'not confirmed'}:`, sent.hash);
+    return false;
+  }
+  return true;
+}
+
+/**
+ * Unlock weapon on-chain with ZK proof.
+ * The proof's pub_signals are [threshold, publicHash, player_hi, player_lo]; unlock_weapon
+ * only accepts it when player_hi / player_lo are the commitment of `publicKey`, so the proof
+ * must have been generated for this wallet. Each publicHash unlocks once.
  * @param {string} publicKey - Player's Stellar public key
  * @param {function} signTransaction - Function to sign transaction
  * @param {number} weaponId - Weapon ID to unlock
@@ -228,39 +282,26 @@ export async function unlockWeapon(publicKey, signTransaction, weaponId, proof)
     throw new Error('Invalid weapon ID');
   }
 
+  const vkHash = getSkillProofVkHash();
+  if (!vkHash) {
+    throw new Error('VITE_SKILLPROOF_VK_HASH not set (hash returned by register_vk)');
+  }
+
   try {
-    const { Contract, TransactionBuilder, Account, BASE_FEE, xdr } = await import('@stellar/stellar-sdk');
-    const { rpc } = await import('@stellar/stellar-sdk');
-    
-    const server = new rpc.Server(TESTNET_RPC);
-    const source = await server.getAccount(publicKey);
-    const account = new Account(publicKey, String(source.sequence ?? '0'));
-    const contract = new Contract(contractId);
+    const { xdr } = await import('@stellar/stellar-sdk');
 
-    // Build unlock_weapon transaction
-    const op = contract.call(
+    return await sendContractCall(
+      publicKey,
+      signTransaction,
+      contractId,
       'unlock_weapon',
       publicKey,
       weaponId.toString(),
       proof.proof,
-      proof.vk,
+      xdr.ScVal.scvBytes(Uint8Array.from(vkHash.replace(/^0x/, '').match(/../g), (b) => parseInt(b, 16))),
       proof.pub_signals,
       weapon.tier.threshold.toString()
     );
-
-    const tx = new TransactionBuilder(account, {
-      fee: BASE_FEE,
-      networkPassphrase: TESTNET_PASSPHRASE,
-    })
-      .addOperation(op)
-      .setTimeout(30)
-      .build();
-
-    const signedXdr = await signTransaction(tx.toXDR());
-    const signedTx = xdr.TransactionEnvelope.fromXDR(signedXdr, 'base64');
-    
-    const result = await server.sendTransaction(signedTx);
-    return result.status === 'SUCCESS';
   } catch (e) {
     console.error('[WeaponClient] Failed to unlock weapon:', e);
     throw e;
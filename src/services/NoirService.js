@@ -22,7 +22,7 @@ export class NoirService {
     const { proofBlob, proofId } = this.buildProofBlob(publicInputs, proofBytes);
 
     const vkJson = await this.loadVk(circuitName);
-    return { vkJson, proofBlob, proofId };
+    return { vkJson, proofBlob, proofBytes, proofId };
   }
 
   async loadVk(circuitName) {